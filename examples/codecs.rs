@@ -0,0 +1,74 @@
+//! Compares the compression ratio and speed of the F64 stream codecs on a few synthetic series.
+//!
+//! Run with `cargo run --release --example codecs --features std`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use float_bits::F64;
+use float_bits::codec::Codec;
+
+const LEN: usize = 1 << 20;
+
+fn series() -> Vec<(&'static str, Vec<F64>)> {
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    let mut random = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    let sensor = (0..LEN)
+        .map(|i| (20.0 + 5.0 * (i as f64 / 500.0).sin()) * 100.0)
+        .map(|x| x.round() / 100.0);
+    let mut price = 100.0;
+    let walk: Vec<f64> = (0..LEN)
+        .map(|_| {
+            price += (random() % 201) as f64 / 100.0 - 1.0;
+            price
+        })
+        .collect();
+    let cycle = (0..LEN).map(|i| [0.5, 1.25, 3.0, 0.5, 7.75][i % 5]);
+    let noise = (0..LEN).map(|_| f64::from_bits(random() >> 2));
+
+    vec![
+        ("sensor", sensor.map(F64::from_float).collect()),
+        ("random walk", walk.into_iter().map(F64::from_float).collect()),
+        ("cycle", cycle.map(F64::from_float).collect()),
+        ("noise", noise.map(F64::from_float).collect()),
+    ]
+}
+
+fn main() {
+    println!(
+        "{:<12} {:<9} {:>10} {:>12} {:>12}",
+        "series", "codec", "bits/value", "encode MB/s", "decode MB/s"
+    );
+    for (name, values) in series() {
+        for codec in [Codec::Gorilla, Codec::Chimp128, Codec::Alp] {
+            let start = Instant::now();
+            let bytes = black_box(codec.encode(black_box(&values)));
+            let encode = start.elapsed();
+
+            let start = Instant::now();
+            let mut count = 0;
+            for value in codec.decode(&bytes) {
+                black_box(value.expect("round trip"));
+                count += 1;
+            }
+            let decode = start.elapsed();
+            assert_eq!(values.len(), count);
+
+            let megabytes = (values.len() * 8) as f64 / 1e6;
+            println!(
+                "{:<12} {:<9} {:>10.2} {:>12.0} {:>12.0}",
+                name,
+                format!("{codec:?}"),
+                (bytes.len() * 8) as f64 / values.len() as f64,
+                megabytes / encode.as_secs_f64(),
+                megabytes / decode.as_secs_f64(),
+            );
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! Compares `radix_sort_total` with `sort_unstable` on random slices of each width.
+//!
+//! Run with `cargo run --release --example radix_sort --features std`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use float_bits::{BF16, F32, F64, F128, FloatBits, radix_sort_total};
+
+const LEN: usize = 5_000_000;
+
+fn time(mut f: impl FnMut()) -> Duration {
+    // The best of a few runs, to keep noise from other processes out.
+    (0..3)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .min()
+        .expect("at least one run")
+}
+
+fn compare<T: FloatBits>(name: &str, values: Vec<T>) {
+    let radix = time(|| {
+        let mut xs = values.clone();
+        radix_sort_total(black_box(&mut xs));
+        black_box(xs);
+    });
+    let comparison = time(|| {
+        let mut xs = values.clone();
+        black_box(&mut xs).sort_unstable();
+        black_box(xs);
+    });
+    println!(
+        "{:<6} {:>12.1} {:>12.1} {:>8.2}",
+        name,
+        radix.as_secs_f64() * 1e3,
+        comparison.as_secs_f64() * 1e3,
+        comparison.as_secs_f64() / radix.as_secs_f64(),
+    );
+}
+
+fn main() {
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    let mut random = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    println!("{:<6} {:>12} {:>12} {:>8}", "type", "radix ms", "sort ms", "speedup");
+    compare("BF16", (0..LEN).map(|_| BF16::from_bits(random() as u16)).collect());
+    compare("F32", (0..LEN).map(|_| F32::from_bits(random() as u32)).collect());
+    compare("F64", (0..LEN).map(|_| F64::from_bits(random())).collect());
+    let wide = (0..LEN).map(|_| F128::from_bits(u128::from(random()) << 64 | u128::from(random())));
+    compare("F128", wide.collect());
+}
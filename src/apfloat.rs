@@ -0,0 +1,58 @@
+//! Conversions to and from the soft floats of `rustc_apfloat`.
+//!
+//! Both sides store IEEE 754 bits, so each conversion is a bit-for-bit copy: NaN payloads and the
+//! sign of zero carry over, and nothing is rounded.  [`BF16`] pairs with `BFloat`, [`F16`] with
+//! `Half`, [`F32`] with `Single`, [`F64`] with `Double`, and [`F128`] with `Quad`.
+
+use rustc_apfloat::Float;
+use rustc_apfloat::ieee::{BFloat, Double, Half, Quad, Single};
+
+use crate::{BF16, F16, F32, F64, F128};
+
+macro_rules! conversions {
+    ($( $ty:ident / $ap_ty:ident ),*) => {
+        $(
+            impl From<$ty> for $ap_ty {
+                fn from(val: $ty) -> Self {
+                    $ap_ty::from_bits(val.to_bits() as u128)
+                }
+            }
+
+            impl From<$ap_ty> for $ty {
+                fn from(val: $ap_ty) -> Self {
+                    $ty::from_bits(val.to_bits() as _)
+                }
+            }
+        )*
+    };
+}
+
+conversions!(BF16 / BFloat, F16 / Half, F32 / Single, F64 / Double, F128 / Quad);
+
+#[cfg(test)]
+mod tests {
+    use rustc_apfloat::ieee::{BFloat, Double, Half, Quad, Single};
+    use rustc_apfloat::{Float, Round};
+
+    use crate::{BF16, F16, F32, F64, F128};
+
+    #[test]
+    fn conversions_test() {
+        assert_eq!(BF16::ONE, BFloat::from_u128(1).value.into());
+        assert_eq!(F16::MAX, Half::largest().into());
+        assert_eq!(F32::NEG_ZERO, (-Single::ZERO).into());
+        assert_eq!(F128::INFINITY, Quad::INFINITY.into());
+
+        // Arithmetic in apfloat, storage in this crate.
+        let third = (Double::from(F64::ONE) / Double::from(F64::from_float(3.0))).value;
+        assert_eq!(F64::from_float(1.0 / 3.0), third.into());
+        let sum =
+            Quad::from(F128::ONE).add_r(Quad::from(F128::from_bits(1)), Round::TowardPositive);
+        assert_eq!(F128::from_bits(F128::ONE.to_bits() + 1), sum.value.into());
+
+        let nan = F64::from_bits(0xfff4000000000123);
+        let ap = Double::from(nan);
+        assert!(ap.is_signaling() && ap.is_negative());
+        assert_eq!(nan, ap.into());
+    }
+}
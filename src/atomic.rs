@@ -0,0 +1,250 @@
+//! Atomic cells holding the bits of a float, for targets with atomic integers of the same width.
+
+use core::fmt;
+use core::sync::atomic::Ordering;
+
+/// Returns the ordering for the loads of a read-modify-write loop whose store uses `order`.
+const fn load_ordering(order: Ordering) -> Ordering {
+    match order {
+        Ordering::Release | Ordering::Relaxed => Ordering::Relaxed,
+        Ordering::SeqCst => Ordering::SeqCst,
+        _ => Ordering::Acquire,
+    }
+}
+
+macro_rules! define_atomic {
+    ($name:ident; $ty:ident; $u_ty:ty; $atomic:ident; $width:literal) => {
+        #[doc = concat!("An [`", stringify!($ty), "`](crate::", stringify!($ty), ") that can be shared between")]
+        #[doc = concat!("threads, stored as an [`", stringify!($atomic), "`](core::sync::atomic::", stringify!($atomic), ").")]
+        ///
+        /// The operations take the same [`Ordering`] arguments as those of the integer atomics,
+        /// and the value is stored unchanged, bits included.  [`fetch_max_total`] and
+        /// [`fetch_min_total`] order values by [`total_cmp`], so that a NaN is stored only if it
+        /// is greater or less than every other value in that order, as a positive or negative NaN.
+        ///
+        #[doc = concat!("[`total_cmp`]: crate::", stringify!($ty), "::total_cmp")]
+        /// [`fetch_max_total`]: Self::fetch_max_total
+        /// [`fetch_min_total`]: Self::fetch_min_total
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        #[doc = concat!("# use float_bits::{", stringify!($name), ", ", stringify!($ty), "};")]
+        /// # use core::sync::atomic::Ordering;
+        ///
+        #[doc = concat!("let max = ", stringify!($name), "::new(", stringify!($ty), "::NEG_INFINITY);")]
+        /// for x in [1.5, -2.0, 4.25] {
+        #[doc = concat!("    max.fetch_max_total(", stringify!($ty), "::from_float(x), Ordering::Relaxed);")]
+        /// }
+        #[doc = concat!("assert_eq!(", stringify!($ty), "::from_float(4.25), max.load(Ordering::Relaxed));")]
+        /// ```
+        #[cfg(target_has_atomic = $width)]
+        #[derive(Default)]
+        #[repr(transparent)]
+        pub struct $name(core::sync::atomic::$atomic);
+
+        #[cfg(target_has_atomic = $width)]
+        impl $name {
+            /// Creates a cell holding `value`.
+            pub const fn new(value: crate::$ty) -> Self {
+                Self(core::sync::atomic::$atomic::new(value.to_bits()))
+            }
+
+            /// Consumes the cell and returns its value.
+            pub fn into_inner(self) -> crate::$ty {
+                crate::$ty::from_bits(self.0.into_inner())
+            }
+
+            /// Returns a mutable reference to the value, which needs no synchronization since the
+            /// cell is borrowed mutably.
+            pub fn get_mut(&mut self) -> &mut crate::$ty {
+                // SAFETY: the float is `repr(transparent)` over its bits, and every bit pattern is
+                // valid for both.
+                unsafe { &mut *(self.0.get_mut() as *mut $u_ty as *mut crate::$ty) }
+            }
+
+            /// Loads the value.
+            pub fn load(&self, order: Ordering) -> crate::$ty {
+                crate::$ty::from_bits(self.0.load(order))
+            }
+
+            /// Stores `value`.
+            pub fn store(&self, value: crate::$ty, order: Ordering) {
+                self.0.store(value.to_bits(), order)
+            }
+
+            /// Stores `value`, returning the previous value.
+            pub fn swap(&self, value: crate::$ty, order: Ordering) -> crate::$ty {
+                crate::$ty::from_bits(self.0.swap(value.to_bits(), order))
+            }
+
+            /// Stores `new` if the value has the same bits as `current`, returning the previous
+            /// value, or the value as an error if it did not match.
+            ///
+            /// The comparison is by bits: `-0.0` does not match `+0.0`, and a NaN matches itself.
+            pub fn compare_exchange(
+                &self,
+                current: crate::$ty,
+                new: crate::$ty,
+                success: Ordering,
+                failure: Ordering,
+            ) -> Result<crate::$ty, crate::$ty> {
+                self.0
+                    .compare_exchange(current.to_bits(), new.to_bits(), success, failure)
+                    .map(crate::$ty::from_bits)
+                    .map_err(crate::$ty::from_bits)
+            }
+
+            /// Like [`compare_exchange`](Self::compare_exchange), but may fail spuriously, which
+            /// is faster on some platforms when called in a loop.
+            pub fn compare_exchange_weak(
+                &self,
+                current: crate::$ty,
+                new: crate::$ty,
+                success: Ordering,
+                failure: Ordering,
+            ) -> Result<crate::$ty, crate::$ty> {
+                self.0
+                    .compare_exchange_weak(current.to_bits(), new.to_bits(), success, failure)
+                    .map(crate::$ty::from_bits)
+                    .map_err(crate::$ty::from_bits)
+            }
+
+            /// Stores the greater of the value and `value` by [`total_cmp`], returning the
+            /// previous value.
+            ///
+            #[doc = concat!("[`total_cmp`]: crate::", stringify!($ty), "::total_cmp")]
+            pub fn fetch_max_total(&self, value: crate::$ty, order: Ordering) -> crate::$ty {
+                self.fetch_update_total(value, order, core::cmp::Ordering::Less)
+            }
+
+            /// Stores the lesser of the value and `value` by [`total_cmp`], returning the
+            /// previous value.
+            ///
+            #[doc = concat!("[`total_cmp`]: crate::", stringify!($ty), "::total_cmp")]
+            pub fn fetch_min_total(&self, value: crate::$ty, order: Ordering) -> crate::$ty {
+                self.fetch_update_total(value, order, core::cmp::Ordering::Greater)
+            }
+
+            /// Adds `value` to the value, returning the previous value.
+            ///
+            /// The addition is that of the native float, rounded to nearest, retried in a
+            /// compare-exchange loop until no other thread has changed the value in between.  The
+            /// loop compares bits rather than floats, so it ends even when the value is NaN.
+            pub fn fetch_add(&self, value: crate::$ty, order: Ordering) -> crate::$ty {
+                let result = self.0.fetch_update(order, load_ordering(order), |bits| {
+                    Some((crate::$ty::from_bits(bits) + value).to_bits())
+                });
+                match result {
+                    Ok(bits) | Err(bits) => crate::$ty::from_bits(bits),
+                }
+            }
+
+            // Stores `value` if the current value compares to it as `replace`.
+            fn fetch_update_total(
+                &self,
+                value: crate::$ty,
+                order: Ordering,
+                replace: core::cmp::Ordering,
+            ) -> crate::$ty {
+                let result = self.0.fetch_update(order, load_ordering(order), |bits| {
+                    let current = crate::$ty::from_bits(bits);
+                    (current.total_cmp(value) == replace).then_some(value.to_bits())
+                });
+                match result {
+                    Ok(bits) | Err(bits) => crate::$ty::from_bits(bits),
+                }
+            }
+        }
+
+        #[cfg(target_has_atomic = $width)]
+        impl From<crate::$ty> for $name {
+            fn from(value: crate::$ty) -> Self {
+                Self::new(value)
+            }
+        }
+
+        #[cfg(target_has_atomic = $width)]
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(&self.load(Ordering::Relaxed), f)
+            }
+        }
+    };
+}
+
+define_atomic!(AtomicF32Bits; F32; u32; AtomicU32; "32");
+define_atomic!(AtomicF64Bits; F64; u64; AtomicU64; "64");
+
+#[cfg(test)]
+mod tests {
+    use core::sync::atomic::Ordering::*;
+
+    use super::*;
+    use crate::{F32, F64};
+
+    #[cfg(target_has_atomic = "32")]
+    #[test]
+    fn atomic_f32_test() {
+        let mut x = AtomicF32Bits::new(F32::ONE);
+        assert_eq!(F32::ONE, x.swap(F32::NEG_ZERO, Relaxed));
+        assert_eq!(Err(F32::NEG_ZERO), x.compare_exchange(F32::ZERO, F32::ONE, SeqCst, SeqCst));
+        assert_eq!(Ok(F32::NEG_ZERO), x.compare_exchange(F32::NEG_ZERO, F32::NAN, SeqCst, SeqCst));
+        assert_eq!(F32::NAN.to_bits(), x.load(Acquire).to_bits());
+        *x.get_mut() = F32::MAX;
+        x.store(F32::from_float(2.5), Release);
+        assert_eq!(F32::from_float(2.5), x.into_inner());
+    }
+
+    #[cfg(target_has_atomic = "64")]
+    #[test]
+    fn fetch_max_min_total_test() {
+        let x = AtomicF64Bits::from(F64::ZERO);
+        assert_eq!(F64::ZERO, x.fetch_max_total(F64::NEG_ZERO, AcqRel));
+        assert_eq!(F64::ZERO, x.fetch_max_total(F64::ONE, AcqRel));
+        assert_eq!(F64::ONE, x.fetch_min_total(F64::NEG_ZERO, Release));
+        assert_eq!(F64::NEG_ZERO, x.fetch_min_total(F64::NEG_QNAN, Relaxed));
+        assert_eq!(F64::NEG_QNAN.to_bits(), x.fetch_max_total(F64::INFINITY, SeqCst).to_bits());
+        assert_eq!(F64::INFINITY, x.load(Relaxed));
+    }
+
+    #[cfg(all(feature = "std", target_has_atomic = "64"))]
+    #[test]
+    fn threads_test() {
+        let max = AtomicF64Bits::new(F64::NEG_INFINITY);
+        std::thread::scope(|s| {
+            for t in 0..4 {
+                let max = &max;
+                s.spawn(move || {
+                    for i in 0..1000 {
+                        max.fetch_max_total(F64::from_float((i * 4 + t) as f64), Relaxed);
+                    }
+                });
+            }
+        });
+        assert_eq!(F64::from_float(3999.0), max.into_inner());
+
+        let sum = AtomicF32Bits::new(F32::ZERO);
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for _ in 0..1000 {
+                        sum.fetch_add(F32::from_float(0.5), Relaxed);
+                    }
+                });
+            }
+        });
+        assert_eq!(F32::from_float(2000.0), sum.into_inner());
+    }
+
+    #[cfg(target_has_atomic = "64")]
+    #[test]
+    fn fetch_add_test() {
+        let x = AtomicF64Bits::new(F64::ONE);
+        assert_eq!(F64::ONE, x.fetch_add(F64::from_float(0.25), AcqRel));
+        assert_eq!(F64::from_float(1.25), x.fetch_add(F64::NEG_INFINITY, SeqCst));
+        assert_eq!(F64::NEG_INFINITY, x.fetch_add(F64::INFINITY, Relaxed));
+        assert!(x.fetch_add(F64::ONE, Relaxed).is_nan());
+        assert!(x.load(Relaxed).is_nan());
+    }
+}
@@ -0,0 +1,483 @@
+//! Adaptive lossless compression for [`F64`] columns, after the ALP scheme of Afroozeh and Boncz.
+//!
+//! Most real-world doubles started out as decimals with a few digits, such as prices or sensor
+//! readings.  For such a value `v` there are small exponents `e` and `f` such that
+//! `n = round(v × 10^e / 10^f)` is an integer and `n × 10^f / 10^e` gives back exactly `v`.  The
+//! integers are then stored as offsets from their minimum, bit-packed to the width of the largest
+//! offset.  Values that do not come back exactly, like NaN, infinities, `-0.0`, and values with
+//! too many digits, are stored in full as exceptions.
+//!
+//! Values are encoded in blocks of up to 1024.  The exponents of each block are chosen by trying
+//! every pair with `0 ≤ f ≤ e ≤ 18` on a sample of 32 values.  When more than an eighth of the
+//! values of a block are exceptions, the data is not decimal-like, and the block is encoded with
+//! [Gorilla](super::gorilla) XOR coding instead.
+//!
+//! Every block is a whole number of bytes, and all integers are little-endian:
+//!
+//! * An ALP block is a `0` byte, the number of values as a `u16`, `e` and `f` as bytes, the
+//!   minimum as an `i64`, the bit width as a byte, the offsets packed most significant bit first
+//!   and padded to a whole byte, the number of exceptions as a `u16`, then each exception as its
+//!   position in the block as a `u16` followed by its bits as a `u64`, in increasing order of
+//!   position.  The offset stored at the position of an exception is ignored.
+//! * An XOR block is a `1` byte, the number of values as a `u16`, the length of the Gorilla stream
+//!   as a `u32`, then the Gorilla stream.
+//!
+//! The stream is the sequence of blocks, and an empty stream is empty.  The bit patterns are
+//! preserved exactly, NaN payloads and the sign of zero included.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use super::BitWriter;
+use super::{BitReader, DecodeError, DecodeErrorKind, gorilla};
+use crate::F64;
+
+/// The greatest number of values in a block.
+#[cfg(feature = "alloc")]
+const BLOCK_LEN: usize = 1024;
+
+/// The number of values of a block on which the exponents are chosen.
+#[cfg(feature = "alloc")]
+const SAMPLES: usize = 32;
+
+const KIND_ALP: u8 = 0;
+const KIND_XOR: u8 = 1;
+
+const POW10: [f64; 19] = [
+    1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13, 1e14, 1e15, 1e16,
+    1e17, 1e18,
+];
+
+/// Returns `n × 10^f / 10^e`.
+// Dividing rather than multiplying by 10^-e rounds correctly, so that a decimal with up to 18
+// fraction digits comes back exactly as parsed.
+const fn decode_value(n: i64, e: usize, f: usize) -> f64 {
+    n as f64 * POW10[f] / POW10[e]
+}
+
+/// Returns the integer that decodes to exactly `v` with exponents `e` and `f`, if there is one.
+#[cfg(feature = "alloc")]
+fn encode_value(v: f64, e: usize, f: usize) -> Option<i64> {
+    // Adding and subtracting 1.5 × 2^52 rounds to an integer, ties to even.
+    const MAGIC: f64 = 6755399441055744.0;
+    let x = v * POW10[e] / POW10[f];
+    if x.is_nan() || x.abs() >= (1u64 << 51) as f64 {
+        return None;
+    }
+    let n = ((x + MAGIC) - MAGIC) as i64;
+    (decode_value(n, e, f).to_bits() == v.to_bits()).then_some(n)
+}
+
+/// Returns the exponents `(e, f)` that encode the sample of `values` in the fewest bits.
+#[cfg(feature = "alloc")]
+fn choose_exponents(values: &[F64]) -> (usize, usize) {
+    let samples = values.len().min(SAMPLES);
+    let mut best = (0, 0);
+    let mut best_cost = usize::MAX;
+    for e in 0..POW10.len() {
+        for f in 0..=e {
+            let mut exceptions = 0;
+            let (mut min, mut max) = (i64::MAX, i64::MIN);
+            for i in 0..samples {
+                match encode_value(values[i * values.len() / samples].to_float(), e, f) {
+                    Some(n) => (min, max) = (min.min(n), max.max(n)),
+                    None => exceptions += 1,
+                }
+            }
+            let width = 64 - (max.wrapping_sub(min) as u64).leading_zeros() as usize;
+            let cost = samples * width + exceptions * 80;
+            if cost < best_cost {
+                best = (e, f);
+                best_cost = cost;
+            }
+        }
+    }
+    best
+}
+
+/// Appends the encoding of the block `values` to `out`.
+#[cfg(feature = "alloc")]
+fn encode_block(values: &[F64], out: &mut Vec<u8>) {
+    let (e, f) = choose_exponents(values);
+    let ints: Vec<Option<i64>> = values.iter().map(|v| encode_value(v.to_float(), e, f)).collect();
+    let exceptions = ints.iter().filter(|n| n.is_none()).count();
+    let len = (values.len() as u16).to_le_bytes();
+
+    if exceptions * 8 > values.len() {
+        out.push(KIND_XOR);
+        out.extend_from_slice(&len);
+        let stream = gorilla::encode(values);
+        out.extend_from_slice(&(stream.len() as u32).to_le_bytes());
+        out.extend_from_slice(&stream);
+        return;
+    }
+
+    out.push(KIND_ALP);
+    out.extend_from_slice(&len);
+    let min = ints.iter().flatten().copied().min().unwrap_or(0);
+    let max = ints.iter().flatten().copied().max().unwrap_or(0);
+    let width = 64 - (max.wrapping_sub(min) as u64).leading_zeros();
+    out.extend_from_slice(&[e as u8, f as u8]);
+    out.extend_from_slice(&min.to_le_bytes());
+    out.push(width as u8);
+    let mut packed = BitWriter::new();
+    for n in &ints {
+        packed.write(n.unwrap_or(min).wrapping_sub(min) as u64, width);
+    }
+    out.extend_from_slice(&packed.finish());
+    out.extend_from_slice(&(exceptions as u16).to_le_bytes());
+    for (i, value) in values.iter().enumerate().filter(|(i, _)| ints[*i].is_none()) {
+        out.extend_from_slice(&(i as u16).to_le_bytes());
+        out.extend_from_slice(&value.to_bits().to_le_bytes());
+    }
+}
+
+/// A streaming ALP encoder.
+///
+/// # Features
+///
+/// Available only with feature: `alloc`.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default)]
+pub struct Encoder {
+    out: Vec<u8>,
+    block: Vec<F64>,
+}
+
+#[cfg(feature = "alloc")]
+impl Encoder {
+    /// Creates an encoder for an empty stream.
+    pub const fn new() -> Self {
+        Self { out: Vec::new(), block: Vec::new() }
+    }
+
+    /// Appends `value` to the stream.
+    pub fn push(&mut self, value: F64) {
+        self.block.push(value);
+        if self.block.len() == BLOCK_LEN {
+            encode_block(&self.block, &mut self.out);
+            self.block.clear();
+        }
+    }
+
+    /// Ends the stream and returns its bytes.
+    pub fn finish(mut self) -> Vec<u8> {
+        if !self.block.is_empty() {
+            encode_block(&self.block, &mut self.out);
+        }
+        self.out
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Extend<F64> for Encoder {
+    fn extend<I: IntoIterator<Item = F64>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+/// Encodes `values` as an ALP stream.
+///
+/// # Features
+///
+/// Available only with feature: `alloc`.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::F64;
+/// # use float_bits::codec::alp;
+///
+/// let prices: Vec<F64> = (1999..2099).map(|i| F64::from_float(i as f64 / 100.0)).collect();
+/// let bytes = alp::encode(&prices);
+/// assert!(bytes.len() < 100 * 2);
+/// let decoded: Result<Vec<F64>, _> = alp::decode(&bytes).collect();
+/// assert_eq!(prices, decoded.unwrap());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode(values: &[F64]) -> Vec<u8> {
+    let mut encoder = Encoder::new();
+    encoder.extend(values.iter().copied());
+    encoder.finish()
+}
+
+/// Reads the fields of a block, checking their bounds.
+struct Cursor<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], DecodeError> {
+        let bytes = self
+            .input
+            .get(self.pos..self.pos + n)
+            .ok_or(DecodeError::new(DecodeErrorKind::UnexpectedEnd, self.input.len() * 8))?;
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn invalid(&self, pos: usize) -> DecodeError {
+        DecodeError::new(DecodeErrorKind::Invalid, pos * 8)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Block<'a> {
+    Alp {
+        len: usize,
+        index: usize,
+        e: usize,
+        f: usize,
+        min: i64,
+        width: u32,
+        packed: BitReader<'a>,
+        // The exceptions not yet reached, 10 bytes each.
+        exceptions: &'a [u8],
+    },
+    Xor {
+        remaining: usize,
+        values: gorilla::Decoder<'a>,
+        // The offset of the Gorilla stream in the input, in bytes.
+        start: usize,
+    },
+}
+
+/// A streaming ALP decoder, which yields the values of a stream in order.
+///
+/// After the end of the stream or an error, the decoder yields `None`.
+#[derive(Debug, Clone)]
+pub struct Decoder<'a> {
+    input: &'a [u8],
+    // The offset of the next block in the input, in bytes.
+    pos: usize,
+    block: Option<Block<'a>>,
+    done: bool,
+}
+
+impl<'a> Decoder<'a> {
+    /// Creates a decoder for the stream in `bytes`.
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self { input: bytes, pos: 0, block: None, done: false }
+    }
+
+    /// Reads the header of the block at `self.pos`, and moves past the block.
+    fn read_block(&mut self) -> Result<Block<'a>, DecodeError> {
+        let mut cursor = Cursor { input: self.input, pos: self.pos };
+        let [kind] = cursor.take_array()?;
+        let len = u16::from_le_bytes(cursor.take_array()?) as usize;
+        if len == 0 {
+            return Err(cursor.invalid(self.pos + 1));
+        }
+        let block = match kind {
+            KIND_ALP => {
+                let [e, f] = cursor.take_array().map(|ef| ef.map(usize::from))?;
+                if e >= POW10.len() || f > e {
+                    return Err(cursor.invalid(self.pos + 3));
+                }
+                let min = i64::from_le_bytes(cursor.take_array()?);
+                let [width] = cursor.take_array()?;
+                if width > 64 {
+                    return Err(cursor.invalid(cursor.pos - 1));
+                }
+                let packed = BitReader::new(cursor.take((len * width as usize).div_ceil(8))?);
+                let count = u16::from_le_bytes(cursor.take_array()?) as usize;
+                let start = cursor.pos;
+                let exceptions = cursor.take(count * 10)?;
+                let mut next = 0;
+                for (i, exception) in exceptions.chunks_exact(10).enumerate() {
+                    let position = u16::from_le_bytes([exception[0], exception[1]]) as usize;
+                    if position < next || position >= len {
+                        return Err(cursor.invalid(start + i * 10));
+                    }
+                    next = position + 1;
+                }
+                Block::Alp { len, index: 0, e, f, min, width: width as u32, packed, exceptions }
+            },
+            KIND_XOR => {
+                let stream_len = u32::from_le_bytes(cursor.take_array()?) as usize;
+                let start = cursor.pos;
+                let values = gorilla::decode(cursor.take(stream_len)?);
+                Block::Xor { remaining: len, values, start }
+            },
+            _ => return Err(cursor.invalid(self.pos)),
+        };
+        self.pos = cursor.pos;
+        Ok(block)
+    }
+
+    fn next_bits(&mut self) -> Result<Option<u64>, DecodeError> {
+        loop {
+            let block = match &mut self.block {
+                Some(block) => block,
+                None if self.pos == self.input.len() => return Ok(None),
+                None => {
+                    let block = self.read_block()?;
+                    self.block.insert(block)
+                },
+            };
+            match block {
+                Block::Alp { len, index, .. } if index == len => {},
+                Block::Alp { index, e, f, min, width, packed, exceptions, .. } => {
+                    let n = min.wrapping_add(packed.read(*width)? as i64);
+                    let position = match exceptions {
+                        [lo, hi, ..] => u16::from_le_bytes([*lo, *hi]) as usize,
+                        _ => usize::MAX,
+                    };
+                    *index += 1;
+                    if position == *index - 1 {
+                        let bits = u64::from_le_bytes(exceptions[2..10].try_into().unwrap());
+                        *exceptions = &exceptions[10..];
+                        return Ok(Some(bits));
+                    }
+                    return Ok(Some(decode_value(n, *e, *f).to_bits()));
+                },
+                Block::Xor { remaining, values, start } => {
+                    let offset = *start * 8;
+                    let result = values
+                        .next()
+                        .transpose()
+                        .map_err(|err| DecodeError::new(err.kind(), offset + err.offset()))?;
+                    match (result, *remaining) {
+                        (None, 0) => {},
+                        (Some(value), 1..) => {
+                            *remaining -= 1;
+                            return Ok(Some(value.to_bits()));
+                        },
+                        _ => return Err(DecodeError::new(DecodeErrorKind::Invalid, offset)),
+                    }
+                },
+            }
+            self.block = None;
+        }
+    }
+}
+
+impl Iterator for Decoder<'_> {
+    type Item = Result<F64, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.next_bits() {
+            Ok(Some(bits)) => Some(Ok(F64::from_bits(bits))),
+            Ok(None) => {
+                self.done = true;
+                None
+            },
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            },
+        }
+    }
+}
+
+impl core::iter::FusedIterator for Decoder<'_> {}
+
+/// Returns a decoder for the ALP stream in `bytes`.
+pub const fn decode(bytes: &[u8]) -> Decoder<'_> {
+    Decoder::new(bytes)
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    fn round_trip(values: &[F64]) -> Vec<u8> {
+        let bytes = encode(values);
+        let decoded: Vec<F64> = decode(&bytes).map(Result::unwrap).collect();
+        assert_eq!(values, decoded);
+        bytes
+    }
+
+    #[test]
+    fn encode_value_test() {
+        assert_eq!(Some(1999), encode_value(19.99, 2, 0));
+        assert_eq!(None, encode_value(19.99, 1, 0));
+        assert_eq!(Some(12), encode_value(1200.0, 0, 2));
+        assert_eq!(Some(-5), encode_value(-0.5, 1, 0));
+        assert_eq!(None, encode_value(-0.0, 0, 0));
+        assert_eq!(None, encode_value(f64::NAN, 0, 0));
+        assert_eq!(None, encode_value(1e300, 0, 0));
+        assert_eq!(None, encode_value(0.1 + 0.2, 2, 0));
+    }
+
+    #[test]
+    fn alp_block_test() {
+        let values = [1.5, 2.25, 3.0, 1.75, 2.0, 2.5, 1.25, 2.75, -0.0].map(F64::from_float);
+        let bytes = round_trip(&values);
+        let mut expected = vec![KIND_ALP, 9, 0, 2, 0];
+        expected.extend_from_slice(&125i64.to_le_bytes());
+        expected.extend_from_slice(&[8, 25, 100, 175, 50, 75, 125, 0, 150, 0]);
+        expected.extend_from_slice(&[1, 0, 8, 0]);
+        expected.extend_from_slice(&(-0.0f64).to_bits().to_le_bytes());
+        assert_eq!(expected, bytes);
+    }
+
+    #[test]
+    fn round_trip_test() {
+        assert!(round_trip(&[]).is_empty());
+
+        let prices: Vec<F64> =
+            (0..5000).map(|i| F64::from_float(((i * 7919) % 10000) as f64 / 100.0)).collect();
+        let bytes = round_trip(&prices);
+        assert!(bytes.len() < prices.len() * 2, "{}", bytes.len());
+        assert_eq!(KIND_ALP, bytes[0]);
+
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let noise: Vec<F64> = (0..3000)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                F64::from_bits(state >> (state % 64))
+            })
+            .collect();
+        let bytes = round_trip(&noise);
+        assert_eq!(KIND_XOR, bytes[0]);
+
+        let mut mixed = prices[..1000].to_vec();
+        mixed.extend([F64::NAN, F64::NEG_INFINITY, F64::NEG_ZERO, F64::from_float(0.1 + 0.2)]);
+        mixed.extend_from_slice(&noise[..30]);
+        round_trip(&mixed);
+        round_trip(&[F64::MAX, F64::MIN, F64::from_bits(1)]);
+    }
+
+    #[test]
+    fn error_test() {
+        let values: Vec<F64> = (0..50).map(|i| F64::from_float(i as f64 * 0.5)).collect();
+        let mut values_with_exception = values.clone();
+        values_with_exception[3] = F64::NAN;
+        for values in [&values[..], &values_with_exception] {
+            let bytes = encode(values);
+            for n in 1..bytes.len() {
+                let err = decode(&bytes[..n]).find_map(Result::err).unwrap();
+                assert_eq!(DecodeErrorKind::UnexpectedEnd, err.kind(), "{n}");
+            }
+        }
+
+        let bytes = [7, 1, 0];
+        assert_eq!(DecodeErrorKind::Invalid, decode(&bytes).next().unwrap().unwrap_err().kind());
+
+        // A Gorilla stream with fewer values than the block claims.
+        let mut bytes = vec![KIND_XOR, 2, 0];
+        let stream = gorilla::encode(&[F64::ONE]);
+        bytes.extend_from_slice(&(stream.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&stream);
+        let err = decode(&bytes).nth(1).unwrap().unwrap_err();
+        assert_eq!(DecodeError::new(DecodeErrorKind::Invalid, 56), err);
+    }
+}
@@ -0,0 +1,142 @@
+//! The `BYTE_STREAM_SPLIT` encoding of Apache Parquet, for slices of any format.
+//!
+//! The encoding does not compress by itself.  It splits the little-endian bytes of the values
+//! into one stream per byte position, so that the sign and exponent bytes, which vary little, end
+//! up next to each other instead of interleaved with the noisy low mantissa bytes.  A general
+//! purpose compressor applied afterwards then does much better.  For `n` values of `k` bytes, byte
+//! `j` of value `i` is stored at `j × n + i`.
+
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use super::{DecodeError, DecodeErrorKind};
+use crate::FloatBits;
+
+/// Writes the encoding of `values` to `out`.
+///
+/// # Panics
+///
+/// Panics if `out` is not exactly as long as the encoding, which is as long as the raw bytes of
+/// `values`.
+pub fn split_into<T: FloatBits>(values: &[T], out: &mut [u8]) {
+    let size = T::BITS / 8;
+    assert_eq!(values.len() * size, out.len(), "output must be as long as the input bytes");
+    if values.is_empty() {
+        return;
+    }
+    for (j, stream) in out.chunks_exact_mut(values.len()).enumerate() {
+        for (byte, value) in stream.iter_mut().zip(values) {
+            *byte = (value.to_bits().into() >> (8 * j)) as u8;
+        }
+    }
+}
+
+/// Returns the encoding of `values`.
+///
+/// # Features
+///
+/// Available only with feature: `alloc`.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::F32;
+/// # use float_bits::codec::byte_stream_split;
+///
+/// let values = [1.0, 2.0].map(F32::from_float);
+/// let bytes = byte_stream_split::split(&values);
+/// assert_eq!([0, 0, 0, 0, 0x80, 0, 0x3f, 0x40], bytes[..]);
+/// assert_eq!(Ok(values.to_vec()), byte_stream_split::unsplit(&bytes));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn split<T: FloatBits>(values: &[T]) -> Vec<u8> {
+    let mut out = vec![0; values.len() * (T::BITS / 8)];
+    split_into(values, &mut out);
+    out
+}
+
+/// Decodes `bytes` into `out`, the inverse of [`split_into`].
+///
+/// # Panics
+///
+/// Panics if `bytes` is not exactly as long as the raw bytes of `out`.
+pub fn unsplit_into<T: FloatBits>(bytes: &[u8], out: &mut [T]) {
+    let size = T::BITS / 8;
+    assert_eq!(out.len() * size, bytes.len(), "input must be as long as the output bytes");
+    let len = out.len();
+    for (i, value) in out.iter_mut().enumerate() {
+        let bits =
+            (0..size).fold(0u128, |bits, j| bits | ((bytes[j * len + i] as u128) << (8 * j)));
+        *value = match T::Bits::try_from(bits) {
+            Ok(bits) => T::from_bits(bits),
+            Err(_) => unreachable!("{size} bytes fit the bits"),
+        };
+    }
+}
+
+/// Decodes `bytes`, the inverse of [`split`].
+///
+/// Returns an error of kind [`UnexpectedEnd`](DecodeErrorKind::UnexpectedEnd) if the length of
+/// `bytes` is not a multiple of the size of `T`.
+///
+/// # Features
+///
+/// Available only with feature: `alloc`.
+#[cfg(feature = "alloc")]
+pub fn unsplit<T: FloatBits>(bytes: &[u8]) -> Result<Vec<T>, DecodeError> {
+    let size = T::BITS / 8;
+    if !bytes.len().is_multiple_of(size) {
+        return Err(DecodeError::new(DecodeErrorKind::UnexpectedEnd, bytes.len() * 8));
+    }
+    let mut out = vec![T::ZERO; bytes.len() / size];
+    unsplit_into(bytes, &mut out);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BF16, F64, F128};
+
+    #[test]
+    fn split_into_test() {
+        let values = [F64::from_bits(0x0102_0304_0506_0708), F64::from_bits(0x1112_1314_1516_1718)];
+        let mut bytes = [0; 16];
+        split_into(&values, &mut bytes);
+        assert_eq!([8, 0x18, 7, 0x17, 6, 0x16, 5, 0x15, 4, 0x14, 3, 0x13, 2, 0x12, 1, 0x11], bytes);
+        let mut decoded = [F64::ZERO; 2];
+        unsplit_into(&bytes, &mut decoded);
+        assert_eq!(values, decoded);
+
+        let values = [BF16::ONE, BF16::NEG_ONE, BF16::NAN];
+        let mut bytes = [0; 6];
+        split_into(&values, &mut bytes);
+        assert_eq!([0x80, 0x80, 0xc1, 0x3f, 0xbf, 0x7f], bytes);
+
+        split_into::<F128>(&[], &mut []);
+        unsplit_into::<F128>(&[], &mut []);
+    }
+
+    #[test]
+    #[should_panic = "output must be as long as the input bytes"]
+    fn split_into_length_test() {
+        split_into(&[F64::ONE], &mut [0; 4]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn split_test() {
+        let values: Vec<F128> = (0..37u128)
+            .map(|i| F128::from_bits(i.wrapping_mul(0x9e37_79b9_7f4a_7c15_f39c_c060_5ced_c835)))
+            .collect();
+        let bytes = split(&values);
+        assert_eq!(37 * 16, bytes.len());
+        assert_eq!(Ok(values), unsplit(&bytes));
+
+        let err = unsplit::<BF16>(&[0; 3]).unwrap_err();
+        assert_eq!(DecodeError::new(DecodeErrorKind::UnexpectedEnd, 24), err);
+    }
+}
@@ -0,0 +1,391 @@
+//! The Chimp128 compression of Liakos, Papakonstantinopoulou, and Kotidis, for [`F64`] streams.
+//!
+//! Chimp128 refines the XOR scheme of [Gorilla](super::gorilla).  Instead of always XORing with
+//! the previous value, it may XOR with any of the previous 128 values, picking one whose low 14
+//! bits are the same, which pays off for series that revisit earlier values.  Leading zero counts
+//! are rounded down to one of 8 steps and stored in 3 bits.  Each value is stored as:
+//!
+//! * `00`, then a 7-bit index: the value repeats the value at that index of the ring of previous
+//!   values.
+//! * `01`, then a 7-bit index, a 3-bit leading zero step, and a 6-bit meaningful bit count, then
+//!   the meaningful bits: the XOR with the value at that index has more than 13 trailing zeros.
+//! * `10`, then the bits after the leading zeros: the XOR with the previous value has the same
+//!   leading zero step as the last value stored with `11`.
+//! * `11`, then a 3-bit leading zero step and the bits after the leading zeros: the XOR with the
+//!   previous value, with a new leading zero step.
+//!
+//! The first value is stored in full.  Where the reference implementation ends the stream with a
+//! NaN, which could then not be stored, this stream ends with `01` and 16 zero bits, a meaningful
+//! bit count of 0 that no value produces, and is padded with zero bits to a whole byte.  An empty
+//! stream is empty.
+//!
+//! The bit patterns are preserved exactly, NaN payloads and the sign of zero included.
+
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use super::BitWriter;
+use super::{BitReader, DecodeError, DecodeErrorKind};
+use crate::F64;
+
+/// The number of previous values that can be referred to.
+const RING: usize = 128;
+
+/// Bits of the index into the ring of previous values.
+const RING_BITS: u32 = RING.trailing_zeros();
+
+/// Values whose XOR has more than this many trailing zeros are stored with their index.
+#[cfg(feature = "alloc")]
+const THRESHOLD: u32 = 6 + RING_BITS;
+
+/// Mask of the low bits by which previous values are looked up.
+#[cfg(feature = "alloc")]
+const KEY_MASK: u64 = (1 << (THRESHOLD + 1)) - 1;
+
+/// The leading zero counts that each 3-bit step stands for.
+const LEADING_STEPS: [u32; 8] = [0, 8, 12, 16, 18, 20, 22, 24];
+
+/// Returns the step of the leading zero count `leading`, rounding it down.
+#[cfg(feature = "alloc")]
+const fn leading_step(leading: u32) -> u32 {
+    let mut step = 7;
+    while LEADING_STEPS[step as usize] > leading {
+        step -= 1;
+    }
+    step
+}
+
+/// A streaming Chimp128 encoder.
+///
+/// # Features
+///
+/// Available only with feature: `alloc`.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct Encoder {
+    out: BitWriter,
+    ring: [u64; RING],
+    // The position of the most recent value with each key, by its low bits.
+    positions: Vec<usize>,
+    len: usize,
+    // The leading zero step of the last value stored with `11`, if the next may use `10`.
+    leading: Option<u32>,
+}
+
+#[cfg(feature = "alloc")]
+impl Encoder {
+    /// Creates an encoder for an empty stream.
+    pub fn new() -> Self {
+        let positions = vec![0; KEY_MASK as usize + 1];
+        Self { out: BitWriter::new(), ring: [0; RING], positions, len: 0, leading: None }
+    }
+
+    /// Appends `value` to the stream.
+    pub fn push(&mut self, value: F64) {
+        let bits = value.to_bits();
+        let key = (bits & KEY_MASK) as usize;
+        if self.len == 0 {
+            self.out.write(bits, 64);
+        } else {
+            self.push_xor(bits, key);
+        }
+        self.ring[self.len % RING] = bits;
+        self.positions[key] = self.len;
+        self.len += 1;
+    }
+
+    fn push_xor(&mut self, bits: u64, key: usize) {
+        let last = (self.len - 1) % RING;
+        let candidate = self.positions[key];
+        let mut trailing = 0;
+        let mut index = last;
+        if self.len - candidate <= RING {
+            trailing = (bits ^ self.ring[candidate % RING]).trailing_zeros();
+            if trailing > THRESHOLD {
+                index = candidate % RING;
+            }
+        }
+        let xor = bits ^ self.ring[index];
+
+        if xor == 0 {
+            self.out.write(index as u64, 2 + RING_BITS);
+            self.leading = None;
+            return;
+        }
+        let step = leading_step(xor.leading_zeros());
+        let leading = LEADING_STEPS[step as usize];
+        if trailing > THRESHOLD {
+            let len = 64 - leading - trailing;
+            self.write_index(index, step, len);
+            self.out.write(xor >> trailing, len);
+            self.leading = None;
+        } else if self.leading == Some(step) {
+            self.out.write(0b10, 2);
+            self.out.write(xor, 64 - leading);
+        } else {
+            self.out.write(0b11, 2);
+            self.out.write(step as u64, 3);
+            self.out.write(xor, 64 - leading);
+            self.leading = Some(step);
+        }
+    }
+
+    fn write_index(&mut self, index: usize, step: u32, len: u32) {
+        self.out.write(0b01, 2);
+        self.out.write(index as u64, RING_BITS);
+        self.out.write(step as u64, 3);
+        self.out.write(len as u64, 6);
+    }
+
+    /// Ends the stream and returns its bytes.
+    pub fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.write_index(0, 0, 0);
+        }
+        self.out.finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for Encoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Extend<F64> for Encoder {
+    fn extend<I: IntoIterator<Item = F64>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+/// Encodes `values` as a Chimp128 stream.
+///
+/// # Features
+///
+/// Available only with feature: `alloc`.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::F64;
+/// # use float_bits::codec::chimp128;
+///
+/// let values = [12.0, 12.5, 13.0, 12.0, 12.5, 13.0].map(F64::from_float);
+/// let bytes = chimp128::encode(&values);
+/// assert!(bytes.len() < 6 * 8);
+/// let decoded: Result<Vec<F64>, _> = chimp128::decode(&bytes).collect();
+/// assert_eq!(values[..], decoded.unwrap());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode(values: &[F64]) -> Vec<u8> {
+    let mut encoder = Encoder::new();
+    encoder.extend(values.iter().copied());
+    encoder.finish()
+}
+
+/// A streaming Chimp128 decoder, which yields the values of a stream in order.
+///
+/// After the end of the stream or an error, the decoder yields `None`.  Bytes after the end of
+/// the stream are ignored.
+#[derive(Debug, Clone)]
+pub struct Decoder<'a> {
+    input: BitReader<'a>,
+    ring: [u64; RING],
+    len: usize,
+    leading: Option<u32>,
+    done: bool,
+}
+
+impl<'a> Decoder<'a> {
+    /// Creates a decoder for the stream in `bytes`.
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        let input = BitReader::new(bytes);
+        Self { input, ring: [0; RING], len: 0, leading: None, done: bytes.is_empty() }
+    }
+
+    fn next_bits(&mut self) -> Result<Option<u64>, DecodeError> {
+        if self.len == 0 {
+            return self.input.read(64).map(Some);
+        }
+        let offset = self.input.offset();
+        let prev = self.ring[(self.len - 1) % RING];
+        match self.input.read(2)? {
+            0b00 => {
+                let index = self.input.read(RING_BITS)? as usize;
+                self.check_index(index, offset)?;
+                self.leading = None;
+                Ok(Some(self.ring[index]))
+            },
+            0b01 => {
+                let index = self.input.read(RING_BITS)? as usize;
+                let leading = LEADING_STEPS[self.input.read(3)? as usize];
+                let len = self.input.read(6)? as u32;
+                if len == 0 {
+                    return Ok(None);
+                } else if leading + len > 64 {
+                    return Err(DecodeError::new(DecodeErrorKind::Invalid, offset));
+                }
+                self.check_index(index, offset)?;
+                self.leading = None;
+                let xor = self.input.read(len)? << (64 - leading - len);
+                Ok(Some(self.ring[index] ^ xor))
+            },
+            0b10 => {
+                let step =
+                    self.leading.ok_or(DecodeError::new(DecodeErrorKind::Invalid, offset))?;
+                let xor = self.input.read(64 - LEADING_STEPS[step as usize])?;
+                Ok(Some(prev ^ xor))
+            },
+            _ => {
+                let step = self.input.read(3)? as u32;
+                self.leading = Some(step);
+                let xor = self.input.read(64 - LEADING_STEPS[step as usize])?;
+                Ok(Some(prev ^ xor))
+            },
+        }
+    }
+
+    // Rejects indices of ring slots that no value has filled yet.
+    const fn check_index(&self, index: usize, offset: usize) -> Result<(), DecodeError> {
+        if index >= self.len {
+            Err(DecodeError::new(DecodeErrorKind::Invalid, offset))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Iterator for Decoder<'_> {
+    type Item = Result<F64, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.next_bits() {
+            Ok(Some(bits)) => {
+                self.ring[self.len % RING] = bits;
+                self.len += 1;
+                Some(Ok(F64::from_bits(bits)))
+            },
+            Ok(None) => {
+                self.done = true;
+                None
+            },
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            },
+        }
+    }
+}
+
+impl core::iter::FusedIterator for Decoder<'_> {}
+
+/// Returns a decoder for the Chimp128 stream in `bytes`.
+pub const fn decode(bytes: &[u8]) -> Decoder<'_> {
+    Decoder::new(bytes)
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    fn round_trip(values: &[F64]) -> Vec<u8> {
+        let bytes = encode(values);
+        let decoded: Vec<F64> = decode(&bytes).map(Result::unwrap).collect();
+        assert_eq!(values, decoded);
+        bytes
+    }
+
+    #[test]
+    fn leading_step_test() {
+        let steps: Vec<u32> = (0..=64).map(|n| LEADING_STEPS[leading_step(n) as usize]).collect();
+        assert_eq!([0, 0, 8, 8, 12, 16, 16, 18, 18, 22, 24, 24], [
+            steps[0], steps[7], steps[8], steps[11], steps[12], steps[16], steps[17], steps[18],
+            steps[19], steps[23], steps[24], steps[64]
+        ]);
+    }
+
+    #[test]
+    fn round_trip_test() {
+        assert!(round_trip(&[]).is_empty());
+        assert_eq!(11, round_trip(&[F64::ONE]).len());
+
+        let series: Vec<F64> =
+            (0..1000).map(|i| F64::from_float(20.0 + (i % 7) as f64 * 0.1)).collect();
+        let bytes = round_trip(&series);
+        assert!(bytes.len() < series.len() * 2, "{}", bytes.len());
+        assert!(bytes.len() < super::super::gorilla::encode(&series).len());
+
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let noise: Vec<F64> = (0..500)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                F64::from_bits(state >> (state % 64))
+            })
+            .collect();
+        round_trip(&noise);
+        round_trip(&[
+            F64::NEG_ZERO,
+            F64::ZERO,
+            F64::QNAN,
+            F64::NEG_SNAN,
+            F64::MAX,
+            F64::from_bits(1),
+        ]);
+    }
+
+    #[test]
+    fn fields_test() {
+        // The values share their low 14 bits, so each is compared with the one before.  2.5 and
+        // 3.0 differ in bits 50 and 51.
+        let values = [2.5, 3.0, 2.5, 2.5].map(F64::from_float);
+        let bytes = round_trip(&values);
+        let mut r = BitReader::new(&bytes);
+        assert_eq!(Ok(values[0].to_bits()), r.read(64));
+        let fields = [
+            (2, 0b01),
+            (7, 0),
+            (3, 2),
+            (6, 2),
+            (2, 0b11),
+            (2, 0b01),
+            (7, 1),
+            (3, 2),
+            (6, 2),
+            (2, 0b11),
+            (2, 0b00),
+            (7, 2),
+            (2, 0b01),
+            (16, 0),
+        ];
+        for (n, field) in fields {
+            assert_eq!(Ok(field), r.read(n));
+        }
+    }
+
+    #[test]
+    fn error_test() {
+        let bytes = encode(&[F64::ONE, F64::from_float(1.5), F64::ONE]);
+        for n in 1..bytes.len() {
+            let err = decode(&bytes[..n]).find_map(Result::err).unwrap();
+            assert_eq!(DecodeErrorKind::UnexpectedEnd, err.kind());
+        }
+
+        // A repeat of a ring slot that no value has filled.
+        let mut bytes = F64::ONE.to_bits().to_be_bytes().to_vec();
+        bytes.extend([0b0000_0001, 0]);
+        let err = decode(&bytes).nth(1).unwrap().unwrap_err();
+        assert_eq!(DecodeError::new(DecodeErrorKind::Invalid, 64), err);
+    }
+}
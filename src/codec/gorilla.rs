@@ -0,0 +1,286 @@
+//! The XOR compression of Facebook's Gorilla time-series database, for [`F64`] streams.
+//!
+//! Consecutive values of a time series tend to share their sign, exponent, and leading mantissa
+//! bits, so the XOR of their bit patterns has long runs of zeros at both ends.  The stream stores
+//! the first value in full, then each later value as the XOR with its predecessor:
+//!
+//! * `0`: the XOR is zero, so the value repeats.
+//! * `10`, then the meaningful bits: the nonzero bits of the XOR fit within the window of the
+//!   previous block, so only the bits of that window are stored.
+//! * `11`, then 5 bits of leading zero count, 6 bits of meaningful bit count (with 64 stored as
+//!   0), then the meaningful bits: a new window.
+//!
+//! Leading zero counts above 31 are stored as 31.  The stream ends with `11`, a leading zero
+//! count of 31, and a meaningful bit count of 64, which no value produces, and is padded with zero
+//! bits to a whole byte.  An empty stream is empty.
+//!
+//! The bit patterns are preserved exactly, NaN payloads and the sign of zero included.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use super::BitWriter;
+use super::{BitReader, DecodeError, DecodeErrorKind};
+use crate::F64;
+
+const MAX_LEADING: u32 = 31;
+
+/// The leading zero count and meaningful bit count of the end marker.
+const END: (u32, u32) = (MAX_LEADING, 64);
+
+/// A streaming Gorilla encoder.
+///
+/// # Features
+///
+/// Available only with feature: `alloc`.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default)]
+pub struct Encoder {
+    out: BitWriter,
+    prev: Option<u64>,
+    // The leading zero count and meaningful bit count of the current window.
+    window: Option<(u32, u32)>,
+}
+
+#[cfg(feature = "alloc")]
+impl Encoder {
+    /// Creates an encoder for an empty stream.
+    pub const fn new() -> Self {
+        Self { out: BitWriter::new(), prev: None, window: None }
+    }
+
+    /// Appends `value` to the stream.
+    pub fn push(&mut self, value: F64) {
+        let bits = value.to_bits();
+        let Some(prev) = self.prev.replace(bits) else {
+            self.out.write(bits, 64);
+            return;
+        };
+        let xor = prev ^ bits;
+        if xor == 0 {
+            self.out.write(0b0, 1);
+            return;
+        }
+        let leading = xor.leading_zeros().min(MAX_LEADING);
+        let trailing = xor.trailing_zeros();
+        match self.window {
+            Some((lead, len)) if leading >= lead && trailing >= 64 - lead - len => {
+                self.out.write(0b10, 2);
+                self.out.write(xor >> (64 - lead - len), len);
+            },
+            _ => {
+                let len = 64 - leading - trailing;
+                self.write_window(leading, len);
+                self.out.write(xor >> trailing, len);
+                self.window = Some((leading, len));
+            },
+        }
+    }
+
+    fn write_window(&mut self, leading: u32, len: u32) {
+        self.out.write(0b11, 2);
+        self.out.write(leading as u64, 5);
+        self.out.write(len as u64 % 64, 6);
+    }
+
+    /// Ends the stream and returns its bytes.
+    pub fn finish(mut self) -> Vec<u8> {
+        if self.prev.is_some() {
+            self.write_window(END.0, END.1);
+        }
+        self.out.finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Extend<F64> for Encoder {
+    fn extend<I: IntoIterator<Item = F64>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+/// Encodes `values` as a Gorilla stream.
+///
+/// # Features
+///
+/// Available only with feature: `alloc`.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::F64;
+/// # use float_bits::codec::gorilla;
+///
+/// let values = [12.0, 12.0, 24.0, 15.5].map(F64::from_float);
+/// let bytes = gorilla::encode(&values);
+/// assert!(bytes.len() < 16);
+/// let decoded: Result<Vec<F64>, _> = gorilla::decode(&bytes).collect();
+/// assert_eq!(values[..], decoded.unwrap());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode(values: &[F64]) -> Vec<u8> {
+    let mut encoder = Encoder::new();
+    encoder.extend(values.iter().copied());
+    encoder.finish()
+}
+
+/// A streaming Gorilla decoder, which yields the values of a stream in order.
+///
+/// After the end of the stream or an error, the decoder yields `None`.  Bytes after the end of
+/// the stream are ignored.
+#[derive(Debug, Clone)]
+pub struct Decoder<'a> {
+    input: BitReader<'a>,
+    prev: Option<u64>,
+    window: Option<(u32, u32)>,
+    done: bool,
+}
+
+impl<'a> Decoder<'a> {
+    /// Creates a decoder for the stream in `bytes`.
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self { input: BitReader::new(bytes), prev: None, window: None, done: bytes.is_empty() }
+    }
+
+    fn next_bits(&mut self) -> Result<Option<u64>, DecodeError> {
+        let Some(prev) = self.prev else {
+            return self.input.read(64).map(Some);
+        };
+        if self.input.read(1)? == 0 {
+            return Ok(Some(prev));
+        }
+        let (lead, len) = if self.input.read(1)? == 0 {
+            let offset = self.input.offset() - 2;
+            self.window.ok_or(DecodeError::new(DecodeErrorKind::Invalid, offset))?
+        } else {
+            let offset = self.input.offset() - 2;
+            let lead = self.input.read(5)? as u32;
+            let len = match self.input.read(6)? as u32 {
+                0 => 64,
+                len => len,
+            };
+            if (lead, len) == END {
+                return Ok(None);
+            } else if lead + len > 64 {
+                return Err(DecodeError::new(DecodeErrorKind::Invalid, offset));
+            }
+            self.window = Some((lead, len));
+            (lead, len)
+        };
+        let xor = self.input.read(len)? << (64 - lead - len);
+        Ok(Some(prev ^ xor))
+    }
+}
+
+impl Iterator for Decoder<'_> {
+    type Item = Result<F64, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.next_bits() {
+            Ok(Some(bits)) => {
+                self.prev = Some(bits);
+                Some(Ok(F64::from_bits(bits)))
+            },
+            Ok(None) => {
+                self.done = true;
+                None
+            },
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            },
+        }
+    }
+}
+
+impl core::iter::FusedIterator for Decoder<'_> {}
+
+/// Returns a decoder for the Gorilla stream in `bytes`.
+pub const fn decode(bytes: &[u8]) -> Decoder<'_> {
+    Decoder::new(bytes)
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    fn round_trip(values: &[F64]) -> Vec<u8> {
+        let bytes = encode(values);
+        let decoded: Vec<F64> = decode(&bytes).map(Result::unwrap).collect();
+        assert_eq!(values, decoded);
+        bytes
+    }
+
+    #[test]
+    fn round_trip_test() {
+        assert!(round_trip(&[]).is_empty());
+        assert_eq!(10, round_trip(&[F64::ONE]).len());
+        assert_eq!(10, round_trip(&[F64::ONE, F64::ONE, F64::ONE]).len());
+
+        let series: Vec<F64> =
+            (0..1000).map(|i| F64::from_float(20.0 + (i % 7) as f64 * 0.25)).collect();
+        let bytes = round_trip(&series);
+        assert!(bytes.len() < series.len() * 2, "{}", bytes.len());
+
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let noise: Vec<F64> = (0..500)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                F64::from_bits(state >> (state % 64))
+            })
+            .collect();
+        round_trip(&noise);
+        round_trip(&[
+            F64::NEG_ZERO,
+            F64::ZERO,
+            F64::QNAN,
+            F64::NEG_SNAN,
+            F64::MAX,
+            F64::from_bits(1),
+        ]);
+    }
+
+    #[test]
+    fn window_test() {
+        // Each XOR has a single bit set: bit 51, then bit 50 twice.
+        let values = [1.0, 1.5, 1.75, 1.5, 1.5].map(F64::from_float);
+        let bytes = round_trip(&values);
+        let mut r = BitReader::new(&bytes);
+        assert_eq!(Ok(F64::ONE.to_bits()), r.read(64));
+        let fields = [(2, 0b11), (5, 12), (6, 1), (1, 1), (2, 0b11), (5, 13), (6, 1), (1, 1)];
+        for (n, field) in fields {
+            assert_eq!(Ok(field), r.read(n));
+        }
+        let fields = [(2, 0b10), (1, 1), (1, 0), (2, 0b11), (5, 31), (6, 0)];
+        for (n, field) in fields {
+            assert_eq!(Ok(field), r.read(n));
+        }
+        assert_eq!(r.offset().div_ceil(8), bytes.len());
+    }
+
+    #[test]
+    fn error_test() {
+        let bytes = encode(&[F64::ONE, F64::from_float(1.5)]);
+        let errors: Vec<_> =
+            (0..bytes.len()).map(|n| decode(&bytes[..n]).find_map(Result::err)).collect();
+        assert_eq!(None, errors[0]);
+        assert!(errors[1..].iter().all(|e| e.unwrap().kind() == DecodeErrorKind::UnexpectedEnd));
+
+        // A reuse of a window before any window was set.
+        let mut bytes = F64::ONE.to_bits().to_be_bytes().to_vec();
+        bytes.push(0b1000_0000);
+        let err = decode(&bytes).nth(1).unwrap().unwrap_err();
+        assert_eq!(DecodeError::new(DecodeErrorKind::Invalid, 64), err);
+        let mut decoder = decode(&bytes);
+        decoder.by_ref().for_each(drop);
+        assert_eq!(None, decoder.next());
+    }
+}
@@ -0,0 +1,262 @@
+//! Lossless compression codecs and transforms for streams of floats.
+//!
+//! Each codec has its own module.  Encoding needs crate feature `alloc`, since the output grows
+//! as values are pushed, but decoding works on a borrowed byte slice and needs no allocator.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::F64;
+
+pub mod alp;
+pub mod byte_stream_split;
+pub mod chimp128;
+pub mod gorilla;
+
+/// A codec for streams of [`F64`] values, for choosing one per stream at runtime.
+///
+/// Of the XOR codecs, [Gorilla](gorilla) is the faster.  [Chimp128](chimp128) usually compresses
+/// better, often by half for measurements with a few decimal digits, at the cost of a 128 KiB
+/// lookup table in the encoder.  Values with short mantissas, such as small multiples of powers of
+/// two, all look alike to that table, and then Gorilla wins.  The example `codecs` compares the
+/// codecs on a few series.
+///
+/// [ALP](alp) is meant for columns of decimal data such as prices, which it stores as small
+/// integers, and falls back to Gorilla for blocks that do not fit that pattern.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Codec {
+    /// The XOR compression of [`gorilla`].
+    Gorilla,
+
+    /// The XOR compression of [`chimp128`].
+    Chimp128,
+
+    /// The adaptive decimal compression of [`alp`].
+    Alp,
+}
+
+impl Codec {
+    /// Encodes `values` as a stream of this codec.
+    ///
+    /// # Features
+    ///
+    /// Available only with feature: `alloc`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use float_bits::F64;
+    /// # use float_bits::codec::Codec;
+    ///
+    /// let values = [1.0, 1.5, 1.0, 1.5].map(F64::from_float);
+    /// for codec in [Codec::Gorilla, Codec::Chimp128, Codec::Alp] {
+    ///     let bytes = codec.encode(&values);
+    ///     let decoded: Result<Vec<F64>, _> = codec.decode(&bytes).collect();
+    ///     assert_eq!(values[..], decoded.unwrap());
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn encode(self, values: &[F64]) -> Vec<u8> {
+        match self {
+            Self::Gorilla => gorilla::encode(values),
+            Self::Chimp128 => chimp128::encode(values),
+            Self::Alp => alp::encode(values),
+        }
+    }
+
+    /// Returns a decoder for the stream of this codec in `bytes`.
+    pub const fn decode(self, bytes: &[u8]) -> Decoder<'_> {
+        match self {
+            Self::Gorilla => Decoder::Gorilla(gorilla::decode(bytes)),
+            Self::Chimp128 => Decoder::Chimp128(chimp128::decode(bytes)),
+            Self::Alp => Decoder::Alp(alp::decode(bytes)),
+        }
+    }
+}
+
+/// A decoder for a stream of any [`Codec`], which yields the values of the stream in order.
+// The Chimp128 decoder holds its ring of previous values inline, so that decoding needs no
+// allocator.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Decoder<'a> {
+    /// A decoder for a [`Codec::Gorilla`] stream.
+    Gorilla(gorilla::Decoder<'a>),
+
+    /// A decoder for a [`Codec::Chimp128`] stream.
+    Chimp128(chimp128::Decoder<'a>),
+
+    /// A decoder for a [`Codec::Alp`] stream.
+    Alp(alp::Decoder<'a>),
+}
+
+impl Iterator for Decoder<'_> {
+    type Item = Result<F64, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Gorilla(decoder) => decoder.next(),
+            Self::Chimp128(decoder) => decoder.next(),
+            Self::Alp(decoder) => decoder.next(),
+        }
+    }
+}
+
+impl core::iter::FusedIterator for Decoder<'_> {}
+
+/// The reason an encoded stream could not be decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DecodeErrorKind {
+    /// The input ended in the middle of a value, or before the end of the stream was marked.
+    UnexpectedEnd,
+
+    /// The input contained a code that no encoder produces.
+    Invalid,
+}
+
+/// An error returned when decoding a compressed stream.
+///
+/// Besides the [kind](DecodeErrorKind) of failure, it records the bit offset in the input where
+/// the failure was detected, counting from the most significant bit of the first byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DecodeError {
+    kind: DecodeErrorKind,
+    offset: usize,
+}
+
+impl DecodeError {
+    pub(crate) const fn new(kind: DecodeErrorKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+
+    /// Returns the reason for the failure.
+    pub const fn kind(&self) -> DecodeErrorKind {
+        self.kind
+    }
+
+    /// Returns the bit offset in the input where the failure was detected.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = match self.kind {
+            DecodeErrorKind::UnexpectedEnd => "unexpected end of compressed stream",
+            DecodeErrorKind::Invalid => "invalid code in compressed stream",
+        };
+        write!(f, "{what} at bit {}", self.offset)
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
+/// Appends bit fields to a byte vector, most significant bit first.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BitWriter {
+    bytes: Vec<u8>,
+    // The pending bits of the last, incomplete byte, in the low `len` bits.
+    acc: u8,
+    len: u32,
+}
+
+#[cfg(feature = "alloc")]
+impl BitWriter {
+    pub(crate) const fn new() -> Self {
+        Self { bytes: Vec::new(), acc: 0, len: 0 }
+    }
+
+    /// Appends the low `n` bits of `value`, where `n` is at most 64.
+    pub(crate) fn write(&mut self, value: u64, n: u32) {
+        let mut n = n;
+        while n > 0 {
+            let take = n.min(8 - self.len);
+            let chunk = (value >> (n - take)) as u8 & (0xff >> (8 - take));
+            self.acc = ((self.acc as u16) << take) as u8 | chunk;
+            self.len += take;
+            n -= take;
+            if self.len == 8 {
+                self.bytes.push(self.acc);
+                self.acc = 0;
+                self.len = 0;
+            }
+        }
+    }
+
+    /// Returns the bytes written, with the last byte padded with zero bits.
+    pub(crate) fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.bytes.push(self.acc << (8 - self.len));
+        }
+        self.bytes
+    }
+}
+
+/// Reads bit fields from a byte slice, most significant bit first.
+#[derive(Debug, Clone)]
+pub(crate) struct BitReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub(crate) const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    /// Returns the offset of the next bit to be read.
+    pub(crate) const fn offset(&self) -> usize {
+        self.pos
+    }
+
+    /// Reads the next `n` bits, where `n` is at most 64.
+    pub(crate) fn read(&mut self, n: u32) -> Result<u64, DecodeError> {
+        if self.pos + n as usize > self.bytes.len() * 8 {
+            return Err(DecodeError::new(DecodeErrorKind::UnexpectedEnd, self.bytes.len() * 8));
+        }
+        let mut value = 0u64;
+        let mut n = n;
+        while n > 0 {
+            let used = (self.pos % 8) as u32;
+            let take = n.min(8 - used);
+            let byte = self.bytes[self.pos / 8] << used;
+            value = ((value as u128) << take) as u64 | (byte >> (8 - take)) as u64;
+            self.pos += take as usize;
+            n -= take;
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bits_test() {
+        let mut w = BitWriter::new();
+        w.write(0b101, 3);
+        w.write(u64::MAX, 64);
+        w.write(0, 0);
+        w.write(0x1234, 13);
+        let bytes = w.finish();
+        assert_eq!(10, bytes.len());
+        assert_eq!([0xbf, 0xff], bytes[..2]);
+
+        let mut r = BitReader::new(&bytes);
+        assert_eq!(Ok(0b101), r.read(3));
+        assert_eq!(Ok(u64::MAX), r.read(64));
+        assert_eq!(Ok(0x1234), r.read(13));
+        assert_eq!(Ok(0), r.read(0));
+        let err = r.read(1).unwrap_err();
+        assert_eq!(DecodeErrorKind::UnexpectedEnd, err.kind());
+        assert_eq!(80, err.offset());
+    }
+}
@@ -0,0 +1,154 @@
+//! Mathematical constants for every format, correctly rounded.
+//!
+//! Each module mirrors [`core::f64::consts`] for one format, with the same names.  A type cannot
+//! hold a module, so the constants of [`F64`](crate::F64) are in [`consts::f64`](f64) rather than
+//! `F64::consts`.  Each constant is the exact value rounded to nearest with ties to even, parsed
+//! at compile time from 60 significant digits, so the formats that Rust has no primitive for get
+//! the same correctly rounded constants as `f32` and `f64`.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use float_bits::{BF16, F64, F128};
+//! # use float_bits::consts;
+//!
+//! assert_eq!(F64::from_float(core::f64::consts::PI), consts::f64::PI);
+//! assert_eq!(BF16::from_bits(0x4049), consts::bf16::PI);
+//! assert_eq!(F128::from_bits(0x4000_921f_b544_42d1_8469_898c_c517_01b8), consts::f128::PI);
+//! ```
+
+macro_rules! define_consts {
+    ($mod:ident, $ty:ident) => {
+        #[doc = concat!("Mathematical constants for [`", stringify!($ty), "`].")]
+        pub mod $mod {
+            use crate::$ty;
+
+            /// Archimedes' constant (π).
+            pub const PI: $ty =
+                $ty::parse_const("3.14159265358979323846264338327950288419716939937510582097494");
+
+            /// The full circle constant (τ), equal to 2π.
+            pub const TAU: $ty =
+                $ty::parse_const("6.28318530717958647692528676655900576839433879875021164194989");
+
+            /// The golden ratio (φ).
+            pub const PHI: $ty =
+                $ty::parse_const("1.61803398874989484820458683436563811772030917980576286213545");
+
+            /// π/2.
+            pub const FRAC_PI_2: $ty =
+                $ty::parse_const("1.57079632679489661923132169163975144209858469968755291048747");
+
+            /// π/3.
+            pub const FRAC_PI_3: $ty =
+                $ty::parse_const("1.04719755119659774615421446109316762806572313312503527365831");
+
+            /// π/4.
+            pub const FRAC_PI_4: $ty =
+                $ty::parse_const("0.785398163397448309615660845819875721049292349843776455243736");
+
+            /// π/6.
+            pub const FRAC_PI_6: $ty =
+                $ty::parse_const("0.523598775598298873077107230546583814032861566562517636829157");
+
+            /// π/8.
+            pub const FRAC_PI_8: $ty =
+                $ty::parse_const("0.392699081698724154807830422909937860524646174921888227621868");
+
+            /// 1/π.
+            pub const FRAC_1_PI: $ty =
+                $ty::parse_const("0.318309886183790671537767526745028724068919291480912897495335");
+
+            /// 2/π.
+            pub const FRAC_2_PI: $ty =
+                $ty::parse_const("0.636619772367581343075535053490057448137838582961825794990669");
+
+            /// 2/sqrt(π).
+            pub const FRAC_2_SQRT_PI: $ty =
+                $ty::parse_const("1.12837916709551257389615890312154517168810125865799771368817");
+
+            /// sqrt(2).
+            pub const SQRT_2: $ty =
+                $ty::parse_const("1.41421356237309504880168872420969807856967187537694807317668");
+
+            /// 1/sqrt(2).
+            pub const FRAC_1_SQRT_2: $ty =
+                $ty::parse_const("0.707106781186547524400844362104849039284835937688474036588340");
+
+            /// Euler's number (e).
+            pub const E: $ty =
+                $ty::parse_const("2.71828182845904523536028747135266249775724709369995957496697");
+
+            /// log₂(e).
+            pub const LOG2_E: $ty =
+                $ty::parse_const("1.44269504088896340735992468100189213742664595415298593413545");
+
+            /// log₂(10).
+            pub const LOG2_10: $ty =
+                $ty::parse_const("3.32192809488736234787031942948939017586483139302458061205476");
+
+            /// log₁₀(e).
+            pub const LOG10_E: $ty =
+                $ty::parse_const("0.434294481903251827651128918916605082294397005803666566114454");
+
+            /// log₁₀(2).
+            pub const LOG10_2: $ty =
+                $ty::parse_const("0.301029995663981195213738894724493026768189881462108541310427");
+
+            /// ln(2).
+            pub const LN_2: $ty =
+                $ty::parse_const("0.693147180559945309417232121458176568075500134360255254120680");
+
+            /// ln(10).
+            pub const LN_10: $ty =
+                $ty::parse_const("2.30258509299404568401799145468436420760110148862877297603333");
+        }
+    };
+}
+
+define_consts!(bf16, BF16);
+define_consts!(f16, F16);
+define_consts!(f32, F32);
+define_consts!(f64, F64);
+define_consts!(f128, F128);
+
+#[cfg(test)]
+mod tests {
+    use crate::{BF16, F16, F32, F64, F128};
+
+    macro_rules! assert_native {
+        ($mod:ident, $ty:ident, $f_ty:ident; $($name:ident),*) => {
+            $(
+                assert_eq!(
+                    $ty::from_float(core::$f_ty::consts::$name).to_bits(),
+                    super::$mod::$name.to_bits(),
+                    stringify!($name),
+                );
+            )*
+        };
+    }
+
+    #[test]
+    fn native_test() {
+        assert_native!(f32, F32, f32; PI, TAU, FRAC_PI_2, FRAC_PI_3, FRAC_PI_4, FRAC_PI_6,
+            FRAC_PI_8, FRAC_1_PI, FRAC_2_PI, FRAC_2_SQRT_PI, SQRT_2, FRAC_1_SQRT_2, E, LOG2_E,
+            LOG2_10, LOG10_E, LOG10_2, LN_2, LN_10);
+        assert_native!(f64, F64, f64; PI, TAU, FRAC_PI_2, FRAC_PI_3, FRAC_PI_4, FRAC_PI_6,
+            FRAC_PI_8, FRAC_1_PI, FRAC_2_PI, FRAC_2_SQRT_PI, SQRT_2, FRAC_1_SQRT_2, E, LOG2_E,
+            LOG2_10, LOG10_E, LOG10_2, LN_2, LN_10);
+        assert_eq!(F64::from_bits(0x3ff9_e377_9b97_f4a8), super::f64::PHI);
+    }
+
+    #[test]
+    fn non_native_test() {
+        assert_eq!(BF16::from_bits(0x4049), super::bf16::PI);
+        assert_eq!(BF16::from_bits(0x402e), super::bf16::E);
+        assert_eq!(BF16::from_bits(0x3f31), super::bf16::LN_2);
+        assert_eq!(F16::from_bits(0x4248), super::f16::PI);
+        assert_eq!(F16::from_bits(0x4170), super::f16::E);
+        assert_eq!(F16::from_bits(0x398c), super::f16::LN_2);
+        assert_eq!(F128::from_bits(0x4000_921f_b544_42d1_8469_898c_c517_01b8), super::f128::PI);
+        assert_eq!(F128::from_bits(0x3ffe_62e4_2fef_a39e_f357_93c7_6730_07e6), super::f128::LN_2);
+        assert_eq!(super::f128::PI.to_bits() + (1 << 112), super::f128::TAU.to_bits());
+    }
+}
@@ -0,0 +1,158 @@
+//! A rounding mode and sticky exception flags for software arithmetic.
+//!
+//! [`FloatContext`] bundles the two pieces of state that IEEE 754 attaches to every operation, so
+//! that a computation can be written as a sequence of calls on the context.
+
+use crate::{FloatBits, RoundingMode, StatusFlags};
+
+/// A rounding mode and the sticky exception flags of a computation, as in IEEE 754 and in
+/// software float libraries.
+///
+/// Each operation rounds according to the rounding mode of the context and adds the flags that it
+/// raised to those of the context, where they stay until cleared.  A long computation can then
+/// check once at the end whether any step was inexact or invalid, without threading a mode into
+/// every call.  The operations are the arithmetic ones of [`FloatBits`] that report flags, such as
+/// [`add_with_flags`](FloatBits::add_with_flags), along with integer powers and conversions
+/// between formats, and work on every format.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::{F32, FloatContext, RoundingMode, StatusFlags};
+///
+/// let mut ctx = FloatContext::new(RoundingMode::TowardZero);
+/// let third = ctx.div(F32::ONE, F32::from_float(3.0));
+/// assert_eq!(F32::from_bits(0x3eaa_aaaa), third);
+/// assert_eq!(F32::ONE, ctx.add(F32::ONE, F32::ZERO));
+/// assert_eq!(StatusFlags::INEXACT, ctx.flags());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FloatContext {
+    mode: RoundingMode,
+    flags: StatusFlags,
+}
+
+impl FloatContext {
+    /// Returns a context that rounds according to `mode`, with no flags set.
+    pub const fn new(mode: RoundingMode) -> Self {
+        Self { mode, flags: StatusFlags::NONE }
+    }
+
+    /// Returns the rounding mode.
+    pub const fn rounding_mode(&self) -> RoundingMode {
+        self.mode
+    }
+
+    /// Sets the rounding mode of the operations that follow, keeping the flags.
+    pub const fn set_rounding_mode(&mut self, mode: RoundingMode) {
+        self.mode = mode;
+    }
+
+    /// Returns the flags raised since the context was created or the flags were last cleared.
+    pub const fn flags(&self) -> StatusFlags {
+        self.flags
+    }
+
+    /// Clears the flags.
+    pub const fn clear_flags(&mut self) {
+        self.flags = StatusFlags::NONE;
+    }
+
+    /// Clears the flags, returning those that were set.
+    pub const fn take_flags(&mut self) -> StatusFlags {
+        let flags = self.flags;
+        self.flags = StatusFlags::NONE;
+        flags
+    }
+
+    /// Returns `a + b`.
+    pub fn add<T: FloatBits>(&mut self, a: T, b: T) -> T {
+        self.record(a.add_with_flags(b, self.mode))
+    }
+
+    /// Returns `a - b`.
+    pub fn sub<T: FloatBits>(&mut self, a: T, b: T) -> T {
+        self.record(a.sub_with_flags(b, self.mode))
+    }
+
+    /// Returns `a × b`.
+    pub fn mul<T: FloatBits>(&mut self, a: T, b: T) -> T {
+        self.record(a.mul_with_flags(b, self.mode))
+    }
+
+    /// Returns `a / b`.
+    pub fn div<T: FloatBits>(&mut self, a: T, b: T) -> T {
+        self.record(a.div_with_flags(b, self.mode))
+    }
+
+    /// Returns the square root of `a`.
+    pub fn sqrt<T: FloatBits>(&mut self, a: T) -> T {
+        self.record(a.sqrt_with_flags(self.mode))
+    }
+
+    /// Returns `a` raised to the integer power `n`.
+    pub fn powi<T: FloatBits>(&mut self, a: T, n: i32) -> T {
+        self.record(a.powi_with_flags(n, self.mode))
+    }
+
+    /// Returns `a` converted to the format `U`.
+    pub fn convert<T: FloatBits, U: FloatBits>(&mut self, a: T) -> U {
+        self.record(a.convert_with_flags(self.mode))
+    }
+
+    fn record<T>(&mut self, (value, flags): (T, StatusFlags)) -> T {
+        self.flags |= flags;
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BF16, F16, F32, F64, F128};
+
+    #[test]
+    fn float_context_test() {
+        let mut ctx = FloatContext::default();
+        assert_eq!(RoundingMode::TiesToEven, ctx.rounding_mode());
+        assert_eq!(F64::from_float(0.1 * 3.0), ctx.mul(F64::from_float(0.1), F64::from_float(3.0)));
+        assert_eq!(StatusFlags::INEXACT, ctx.flags());
+
+        assert!(ctx.sqrt(F128::NEG_ONE).is_nan());
+        assert_eq!(F16::INFINITY, ctx.div(F16::ONE, F16::ZERO));
+        let sticky = StatusFlags::INEXACT | StatusFlags::INVALID | StatusFlags::DIV_BY_ZERO;
+        assert_eq!(sticky, ctx.take_flags());
+        assert!(ctx.flags().is_empty());
+
+        ctx.set_rounding_mode(RoundingMode::TowardPositive);
+        assert_eq!(F16::MAX, ctx.sub(F16::MAX, F16::MIN_POSITIVE_SUBNORMAL));
+        assert_eq!(F16::INFINITY, ctx.add(F16::MAX, F16::MIN_POSITIVE_SUBNORMAL));
+        assert_eq!(StatusFlags::OVERFLOW | StatusFlags::INEXACT, ctx.flags());
+        ctx.clear_flags();
+        assert_eq!(F64::from_float(2.0), ctx.sqrt(F64::from_float(4.0)));
+        assert_eq!(FloatContext::new(RoundingMode::TowardPositive), ctx);
+
+        let ninth = F64::from_float(3.0).powi_rounded(-2, RoundingMode::TowardPositive);
+        assert_eq!(ninth, ctx.powi(F64::from_float(3.0), -2));
+        assert_eq!(StatusFlags::INEXACT, ctx.take_flags());
+        assert_eq!(F16::INFINITY, ctx.powi(F16::ZERO, -1));
+        assert_eq!(StatusFlags::DIV_BY_ZERO, ctx.take_flags());
+        assert!(ctx.powi(F128::SNAN, 3).is_nan());
+        assert_eq!(StatusFlags::INVALID, ctx.take_flags());
+
+        let x: F16 = ctx.convert(F64::from_float(0.5));
+        assert_eq!((F16::from_bits(0x3800), StatusFlags::NONE), (x, ctx.flags()));
+        let x: BF16 = ctx.convert(F32::from_float(1.0 + f32::EPSILON));
+        assert_eq!((BF16::ONE.next_up(), StatusFlags::INEXACT), (x, ctx.take_flags()));
+        let x: F16 = ctx.convert(F64::from_float(1e-10));
+        assert_eq!(F16::MIN_POSITIVE_SUBNORMAL, x);
+        assert_eq!(StatusFlags::UNDERFLOW | StatusFlags::INEXACT, ctx.take_flags());
+        let x: F32 = ctx.convert(F64::NEG_SNAN);
+        assert_eq!(
+            (F32::NEG_QNAN.to_bits() & !1, StatusFlags::INVALID),
+            (x.to_bits(), ctx.flags())
+        );
+        let x: F128 = ctx.convert(F64::QNAN);
+        assert!(x.is_nan());
+    }
+}
@@ -0,0 +1,428 @@
+//! Double-double arithmetic: extended precision built from pairs of binary64 floats.
+
+use crate::soft::{self, Kind};
+use crate::wide::Wide;
+use crate::{F64, F128, RoundingMode};
+
+/// A double-double float: the unevaluated sum of two [`F64`] values, `hi + lo`.
+///
+/// The pair is kept normalized, so that `hi` is `hi + lo` rounded to nearest and `lo` is the
+/// rounding error.  This gives roughly 106 bits of precision with the exponent range of `f64`,
+/// using only native `f64` arithmetic, so it is available on stable Rust without the `f128`
+/// feature.  Arithmetic follows the algorithms of Hida, Li, and Bailey's QD library, and is
+/// accurate to within a few units in the last place of the 106-bit result.
+///
+/// Because the representation is normalized, every value has exactly one representation.
+/// Equality, hashing, and [`Self::total_cmp`] therefore agree with each other and with the
+/// numerical value: pairs are ordered by `hi`, then by `lo`, using [`F64::total_cmp`].  When `hi`
+/// is zero, infinite, or NaN, `lo` is always `+0.0`.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::{DD128, F64};
+///
+/// let x = DD128::from(F64::from_float(0.1));
+/// let y = DD128::from(F64::from_float(0.2));
+/// let sum = x + y;
+/// assert_eq!(F64::from_float(0.30000000000000004), sum.hi());
+/// assert_eq!(F64::from_float(-2.7755575615628914e-17), sum.lo());
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct DD128 {
+    hi: F64,
+    lo: F64,
+}
+
+/// Error-free sum of two `f64` values, with no precondition on their magnitudes.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let (s, e) = F64::two_sum(F64::from_float(a), F64::from_float(b));
+    (s.to_float(), e.to_float())
+}
+
+/// Error-free product of two `f64` values.
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let (p, e) = F64::two_product(F64::from_float(a), F64::from_float(b));
+    (p.to_float(), e.to_float())
+}
+
+/// Widens a finite `f64` to working precision.
+const fn widen(x: F64) -> Wide {
+    let x = soft::unpack(F64::FORMAT, x.bits as u128);
+    if let Kind::Zero = x.kind { Wide::ZERO } else { Wide::from_unpacked(x) }
+}
+
+impl DD128 {
+    /// Positive zero (`+0.0`).
+    pub const ZERO: Self = Self::from_f64(F64::ZERO);
+
+    /// Negative zero (`−0.0`).
+    pub const NEG_ZERO: Self = Self::from_f64(F64::NEG_ZERO);
+
+    /// Positive one (`+1.0`).
+    pub const ONE: Self = Self::from_f64(F64::ONE);
+
+    /// Negative one (`−1.0`).
+    pub const NEG_ONE: Self = Self::from_f64(F64::NEG_ONE);
+
+    /// Positive infinity (`+∞`).
+    pub const INFINITY: Self = Self::from_f64(F64::INFINITY);
+
+    /// Negative infinity (`−∞`).
+    pub const NEG_INFINITY: Self = Self::from_f64(F64::NEG_INFINITY);
+
+    /// Not a Number (NaN).
+    pub const NAN: Self = Self::from_f64(F64::NAN);
+
+    /// Constructs a double-double from the unevaluated sum `hi + lo`.
+    ///
+    /// The inputs need not be normalized; the pair is renormalized so that `hi` holds the rounded
+    /// sum and `lo` holds the rounding error.
+    pub fn new(hi: F64, lo: F64) -> Self {
+        let (hi, lo) = (hi.to_float(), lo.to_float());
+        if !hi.is_finite() || !lo.is_finite() {
+            return Self::from_float_unchecked(hi + lo);
+        }
+        let (s, e) = two_sum(hi, lo);
+        Self::renormalize(s, e)
+    }
+
+    /// Constructs a double-double equal to `value`.
+    pub const fn from_f64(value: F64) -> Self {
+        Self { hi: value, lo: F64::ZERO }
+    }
+
+    /// Returns the high part: the value of `self` rounded to nearest [`F64`].
+    pub const fn hi(&self) -> F64 {
+        self.hi
+    }
+
+    /// Returns the low part: the rounding error of [`Self::hi`].
+    pub const fn lo(&self) -> F64 {
+        self.lo
+    }
+
+    /// Converts a binary128 float to the nearest double-double.
+    ///
+    /// Values outside the range of `f64` overflow to infinity or underflow towards zero, and the
+    /// last few significant bits of values with more than 106 significant bits are rounded off.
+    /// NaN payloads are truncated as for a conversion to `f64`.
+    pub const fn from_f128(value: F128) -> Self {
+        Self::from_f128_rounded(value, RoundingMode::TiesToEven)
+    }
+
+    /// Converts a binary128 float to a double-double, rounding according to `mode`.
+    ///
+    /// Both halves are rounded in the direction given by `mode`, so with a directed rounding mode
+    /// the result bounds `value` from that side.  See [`Self::from_f128`] for details.
+    pub const fn from_f128_rounded(value: F128, mode: RoundingMode) -> Self {
+        let x = soft::unpack(F128::FORMAT, value.bits);
+        let hi_bits = soft::round(F64::FORMAT, x, false, mode);
+        let hi = F64::from_bits(hi_bits as u64);
+        let in_range = x.exp <= F64::FORMAT.emax() && hi.is_finite();
+        if !matches!(x.kind, Kind::Finite) || !in_range || hi.is_zero() {
+            return Self::from_f64(hi);
+        }
+        let rem = Wide::from_unpacked(x).sub(widen(hi));
+        let lo = F64::from_bits(rem.round(F64::FORMAT, mode) as u64);
+        Self::renormalize(hi.to_float(), lo.to_float())
+    }
+
+    /// Converts `self` to the nearest binary128 float.
+    ///
+    /// The result is correctly rounded.  It is exact unless `lo` is so much smaller than `hi`
+    /// that their sum needs more than 113 significant bits.
+    pub const fn to_f128(&self) -> F128 {
+        self.to_f128_rounded(RoundingMode::TiesToEven)
+    }
+
+    /// Converts `self` to a binary128 float, rounding according to `mode`.
+    ///
+    /// See [`Self::to_f128`] for details.
+    pub const fn to_f128_rounded(&self, mode: RoundingMode) -> F128 {
+        if !self.hi.is_finite() || self.hi.is_zero() {
+            let x = soft::unpack(F64::FORMAT, self.hi.bits as u128);
+            return F128::from_bits(soft::round(F128::FORMAT, x, false, mode));
+        }
+        let sum = widen(self.hi).add(widen(self.lo));
+        F128::from_bits(sum.round(F128::FORMAT, mode))
+    }
+
+    /// Returns `true` if this value is NaN.
+    pub const fn is_nan(&self) -> bool {
+        self.hi.is_nan()
+    }
+
+    /// Returns `true` if this value is neither infinite nor NaN.
+    pub const fn is_finite(&self) -> bool {
+        self.hi.is_finite()
+    }
+
+    /// Returns `true` if this value is positive or negative infinity.
+    pub const fn is_infinite(&self) -> bool {
+        self.hi.is_infinite()
+    }
+
+    /// Returns `true` if self has a negative sign, including `−0.0`, `−∞`, and NaN with negative
+    /// sign bit.
+    pub const fn is_sign_negative(&self) -> bool {
+        self.hi.is_sign_negative()
+    }
+
+    /// Computes the absolute value of `self`.
+    pub const fn abs(&self) -> Self {
+        if self.is_sign_negative() { self.neg() } else { *self }
+    }
+
+    /// Reverses the sign of `self`.
+    pub const fn neg(&self) -> Self {
+        if self.lo.is_zero() {
+            Self { hi: self.hi.neg(), lo: self.lo }
+        } else {
+            Self { hi: self.hi.neg(), lo: self.lo.neg() }
+        }
+    }
+
+    /// Returns the ordering between `self` and `rhs`.
+    ///
+    /// Values are ordered as for [`F64::total_cmp`], except that finite values are compared with
+    /// their full double-double precision.
+    pub const fn total_cmp(&self, rhs: Self) -> core::cmp::Ordering {
+        use core::cmp::Ordering;
+        match self.hi.total_cmp(rhs.hi) {
+            Ordering::Equal => self.lo.total_cmp(rhs.lo),
+            ord => ord,
+        }
+    }
+
+    /// Wraps a non-finite or zero `hi`, with `lo` set to `+0.0`.
+    const fn from_float_unchecked(hi: f64) -> Self {
+        Self::from_f64(F64::from_float(hi))
+    }
+
+    /// Normalizes `hi + lo`, given that `|hi| ≥ |lo|` or `hi` is not finite.
+    const fn renormalize(hi: f64, lo: f64) -> Self {
+        if !hi.is_finite() || lo == 0.0 {
+            return Self::from_float_unchecked(hi);
+        }
+        let s = hi + lo;
+        if !s.is_finite() {
+            return Self::from_float_unchecked(s);
+        }
+        let e = lo - (s - hi);
+        Self { hi: F64::from_float(s), lo: F64::from_float(if e == 0.0 { 0.0 } else { e }) }
+    }
+
+    /// Computes `self × rhs` for an `f64` multiplier.
+    fn mul_f64(self, rhs: f64) -> Self {
+        let (p1, p2) = two_product(self.hi.to_float(), rhs);
+        Self::renormalize(p1, p2 + self.lo.to_float() * rhs)
+    }
+}
+
+impl Default for DD128 {
+    /// Returns [`Self::ZERO`].
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl PartialOrd for DD128 {
+    fn partial_cmp(&self, rhs: &Self) -> Option<core::cmp::Ordering> {
+        Some(Ord::cmp(self, rhs))
+    }
+}
+
+impl Ord for DD128 {
+    fn cmp(&self, rhs: &Self) -> core::cmp::Ordering {
+        self.total_cmp(*rhs)
+    }
+}
+
+impl From<F64> for DD128 {
+    fn from(value: F64) -> Self {
+        Self::from_f64(value)
+    }
+}
+
+impl From<DD128> for F64 {
+    fn from(value: DD128) -> Self {
+        value.hi
+    }
+}
+
+impl From<F128> for DD128 {
+    fn from(value: F128) -> Self {
+        Self::from_f128(value)
+    }
+}
+
+impl From<DD128> for F128 {
+    fn from(value: DD128) -> Self {
+        value.to_f128()
+    }
+}
+
+impl core::ops::Add for DD128 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let (s1, s2) = two_sum(self.hi.to_float(), rhs.hi.to_float());
+        let (t1, t2) = two_sum(self.lo.to_float(), rhs.lo.to_float());
+        let s = Self::renormalize(s1, s2 + t1);
+        Self::renormalize(s.hi.to_float(), s.lo.to_float() + t2)
+    }
+}
+
+impl core::ops::Sub for DD128 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        core::ops::Add::add(self, rhs.neg())
+    }
+}
+
+impl core::ops::Mul for DD128 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let (a, b) = (self.hi.to_float(), rhs.hi.to_float());
+        let (p1, p2) = two_product(a, b);
+        let cross = a * rhs.lo.to_float() + self.lo.to_float() * b;
+        Self::renormalize(p1, p2 + cross)
+    }
+}
+
+impl core::ops::Div for DD128 {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        let d = rhs.hi.to_float();
+        let q1 = self.hi.to_float() / d;
+        if !q1.is_finite() || q1 == 0.0 || !d.is_finite() {
+            return Self::from_float_unchecked(q1);
+        }
+        let r = self - rhs.mul_f64(q1);
+        let q2 = r.hi.to_float() / d;
+        let r = r - rhs.mul_f64(q2);
+        let q3 = r.hi.to_float() / d;
+        Self::renormalize(q1, q2) + Self::from_float_unchecked(q3)
+    }
+}
+
+impl core::ops::Neg for DD128 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        DD128::neg(&self)
+    }
+}
+
+macro_rules! define_assign_ops {
+    ($( $op:ident :: $method:ident / $op_assign:ident :: $method_assign:ident ),*) => {
+        $(
+            impl core::ops::$op_assign for DD128 {
+                fn $method_assign(&mut self, rhs: Self) {
+                    *self = core::ops::$op::$method(*self, rhs);
+                }
+            }
+        )*
+    };
+}
+
+define_assign_ops!(
+    Add::add / AddAssign::add_assign,
+    Sub::sub / SubAssign::sub_assign,
+    Mul::mul / MulAssign::mul_assign,
+    Div::div / DivAssign::div_assign
+);
+
+#[cfg(test)]
+mod tests {
+    use core::cmp::Ordering;
+
+    use super::*;
+
+    #[test]
+    fn arithmetic_test() {
+        let f = F64::from_float;
+        let p = |n| 2.0f64.powi(n);
+        let x = DD128::new(f(1.0 + p(-30)), f(p(-70)));
+        let sq = x * x;
+        assert_eq!((f(1.0 + p(-29)), f(p(-60) + p(-69) + p(-99))), (sq.hi(), sq.lo()));
+        let back = sq - x * x;
+        assert_eq!(DD128::ZERO, back);
+
+        let third = DD128::ONE / DD128::from(f(3.0));
+        assert_eq!(f(1.0 / 3.0), third.hi());
+        let err = third * DD128::from(f(3.0)) - DD128::ONE;
+        assert!(err.hi().to_float().abs() <= 2.0f64.powi(-104));
+
+        let big = DD128::new(f(1e300), f(1e283));
+        assert_eq!(DD128::INFINITY, big * big);
+        assert!((DD128::INFINITY - DD128::INFINITY).is_nan());
+        assert_eq!(DD128::NEG_ZERO, DD128::NEG_ZERO + DD128::NEG_ZERO);
+        assert_eq!(DD128::NEG_ZERO, DD128::NEG_ZERO * DD128::ONE);
+        assert_eq!(DD128::NEG_INFINITY, DD128::NEG_ONE / DD128::ZERO);
+        assert_eq!(DD128::ZERO, DD128::ONE / DD128::INFINITY);
+    }
+
+    #[test]
+    fn f128_conversion_test() {
+        // 1 + 2^-100 and 1/3 rounded to 106 bits.
+        const ONE_PLUS: u128 = 0x3fff0000000000000000000000001000;
+        const THIRD: u128 = 0x3ffd5555555555555555555555555555;
+        const THIRD_106: u128 = 0x3ffd5555555555555555555555555540;
+
+        let x = DD128::from_f128(F128::from_bits(ONE_PLUS));
+        assert_eq!((F64::ONE, F64::from_float(2.0f64.powi(-100))), (x.hi(), x.lo()));
+        assert_eq!(ONE_PLUS, x.to_f128().to_bits());
+        assert_eq!(THIRD_106, DD128::from_f128(F128::from_bits(THIRD)).to_f128().to_bits());
+
+        assert_eq!(DD128::NEG_ZERO, DD128::from_f128(F128::NEG_ZERO));
+        assert_eq!(DD128::INFINITY, DD128::from_f128(F128::MAX));
+        assert!(DD128::from_f128(F128::NAN).is_nan());
+        assert_eq!(F128::NEG_INFINITY, DD128::NEG_INFINITY.to_f128());
+        assert_eq!(F128::NEG_ZERO, DD128::NEG_ZERO.to_f128());
+
+        let third = F128::from_bits(THIRD);
+        let up = DD128::from_f128_rounded(third, RoundingMode::TowardPositive);
+        let down = DD128::from_f128_rounded(third, RoundingMode::TowardNegative);
+        assert!(down < up);
+        assert!(down.to_f128().to_bits() < THIRD && THIRD < up.to_f128().to_bits());
+        let x = DD128::new(F64::ONE, F64::from_float(2.0f64.powi(-200)));
+        assert_eq!(F128::ONE, x.to_f128());
+        assert_eq!(F128::ONE, x.to_f128_rounded(RoundingMode::TowardNegative));
+        let ulp_up = F128::from_bits(F128::ONE.to_bits() + 1);
+        assert_eq!(ulp_up, x.to_f128_rounded(RoundingMode::TowardPositive));
+        let max = DD128::from_f128_rounded(F128::MAX, RoundingMode::TowardZero);
+        assert_eq!(DD128::from(F64::MAX), max);
+        let min = DD128::from_f128_rounded(F128::MIN, RoundingMode::TowardPositive);
+        assert_eq!(DD128::from(F64::MIN), min);
+    }
+
+    #[test]
+    fn total_cmp_test() {
+        let f = F64::from_float;
+        let tiny = f(2.0f64.powi(-80));
+        let rows = [
+            DD128::NEG_INFINITY,
+            DD128::new(F64::NEG_ONE, tiny.neg()),
+            DD128::NEG_ONE,
+            DD128::new(F64::NEG_ONE, tiny),
+            DD128::NEG_ZERO,
+            DD128::ZERO,
+            DD128::new(F64::ONE, tiny.neg()),
+            DD128::ONE,
+            DD128::new(F64::ONE, tiny),
+            DD128::INFINITY,
+            DD128::NAN,
+        ];
+        for (i, x) in rows.iter().enumerate() {
+            for (j, y) in rows.iter().enumerate() {
+                assert_eq!(i.cmp(&j), x.total_cmp(*y), "{x:?} <=> {y:?}");
+            }
+        }
+        assert_eq!(Ordering::Equal, DD128::new(f(1.0), f(1.0)).total_cmp(DD128::from(f(2.0))));
+    }
+}
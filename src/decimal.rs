@@ -0,0 +1,1440 @@
+//! Decimal formatting and parsing of the raw bit representation.
+//!
+//! Digits are generated exactly, one at a time, from a ratio of two fixed-size big integers, so
+//! formatting works for every format without converting through a Rust float.  Output follows the
+//! conventions of Rust's `Display` for `f32` and `f64`: plain positional notation and, without a
+//! precision, the shortest digits that parse back to the same value.  With a precision, the exact
+//! value is rounded to that many fractional digits, ties to even.
+//!
+//! The big integers are sized for the exponent range of each format, so that the formats of up
+//! to 32 bits need well under a kibibyte of them, while binary128 needs about 2 KiB for each.
+//! Exact arithmetic on them is much slower than the Grisu algorithm of the standard library, so
+//! `Display` for [`F32`](crate::F32) and [`F64`](crate::F64) uses the standard library wherever
+//! the output is the same.
+//!
+//! Parsing accepts the same syntax as Rust's `FromStr` for `f32` and `f64`, plus the spellings
+//! `qnan`, `snan`, and `nan:0x…` for NaN values.  The decimal value is converted to an exact ratio
+//! of big integers, sized for the format as above, and rounded to the target format exactly once.
+//! `FromStr` for `F32` and `F64` tries the much faster parser of the standard library first, and
+//! falls back to this one only for the extra spellings.
+//!
+//! NaN values are printed as a bare `NaN`, losing their sign and payload.  The alternate flag `#`
+//! prints them as `nan:0x…` instead, giving the whole mantissa field in hex, so that the quiet bit
+//! and payload survive; the parser accepts this spelling too.
+
+use core::cmp::Ordering;
+use core::fmt::{self, Write};
+
+use crate::RoundingMode;
+use crate::error::{ParseError, ParseErrorKind};
+use crate::soft::{self, Format, Kind, Unpacked};
+
+/// Returns the number of 64-bit limbs used for formatting values of `fmt`.
+///
+/// The largest numbers involved arise for the least subnormal, whose digits are generated from
+/// `2^128 × 10^k / 2^(127 − exp)` with `10^k` just below `2^(−exp)`, and for the greatest finite
+/// value, whose digits are generated from `2^128 × 2^(emax − 127) / 10^k` with `10^k` just above
+/// `2^emax`.  A spare limb leaves room for the products by ten.
+const fn format_limbs(fmt: Format) -> usize {
+    let least = fmt.emin() - fmt.mant_bits() as i32;
+    let bits = max((fmt.emax() + 1) as usize, least.unsigned_abs() as usize) + 128;
+    bits / 64 + 2
+}
+
+/// Evaluates `$body` with the constant `$n` set to a number of limbs that is at least
+/// `$limbs($fmt)`.
+///
+/// Only the numbers of limbs that binary32, binary64, and binary128 need are used, so that the
+/// code is instantiated at most three times.
+macro_rules! with_limbs {
+    ($limbs:ident($fmt:expr), |$n:ident| $body:expr) => {{
+        let needed = $limbs($fmt);
+        if needed <= $limbs(crate::F32::FORMAT) {
+            const $n: usize = $limbs(crate::F32::FORMAT);
+            $body
+        } else if needed <= $limbs(crate::F64::FORMAT) {
+            const $n: usize = $limbs(crate::F64::FORMAT);
+            $body
+        } else {
+            const $n: usize = $limbs(crate::F128::FORMAT);
+            $body
+        }
+    }};
+}
+
+/// A fixed-width unsigned integer of `N` limbs, least significant limb first.
+///
+/// Every limb from `len` onwards is zero, so that operations can skip them.
+#[derive(Clone, Copy)]
+struct Big<const N: usize> {
+    limbs: [u64; N],
+    len: usize,
+}
+
+const fn max(a: usize, b: usize) -> usize {
+    if a > b { a } else { b }
+}
+
+impl<const N: usize> Big<N> {
+    const fn from_u128(value: u128) -> Self {
+        let mut limbs = [0; N];
+        limbs[0] = value as u64;
+        limbs[1] = (value >> 64) as u64;
+        Self { limbs, len: 2 }
+    }
+
+    const fn is_zero(&self) -> bool {
+        let mut i = 0;
+        while i < self.len {
+            if self.limbs[i] != 0 {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Returns the number of significant bits.
+    const fn bit_len(&self) -> u32 {
+        let mut i = self.len;
+        while i > 0 {
+            i -= 1;
+            if self.limbs[i] != 0 {
+                return (i as u32 + 1) * 64 - self.limbs[i].leading_zeros();
+            }
+        }
+        0
+    }
+
+    /// Computes `self × m + a`.
+    const fn mul_add_small(&mut self, m: u64, a: u64) {
+        let mut carry = a as u128;
+        let mut i = 0;
+        while i < self.len {
+            let v = self.limbs[i] as u128 * m as u128 + carry;
+            self.limbs[i] = v as u64;
+            carry = v >> 64;
+            i += 1;
+        }
+        if carry != 0 {
+            self.limbs[self.len] = carry as u64;
+            self.len += 1;
+        }
+    }
+
+    const fn mul_small(&mut self, m: u64) {
+        self.mul_add_small(m, 0);
+    }
+
+    const fn mul_pow10(&mut self, mut n: u32) {
+        while n >= 19 {
+            self.mul_small(10u64.pow(19));
+            n -= 19;
+        }
+        self.mul_small(10u64.pow(n));
+    }
+
+    const fn shl(&mut self, n: u32) {
+        let (words, bits) = ((n / 64) as usize, n % 64);
+        let len = self.len + words + (bits != 0) as usize;
+        self.len = if len > N { N } else { len };
+        let mut i = self.len;
+        while i > 0 {
+            i -= 1;
+            self.limbs[i] = if i < words {
+                0
+            } else {
+                let src = i - words;
+                let mut v = self.limbs[src] << bits;
+                if bits != 0 && src > 0 {
+                    v |= self.limbs[src - 1] >> (64 - bits);
+                }
+                v
+            };
+        }
+    }
+
+    const fn shr1(&mut self) {
+        let mut i = 0;
+        while i < self.len {
+            let next = if i + 1 < self.len { self.limbs[i + 1] } else { 0 };
+            self.limbs[i] = (self.limbs[i] >> 1) | (next << 63);
+            i += 1;
+        }
+    }
+
+    const fn cmp(&self, rhs: &Self) -> Ordering {
+        let mut i = max(self.len, rhs.len);
+        while i > 0 {
+            i -= 1;
+            if self.limbs[i] != rhs.limbs[i] {
+                return if self.limbs[i] < rhs.limbs[i] {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                };
+            }
+        }
+        Ordering::Equal
+    }
+
+    const fn add(&mut self, rhs: &Self) {
+        let len = max(self.len, rhs.len);
+        let mut carry = false;
+        let mut i = 0;
+        while i < len {
+            let (v, c1) = self.limbs[i].overflowing_add(rhs.limbs[i]);
+            let (v, c2) = v.overflowing_add(carry as u64);
+            self.limbs[i] = v;
+            carry = c1 || c2;
+            i += 1;
+        }
+        self.len = len;
+        if carry {
+            self.limbs[len] = 1;
+            self.len += 1;
+        }
+    }
+
+    /// Subtracts `rhs`, which must not be greater than `self`.
+    const fn sub(&mut self, rhs: &Self) {
+        let len = max(self.len, rhs.len);
+        let mut borrow = false;
+        let mut i = 0;
+        while i < len {
+            let (v, b1) = self.limbs[i].overflowing_sub(rhs.limbs[i]);
+            let (v, b2) = v.overflowing_sub(borrow as u64);
+            self.limbs[i] = v;
+            borrow = b1 || b2;
+            i += 1;
+        }
+        self.len = len;
+    }
+}
+
+/// Generates the decimal digits of a finite, non-zero value.
+///
+/// The remaining digits are those of `num / den`, which is always less than one.
+struct Digits<const N: usize> {
+    num: Big<N>,
+    den: Big<N>,
+    /// Decimal exponent of the leading digit: `10^exp10 ≤ value < 10^(exp10 + 1)`.
+    exp10: i32,
+}
+
+impl<const N: usize> Digits<N> {
+    /// Starts generating the digits of `sig × 2^(exp − 127)`.
+    fn new(exp: i32, sig: u128) -> Self {
+        let mut num = Big::from_u128(sig);
+        let mut den = Big::from_u128(1);
+        let shift = exp - 127;
+        if shift >= 0 {
+            num.shl(shift as u32);
+        } else {
+            den.shl(shift.unsigned_abs());
+        }
+
+        // 78913 / 2^18 is very close to log10(2), so this estimate is off by at most one.
+        let mut exp10 = ((exp as i64 * 78913) >> 18) as i32;
+        if exp10 + 1 >= 0 {
+            den.mul_pow10((exp10 + 1) as u32);
+        } else {
+            num.mul_pow10((exp10 + 1).unsigned_abs());
+        }
+        if !matches!(num.cmp(&den), Ordering::Less) {
+            den.mul_small(10);
+            exp10 += 1;
+        } else {
+            let mut tenfold = num;
+            tenfold.mul_small(10);
+            if matches!(tenfold.cmp(&den), Ordering::Less) {
+                num = tenfold;
+                exp10 -= 1;
+            }
+        }
+        Self { num, den, exp10 }
+    }
+
+    fn next_digit(&mut self) -> u8 {
+        self.num.mul_small(10);
+        let mut digit = 0;
+        while !matches!(self.num.cmp(&self.den), Ordering::Less) {
+            self.num.sub(&self.den);
+            digit += 1;
+        }
+        digit
+    }
+
+    /// Returns `true` if the digits generated so far should be rounded up, ties to even.
+    fn round_up(&self, odd: bool) -> bool {
+        let mut twice = self.num;
+        twice.shl(1);
+        match twice.cmp(&self.den) {
+            Ordering::Less => false,
+            Ordering::Equal => odd,
+            Ordering::Greater => true,
+        }
+    }
+}
+
+/// Writes digits in positional notation, given their place values in decreasing order.
+///
+/// Zeros are held back until a non-zero digit follows, so that leading and trailing zeros are
+/// only written where [`Self::finish`] asks for them.
+struct Positional<'a> {
+    w: &'a mut dyn Write,
+    started: bool,
+    /// Place value of the next digit to write.
+    next: i32,
+}
+
+impl<'a> Positional<'a> {
+    fn new(w: &'a mut dyn Write) -> Self {
+        Self { w, started: false, next: 0 }
+    }
+
+    fn put(&mut self, digit: u8, place: i32) -> fmt::Result {
+        if place == -1 {
+            self.w.write_char('.')?;
+        }
+        self.w.write_char((b'0' + digit) as char)
+    }
+
+    fn digit(&mut self, digit: u8, place: i32) -> fmt::Result {
+        if digit == 0 {
+            return Ok(());
+        }
+        if !self.started {
+            self.started = true;
+            self.next = if place < 0 { -1 } else { place };
+            if place < 0 {
+                self.w.write_char('0')?;
+            }
+        }
+        while self.next > place {
+            self.put(0, self.next)?;
+            self.next -= 1;
+        }
+        self.put(digit, place)?;
+        self.next = place - 1;
+        Ok(())
+    }
+
+    /// Writes any zeros needed to reach `place`, which is at most zero.
+    fn finish(mut self, place: i32) -> fmt::Result {
+        if !self.started {
+            self.w.write_char('0')?;
+            self.next = -1;
+        }
+        while self.next >= place {
+            self.put(0, self.next)?;
+            self.next -= 1;
+        }
+        Ok(())
+    }
+}
+
+/// Writes `sig × 2^(exp − 127)` rounded to `frac` fractional digits.
+///
+/// This and the other users of the big integers are never inlined, so that the stack frames of
+/// their callers do not hold the big integers of every size at once.
+#[inline(never)]
+fn write_rounded<const N: usize>(w: &mut dyn Write, exp: i32, sig: u128, frac: i32) -> fmt::Result {
+    let mut digits = Digits::<N>::new(exp, sig);
+    let mut out = Positional::new(w);
+    let count = digits.exp10 + 1 + frac;
+
+    // A run of nines is held back together with the digit before it, which starts out as a
+    // leading zero, since rounding up may carry into it.
+    let (mut held, mut held_place, mut nines) = (0, digits.exp10 + 1, 0);
+    for i in 1..=count {
+        let digit = digits.next_digit();
+        if digit == 9 {
+            nines += 1;
+        } else {
+            out.digit(held, held_place)?;
+            for j in 1..=nines {
+                out.digit(9, held_place - j)?;
+            }
+            (held, held_place, nines) = (digit, digits.exp10 + 1 - i, 0);
+        }
+    }
+    if count >= 0 {
+        let odd = nines > 0 || (held & 1) != 0;
+        let (held, tail) = if digits.round_up(odd) { (held + 1, 0) } else { (held, 9) };
+        out.digit(held, held_place)?;
+        for j in 1..=nines {
+            out.digit(tail, held_place - j)?;
+        }
+    }
+    out.finish(-frac)
+}
+
+/// Counts the bytes written, discarding them.
+struct Counter(usize);
+
+impl Write for Counter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+/// Writes `sign` and the output of `body`, honoring the width, fill, and alignment of `f`.
+pub(crate) fn pad(
+    f: &mut fmt::Formatter<'_>,
+    sign: &str,
+    body: impl Fn(&mut dyn Write) -> fmt::Result,
+) -> fmt::Result {
+    let width = match f.width() {
+        Some(width) => width,
+        None => {
+            f.write_str(sign)?;
+            return body(f);
+        },
+    };
+    let mut counter = Counter(sign.len());
+    body(&mut counter)?;
+    let len = counter.0;
+    if len >= width {
+        f.write_str(sign)?;
+        return body(f);
+    }
+    let fill = width - len;
+    if f.sign_aware_zero_pad() {
+        f.write_str(sign)?;
+        for _ in 0..fill {
+            f.write_char('0')?;
+        }
+        return body(f);
+    }
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Left) => (0, fill),
+        Some(fmt::Alignment::Center) => (fill / 2, fill - fill / 2),
+        Some(fmt::Alignment::Right) | None => (fill, 0),
+    };
+    let c = f.fill();
+    for _ in 0..before {
+        f.write_char(c)?;
+    }
+    f.write_str(sign)?;
+    body(f)?;
+    for _ in 0..after {
+        f.write_char(c)?;
+    }
+    Ok(())
+}
+
+/// Maximum number of digits in the shortest representation of a value of any format.
+const SHORTEST_DIGITS: usize = 40;
+
+/// Returns `(digits, len, exp10)`, the shortest decimal digits that round to the finite, non-zero
+/// float `bits` of `fmt`, where the leading digit has place value `10^exp10`.
+///
+/// This is the free-format algorithm of Steele and White: digits are generated exactly, together
+/// with the distances to the midpoints between `bits` and its neighbors, until the digits so far
+/// lie strictly within the interval that rounds to `bits`, or on its boundary if ties round to
+/// `bits`.  The last digit is then chosen to be as close as possible to the exact value, rounding
+/// up when both candidates are equally close, as the standard library does.
+#[inline(never)]
+fn shortest<const N: usize>(fmt: Format, bits: u128) -> ([u8; SHORTEST_DIGITS], usize, i32) {
+    let mant_bits = fmt.mant_bits();
+    let exp_field = ((bits & fmt.exp_mask()) >> mant_bits) as i32;
+    let mant = bits & fmt.mant_mask();
+    let (m, e) = if exp_field == 0 {
+        (mant, fmt.emin() - mant_bits as i32)
+    } else {
+        (mant | (1 << mant_bits), exp_field - fmt.bias() - mant_bits as i32)
+    };
+    // The gap below is half as wide at the bottom of a binade, except for the least normal.
+    let narrow = mant == 0 && exp_field > 1;
+    let inclusive = (m & 1) == 0;
+
+    // The value is `num / den`; the midpoints below and above are `(num ∓ lo/hi) / den`.
+    let mut num = Big::<N>::from_u128(m);
+    num.shl(2);
+    let mut hi = Big::<N>::from_u128(2);
+    let mut lo = Big::<N>::from_u128(if narrow { 1 } else { 2 });
+    let mut den = Big::<N>::from_u128(4);
+    if e >= 0 {
+        num.shl(e as u32);
+        hi.shl(e as u32);
+        lo.shl(e as u32);
+    } else {
+        den.shl(e.unsigned_abs());
+    }
+
+    // Scale by a power of ten so that the upper midpoint lies in [0.1, 1), as for `Digits`.
+    let top = soft::unpack(fmt, bits).exp;
+    let mut exp10 = ((top as i64 * 78913) >> 18) as i32;
+    if exp10 + 1 >= 0 {
+        den.mul_pow10((exp10 + 1) as u32);
+    } else {
+        let n = (exp10 + 1).unsigned_abs();
+        num.mul_pow10(n);
+        hi.mul_pow10(n);
+        lo.mul_pow10(n);
+    }
+    let above = |num: &Big<N>, hi: &Big<N>, den: &Big<N>| {
+        let mut high = *num;
+        high.add(hi);
+        match high.cmp(den) {
+            Ordering::Less => false,
+            Ordering::Equal => inclusive,
+            Ordering::Greater => true,
+        }
+    };
+    while above(&num, &hi, &den) {
+        den.mul_small(10);
+        exp10 += 1;
+    }
+    loop {
+        let (mut num10, mut hi10) = (num, hi);
+        num10.mul_small(10);
+        hi10.mul_small(10);
+        if above(&num10, &hi10, &den) {
+            break;
+        }
+        (num, hi) = (num10, hi10);
+        lo.mul_small(10);
+        exp10 -= 1;
+    }
+
+    let mut digits = [0; SHORTEST_DIGITS];
+    let mut len = 0;
+    loop {
+        num.mul_small(10);
+        hi.mul_small(10);
+        lo.mul_small(10);
+        let mut digit = 0;
+        while !matches!(num.cmp(&den), Ordering::Less) {
+            num.sub(&den);
+            digit += 1;
+        }
+        let low_ok = match num.cmp(&lo) {
+            Ordering::Less => true,
+            Ordering::Equal => inclusive,
+            Ordering::Greater => false,
+        };
+        let high_ok = above(&num, &hi, &den);
+        let round_up = match (low_ok, high_ok) {
+            (false, false) => {
+                digits[len] = digit;
+                len += 1;
+                continue;
+            },
+            (true, false) => false,
+            (false, true) => true,
+            (true, true) => {
+                let mut twice = num;
+                twice.shl(1);
+                !matches!(twice.cmp(&den), Ordering::Less)
+            },
+        };
+        digits[len] = digit + round_up as u8;
+        len += 1;
+        return (digits, len, exp10);
+    }
+}
+
+/// Writes `digits`, the first with place value `10^exp10`, with at least `min_frac` fractional
+/// digits.
+fn write_digits(w: &mut dyn Write, digits: &[u8], exp10: i32, min_frac: i32) -> fmt::Result {
+    let mut out = Positional::new(w);
+    for (i, &digit) in digits.iter().enumerate() {
+        out.digit(digit, exp10 - i as i32)?;
+    }
+    out.finish(-min_frac)
+}
+
+/// Writes the exact value of the float `bits` of `fmt` in decimal, with every significant digit.
+#[cfg(feature = "alloc")]
+pub(crate) fn write_exact(w: &mut dyn Write, fmt: Format, bits: u128) -> fmt::Result {
+    let x = soft::unpack(fmt, bits);
+    if x.sign && !matches!(x.kind, Kind::Nan) {
+        w.write_char('-')?;
+    }
+    match x.kind {
+        Kind::Nan => w.write_str("NaN"),
+        Kind::Infinite => w.write_str("inf"),
+        Kind::Zero => w.write_char('0'),
+        Kind::Finite => with_limbs!(format_limbs(fmt), |N| write_all_digits::<N>(w, x.exp, x.sig)),
+    }
+}
+
+/// Writes `sig × 2^(exp − 127)` with every significant digit.
+#[cfg(feature = "alloc")]
+#[inline(never)]
+fn write_all_digits<const N: usize>(w: &mut dyn Write, exp: i32, sig: u128) -> fmt::Result {
+    let mut digits = Digits::<N>::new(exp, sig);
+    let mut out = Positional::new(w);
+    let mut place = digits.exp10;
+    while !digits.num.is_zero() {
+        out.digit(digits.next_digit(), place)?;
+        place -= 1;
+    }
+    out.finish(0)
+}
+
+/// Formats the float `bits` of `fmt` in decimal, as `Display` does for the Rust float types.
+///
+/// Without a precision, the output is the shortest decimal that parses back to exactly `bits`,
+/// choosing the one closest to the exact value if there are several.  With a precision, the exact
+/// value is rounded to that many fractional digits.  Otherwise, the alternate flag `#` asks for at
+/// least one fractional digit, so that the output always reads as a float: `1.0` rather than `1`.
+///
+/// The `+` flag, width, fill, alignment, and zero padding are honored as for the Rust floats.
+pub(crate) fn display(fmt: Format, bits: u128, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let x = soft::unpack(fmt, bits);
+    let sign = if x.sign {
+        "-"
+    } else if f.sign_plus() {
+        "+"
+    } else {
+        ""
+    };
+    let frac = f.precision().map_or(f.alternate() as i32, |p| p.min(i32::MAX as usize / 2) as i32);
+    match x.kind {
+        Kind::Nan if f.alternate() => {
+            let mant = bits & fmt.mant_mask();
+            pad(f, sign, |w| write!(w, "nan:{mant:#x}"))
+        },
+        Kind::Nan => pad(f, "", |w| w.write_str("NaN")),
+        Kind::Infinite => pad(f, sign, |w| w.write_str("inf")),
+        Kind::Zero => pad(f, sign, |w| Positional::new(w).finish(-frac)),
+        Kind::Finite if f.precision().is_none() => {
+            let (digits, len, exp10) = with_limbs!(format_limbs(fmt), |N| shortest::<N>(fmt, bits));
+            pad(f, sign, |w| write_digits(w, &digits[..len], exp10, frac))
+        },
+        Kind::Finite => pad(f, sign, |w| {
+            with_limbs!(format_limbs(fmt), |N| write_rounded::<N>(w, x.exp, x.sig, frac))
+        }),
+    }
+}
+
+/// The error returned when a buffer is too small to hold the formatted value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BufferTooSmall;
+
+impl fmt::Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("buffer too small for formatted float")
+    }
+}
+
+impl core::error::Error for BufferTooSmall {}
+
+/// Writes into a byte slice, failing once it is full.
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.buf.len() {
+            return Err(fmt::Error);
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Writes the float `bits` of `fmt` into `buf` as `Display` does without flags, and returns the
+/// written part of `buf`.
+pub(crate) fn format_into(fmt: Format, bits: u128, buf: &mut [u8]) -> Result<&str, BufferTooSmall> {
+    let mut w = SliceWriter { buf, len: 0 };
+    write_plain(&mut w, fmt, bits).map_err(|_| BufferTooSmall)?;
+    let SliceWriter { buf, len } = w;
+    Ok(core::str::from_utf8(&buf[..len]).expect("decimal output is ASCII"))
+}
+
+/// Writes the float `bits` of `fmt` as `Display` does without flags, straight to `w`.
+fn write_plain(w: &mut dyn Write, fmt: Format, bits: u128) -> fmt::Result {
+    let x = soft::unpack(fmt, bits);
+    match x.kind {
+        Kind::Nan => w.write_str("NaN"),
+        Kind::Infinite if x.sign => w.write_str("-inf"),
+        Kind::Infinite => w.write_str("inf"),
+        _ if x.sign && w.write_char('-').is_err() => Err(fmt::Error),
+        Kind::Zero => w.write_char('0'),
+        Kind::Finite => {
+            let (digits, len, exp10) = with_limbs!(format_limbs(fmt), |N| shortest::<N>(fmt, bits));
+            write_digits(w, &digits[..len], exp10, 0)
+        },
+    }
+}
+
+/// Writes the float `bits` of `fmt` to a `ufmt` formatter as `Display` does without flags.
+///
+/// Only [`Write::write_str`] and [`Write::write_char`] are used on the way, so none of the
+/// `core::fmt` formatting machinery is pulled in.
+#[cfg(feature = "ufmt")]
+pub(crate) fn write_ufmt<W: ufmt::uWrite + ?Sized>(
+    f: &mut ufmt::Formatter<'_, W>,
+    fmt: Format,
+    bits: u128,
+) -> Result<(), W::Error> {
+    struct Adapter<'a, 'w, W: ufmt::uWrite + ?Sized> {
+        f: &'a mut ufmt::Formatter<'w, W>,
+        error: Option<W::Error>,
+    }
+
+    impl<W: ufmt::uWrite + ?Sized> Write for Adapter<'_, '_, W> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.f.write_str(s).map_err(|err| {
+                self.error = Some(err);
+                fmt::Error
+            })
+        }
+    }
+
+    let mut w = Adapter { f, error: None };
+    match write_plain(&mut w, fmt, bits) {
+        Ok(()) => Ok(()),
+        Err(_) => Err(w.error.expect("only the writer fails")),
+    }
+}
+
+/// Returns the maximum number of significant decimal digits kept when parsing values of `fmt`.
+///
+/// Every midpoint between two adjacent values of `fmt` has fewer significant digits than this,
+/// so replacing the digits beyond it with a sticky bit never changes the rounding.  A midpoint is
+/// `k × 2^−q` for some `k < 2^(p + 1)` and `q ≤ p − emin`, whose digits are those of `k × 5^q`;
+/// 183231 / 2^18 is just above log10(5).
+const fn max_digits(fmt: Format) -> i64 {
+    let p = fmt.precision() as i64;
+    (((p + 1) * 78913 + (p - fmt.emin() as i64) * 183231) >> 18) + 2
+}
+
+/// Returns `(lo, hi)` such that a decimal in `[10^(magnitude − 1), 10^magnitude)` rounds to zero
+/// or to the least subnormal of `fmt` if `magnitude < lo`, and overflows if `magnitude > hi`.
+///
+/// `10^lo` is below half the least subnormal, `2^(emin − p)`, and `10^hi` is above `2^(emax + 1)`,
+/// each with a margin for the estimate of log10(2).
+const fn magnitude_range(fmt: Format) -> (i64, i64) {
+    let tiny = (fmt.emin() - fmt.precision() as i32) as i64;
+    let huge = (fmt.emax() + 1) as i64;
+    (((tiny * 78913) >> 18) - 1, ((huge * 78913) >> 18) + 2)
+}
+
+/// Returns the number of 64-bit limbs used for parsing values of `fmt`.
+///
+/// After the range checks in [`parse`], the denominator is at most `10^(max_digits − lo)`, where
+/// 851 / 256 is just above log2(10), and the numerator is scaled to 127 bits more than that.
+/// Comparing with a decimal in [`cmp_decimal`] needs a few more powers of ten.
+const fn parse_limbs(fmt: Format) -> usize {
+    let digits = max_digits(fmt) - magnitude_range(fmt).0 + 4;
+    let bits = (digits * 851 / 256) as usize + 128;
+    bits / 64 + 2
+}
+
+/// Accumulates decimal digits into a big integer, 19 at a time.
+struct Accumulator<const N: usize> {
+    value: Big<N>,
+    chunk: u64,
+    chunk_len: u32,
+}
+
+impl<const N: usize> Accumulator<N> {
+    const fn push(&mut self, digit: u8) {
+        self.chunk = self.chunk * 10 + digit as u64;
+        self.chunk_len += 1;
+        if self.chunk_len == 19 {
+            self.flush();
+        }
+    }
+
+    const fn flush(&mut self) {
+        self.value.mul_add_small(10u64.pow(self.chunk_len), self.chunk);
+        (self.chunk, self.chunk_len) = (0, 0);
+    }
+}
+
+/// Parses `s` with the syntax of Rust's `FromStr` for floats and rounds it to `fmt`.
+pub(crate) fn parse(fmt: Format, s: &str, mode: RoundingMode) -> Result<u128, ParseError> {
+    parse_bytes(fmt, s.as_bytes(), mode)
+}
+
+/// Splits off the optional sign of `s`, returning whether it is negative and where the rest
+/// starts.
+pub(crate) const fn split_sign(s: &[u8]) -> Result<(bool, usize), ParseError> {
+    match s.first() {
+        None => Err(ParseError::empty()),
+        Some(b'+') => Ok((false, 1)),
+        Some(b'-') => Ok((true, 1)),
+        Some(_) => Ok((false, 0)),
+    }
+}
+
+/// Parses the hex digits of a `nan:0x…` mantissa field from byte `start` of `s` to the end.
+///
+/// The field must be non-zero and fit in `mask`.
+const fn parse_nan_mantissa(s: &[u8], start: usize, mask: u128) -> Result<u128, ParseError> {
+    let mut mant: u128 = 0;
+    let mut i = start;
+    while i < s.len() {
+        let digit = match (s[i] as char).to_digit(16) {
+            Some(digit) => digit,
+            None => return Err(ParseError::invalid(s, i)),
+        };
+        mant = (mant << 4) | digit as u128;
+        if mant > mask {
+            return Err(ParseError::new(ParseErrorKind::FieldTooLarge, s, i));
+        }
+        i += 1;
+    }
+    if mant == 0 {
+        return Err(ParseError::new(ParseErrorKind::FieldTooSmall, s, start));
+    }
+    Ok(mant)
+}
+
+/// Parses the spellings of infinity and NaN from byte `start` of `s`, returning `None` if the
+/// rest of `s` is none of them.
+///
+/// These are `inf` and `infinity`; `nan` and `qnan` for a quiet NaN; `snan` for a signaling NaN;
+/// and `nan:0x…` for a NaN with the given mantissa field.  Case is ignored.  The sign has already
+/// been parsed, giving `sign_bit`.
+pub(crate) const fn parse_special(
+    fmt: Format,
+    sign_bit: u128,
+    s: &[u8],
+    start: usize,
+) -> Option<Result<u128, ParseError>> {
+    let special = sign_bit | fmt.exp_mask();
+    let rest = s.split_at(start).1;
+    if rest.eq_ignore_ascii_case(b"inf") || rest.eq_ignore_ascii_case(b"infinity") {
+        Some(Ok(special))
+    } else if rest.eq_ignore_ascii_case(b"nan") || rest.eq_ignore_ascii_case(b"qnan") {
+        Some(Ok(special | fmt.quiet_mask()))
+    } else if rest.eq_ignore_ascii_case(b"snan") {
+        Some(Ok(special | 1))
+    } else if rest.len() > 6 && rest.split_at(6).0.eq_ignore_ascii_case(b"nan:0x") {
+        Some(match parse_nan_mantissa(s, start + 6, fmt.mant_mask()) {
+            Ok(mant) => Ok(special | mant),
+            Err(err) => Err(err),
+        })
+    } else {
+        None
+    }
+}
+
+/// A decimal number, `value × 10^scale`, plus a little more if `sticky` is set.
+///
+/// Only the first [`max_digits`] significant digits are kept in `value`, which has `kept` digits.
+/// `sticky` records whether any of the digits dropped were non-zero.
+struct Decimal<const N: usize> {
+    value: Big<N>,
+    kept: i64,
+    scale: i64,
+    sticky: bool,
+}
+
+/// Scans an unsigned decimal number with an optional exponent, from byte `start` of `s` to the
+/// end, keeping at most `limit` significant digits.
+const fn scan<const N: usize>(
+    s: &[u8],
+    start: usize,
+    limit: i64,
+) -> Result<Decimal<N>, ParseError> {
+    let mut acc = Accumulator::<N> { value: Big::from_u128(0), chunk: 0, chunk_len: 0 };
+    let (mut kept, mut scale, mut sticky) = (0i64, 0i64, false);
+    let (mut mant_digits, mut in_frac) = (0, false);
+    let mut i = start;
+    while i < s.len() {
+        match s[i] {
+            b @ b'0'..=b'9' => {
+                let digit = b - b'0';
+                if kept < limit && (kept > 0 || digit != 0) {
+                    acc.push(digit);
+                    kept += 1;
+                    scale -= in_frac as i64;
+                } else if kept == 0 {
+                    scale -= in_frac as i64;
+                } else {
+                    sticky |= digit != 0;
+                    scale += !in_frac as i64;
+                }
+                mant_digits += 1;
+            },
+            b'.' if !in_frac => in_frac = true,
+            _ => break,
+        }
+        i += 1;
+    }
+    if mant_digits == 0 {
+        return Err(ParseError::invalid(s, i));
+    }
+    if i < s.len() && matches!(s[i], b'e' | b'E') {
+        i += 1;
+        let neg = i < s.len() && s[i] == b'-';
+        if i < s.len() && matches!(s[i], b'+' | b'-') {
+            i += 1;
+        }
+        if i == s.len() {
+            return Err(ParseError::invalid(s, i));
+        }
+        let mut exp = 0i64;
+        while i < s.len() {
+            let b = s[i];
+            if !b.is_ascii_digit() {
+                return Err(ParseError::invalid(s, i));
+            }
+            exp = exp * 10 + (b - b'0') as i64;
+            if exp > 1 << 40 {
+                exp = 1 << 40;
+            }
+            i += 1;
+        }
+        scale += if neg { -exp } else { exp };
+    }
+    if i < s.len() {
+        return Err(ParseError::invalid(s, i));
+    }
+    acc.flush();
+    Ok(Decimal { value: acc.value, kept, scale, sticky })
+}
+
+/// Implements [`parse`] in a form that can be evaluated at compile time.
+pub(crate) const fn parse_bytes(
+    fmt: Format,
+    s: &[u8],
+    mode: RoundingMode,
+) -> Result<u128, ParseError> {
+    let (sign, start) = match split_sign(s) {
+        Ok(split) => split,
+        Err(err) => return Err(err),
+    };
+    let sign_bit = if sign { fmt.sign_mask() } else { 0 };
+    if let Some(result) = parse_special(fmt, sign_bit, s, start) {
+        return result;
+    }
+    with_limbs!(parse_limbs(fmt), |N| parse_number::<N>(fmt, sign, s, start, mode))
+}
+
+/// Parses the unsigned decimal number from byte `start` of `s` to the end, with the sign already
+/// parsed, and rounds it to `fmt`.
+///
+/// This is never inlined, so that the stack frame holds the big integers of one size only.
+#[inline(never)]
+const fn parse_number<const N: usize>(
+    fmt: Format,
+    sign: bool,
+    s: &[u8],
+    start: usize,
+    mode: RoundingMode,
+) -> Result<u128, ParseError> {
+    let Decimal { value: acc, kept, scale, mut sticky } = match scan::<N>(s, start, max_digits(fmt))
+    {
+        Ok(decimal) => decimal,
+        Err(err) => return Err(err),
+    };
+
+    // The value lies in [10^(magnitude − 1), 10^magnitude).
+    let magnitude = kept + scale;
+    let (lo, hi) = magnitude_range(fmt);
+    let value = if kept == 0 {
+        Unpacked::special(sign, Kind::Zero)
+    } else if magnitude > hi {
+        // Far above the greatest finite value, so this overflows.
+        Unpacked::finite(sign, 1 << 20, 1 << 127)
+    } else if magnitude < lo {
+        // Far below half the least subnormal, so only the direction of rounding matters.
+        sticky = true;
+        Unpacked::finite(sign, -(1 << 20), 1 << 127)
+    } else {
+        let (mut num, mut den) = (acc, Big::<N>::from_u128(1));
+        if scale >= 0 {
+            num.mul_pow10(scale as u32);
+        } else {
+            den.mul_pow10(scale.unsigned_abs() as u32);
+        }
+
+        // Scale the numerator so that the quotient lies in [2^126, 2^128).
+        let shift = den.bit_len() as i32 + 127 - num.bit_len() as i32;
+        if shift >= 0 {
+            num.shl(shift as u32);
+        } else {
+            den.shl(shift.unsigned_abs());
+        }
+        let mut sig: u128 = 0;
+        let mut part = den;
+        part.shl(127);
+        let mut i = 128;
+        while i > 0 {
+            i -= 1;
+            if !matches!(num.cmp(&part), Ordering::Less) {
+                num.sub(&part);
+                sig |= 1 << i;
+            }
+            part.shr1();
+        }
+        let mut exp = 127 - shift;
+        if (sig >> 127) == 0 {
+            num.shl(1);
+            sig <<= 1;
+            if !matches!(num.cmp(&den), Ordering::Less) {
+                num.sub(&den);
+                sig |= 1;
+            }
+            exp -= 1;
+        }
+        sticky |= !num.is_zero();
+        Unpacked::finite(sign, exp, sig)
+    };
+    Ok(soft::round(fmt, value, sticky, mode))
+}
+
+/// Compares the float `bits` of `fmt` with the exact value of the decimal string `s`.
+///
+/// Values are ordered as by `total_cmp`.  The spellings of infinity and NaN in `s` compare as the
+/// corresponding bits of `fmt` would.
+pub(crate) fn cmp_decimal(fmt: Format, bits: u128, s: &str) -> Result<Ordering, ParseError> {
+    let s = s.as_bytes();
+    let (sign, start) = split_sign(s)?;
+    let sign_bit = if sign { fmt.sign_mask() } else { 0 };
+    if let Some(result) = parse_special(fmt, sign_bit, s, start) {
+        let key = |bits: u128| {
+            let abs = (bits & !fmt.sign_mask()) as i128;
+            if (bits & fmt.sign_mask()) != 0 { -1 - abs } else { abs }
+        };
+        return Ok(key(bits).cmp(&key(result?)));
+    }
+    with_limbs!(parse_limbs(fmt), |N| cmp_number::<N>(fmt, bits, sign, s, start))
+}
+
+/// Compares the float `bits` of `fmt` with the unsigned decimal number from byte `start` of `s`
+/// to the end, whose sign is `sign`.
+///
+/// This is never inlined, so that the stack frame holds the big integers of one size only.
+#[inline(never)]
+fn cmp_number<const N: usize>(
+    fmt: Format,
+    bits: u128,
+    sign: bool,
+    s: &[u8],
+    start: usize,
+) -> Result<Ordering, ParseError> {
+    let decimal = scan::<N>(s, start, max_digits(fmt))?;
+    let x = soft::unpack(fmt, bits);
+    if x.sign != sign {
+        return Ok(if x.sign { Ordering::Less } else { Ordering::Greater });
+    }
+    let ord = match x.kind {
+        Kind::Nan | Kind::Infinite => Ordering::Greater,
+        Kind::Zero if decimal.kept == 0 => Ordering::Equal,
+        Kind::Zero => Ordering::Less,
+        Kind::Finite if decimal.kept == 0 => Ordering::Greater,
+        Kind::Finite => cmp_magnitude(x.exp, x.sig, &decimal),
+    };
+    Ok(if sign { ord.reverse() } else { ord })
+}
+
+/// Compares `sig × 2^(exp − 127)` with the non-zero decimal `d`.
+fn cmp_magnitude<const N: usize>(exp: i32, sig: u128, d: &Decimal<N>) -> Ordering {
+    // The float lies in [10^(exp10 − 1), 10^(exp10 + 2)), since the estimate is off by at most
+    // one, and the decimal lies in [10^(magnitude − 1), 10^magnitude).  Settling the far apart
+    // cases here keeps the big integers below within `parse_limbs`.
+    let exp10 = (exp as i64 * 78913) >> 18;
+    let magnitude = d.kept + d.scale;
+    if exp10 > magnitude {
+        return Ordering::Greater;
+    } else if exp10 + 2 < magnitude {
+        return Ordering::Less;
+    }
+
+    let (mut lhs, mut rhs) = (Big::<N>::from_u128(sig), d.value);
+    let shift = exp - 127;
+    if shift >= 0 {
+        lhs.shl(shift as u32);
+    } else {
+        rhs.shl(shift.unsigned_abs());
+    }
+    if d.scale >= 0 {
+        rhs.mul_pow10(d.scale as u32);
+    } else {
+        lhs.mul_pow10(d.scale.unsigned_abs() as u32);
+    }
+    match lhs.cmp(&rhs) {
+        // Every float has fewer than `max_digits` significant digits, so when it differs from the
+        // kept digits, it differs by at least one unit in the last kept place, and the dropped
+        // digits cannot change the outcome.  When it is equal, they can only make `d` larger.
+        Ordering::Equal if d.sticky => Ordering::Less,
+        ord => ord,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::format;
+
+    use super::*;
+    use crate::{BF16, F16, F64, F128};
+
+    struct Soft(F64);
+
+    struct SoftF32(u32);
+
+    impl fmt::Display for SoftF32 {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            display(crate::F32::FORMAT, self.0 as u128, f)
+        }
+    }
+
+    impl fmt::Display for Soft {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            display(F64::FORMAT, self.0.to_bits() as u128, f)
+        }
+    }
+
+    #[test]
+    fn matches_native_test() {
+        let values = [
+            0.1,
+            0.5,
+            2.5,
+            -1.25,
+            9.96,
+            123456.789,
+            1e300,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            5e-324,
+            0.0,
+            -0.0,
+            f64::INFINITY,
+            f64::NAN,
+        ];
+        for x in values {
+            let soft = Soft(F64::from_float(x));
+            for p in [0, 1, 2, 5, 17, 30, 1100] {
+                assert_eq!(format!("{x:.p$}"), format!("{soft:.p$}"), "{x:e} with precision {p}");
+            }
+            assert_eq!(format!("{x:>+12.3}"), format!("{soft:>+12.3}"));
+            assert_eq!(format!("{x:*^14.1}"), format!("{soft:*^14.1}"));
+            assert_eq!(format!("{x:010.2}"), format!("{soft:010.2}"));
+        }
+        assert_eq!("0.30000000000000004", format!("{}", Soft(F64::from_float(0.1 + 0.2))));
+        assert_eq!("0.1", format!("{}", Soft(F64::from_float(0.1))));
+    }
+
+    #[test]
+    fn shortest_matches_native_test() {
+        // A fixed linear congruential generator, so that the test is deterministic.
+        let mut state = 0x853c49e6748fea9bu64;
+        let mut next = || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            state
+        };
+        let specials =
+            [0x0000000000000001, 0x000fffffffffffff, 0x0010000000000000, 0x7fefffffffffffff];
+        for bits in specials.into_iter().chain((0..2000).map(|_| next())) {
+            let x = f64::from_bits(bits);
+            assert_eq!(format!("{x}"), format!("{}", Soft(F64::from_float(x))), "{bits:#x}");
+            let y = f32::from_bits(bits as u32);
+            let soft = format!("{}", SoftF32(y.to_bits()));
+            assert_eq!(format!("{y}"), soft, "{:#x}", bits as u32);
+        }
+        for x in [0.1, 1e23, 5e-324, 1e300, 123456.0, 2.0f64.powi(-1022), 9007199254740993.0] {
+            assert_eq!(format!("{x}"), format!("{}", Soft(F64::from_float(x))));
+        }
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let mut state = 0xda3e39cb94b95bdbu64;
+        let mut next = || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            state
+        };
+        for _ in 0..300 {
+            let bits = (next() as u128) << 64 | next() as u128;
+            let x = F128::from_bits(bits);
+            if x.is_finite() {
+                assert_eq!(x, format!("{x}").parse().unwrap(), "{bits:#x}");
+            }
+        }
+        for bits in 0..=u16::MAX {
+            let (x, y) = (F16::from_bits(bits), BF16::from_bits(bits));
+            if x.is_finite() {
+                assert_eq!(x, format!("{x}").parse().unwrap(), "{bits:#x}");
+            }
+            if y.is_finite() {
+                assert_eq!(y, format!("{y}").parse().unwrap(), "{bits:#x}");
+            }
+        }
+    }
+
+    #[test]
+    fn parse_matches_native_test() {
+        let halfway = format!("9007199254740993{}", "0".repeat(800));
+        let above = format!("{halfway}1");
+        let cases = [
+            "0.1",
+            "-1.5e+3",
+            "1.",
+            ".5",
+            "+0",
+            "-0.0e7",
+            "9007199254740993",
+            &halfway,
+            &above,
+            "2.2250738585072011e-308",
+            "4.9406564584124654e-324",
+            "2.4703282292062327e-324",
+            "2.4703282292062328e-324",
+            "1.7976931348623157e308",
+            "1.7976931348623159e308",
+            "1e400",
+            "1e-400",
+            "1e99999999999999999999",
+            "0.000000000000000000000000000000001e-290",
+            "inf",
+            "-Infinity",
+        ];
+        for s in cases {
+            let native = s.parse::<f64>().unwrap().to_bits() as u128;
+            let soft = parse(F64::FORMAT, s, RoundingMode::TiesToEven).unwrap();
+            assert_eq!(native, soft, "{s}");
+            let native = s.parse::<f32>().unwrap().to_bits() as u128;
+            let soft = parse(crate::F32::FORMAT, s, RoundingMode::TiesToEven).unwrap();
+            assert_eq!(native, soft, "{s}");
+        }
+        // Half the least binary32 subnormal, exactly, which is a tie that rounds to zero.
+        let half = format!("{:.160}", 2f64.powi(-150));
+        for s in [half.clone(), format!("{half}1"), format!("-{half}00001")] {
+            let native = s.parse::<f32>().unwrap().to_bits() as u128;
+            let soft = parse(crate::F32::FORMAT, &s, RoundingMode::TiesToEven).unwrap();
+            assert_eq!(native, soft, "{s}");
+        }
+        use ParseErrorKind::*;
+        let errors = [
+            ("", Empty, 0, None),
+            (".", InvalidSyntax, 1, None),
+            ("e5", InvalidSyntax, 0, Some('e')),
+            ("1e", InvalidSyntax, 2, None),
+            ("1e+", InvalidSyntax, 3, None),
+            ("1x", InvalidSyntax, 1, Some('x')),
+            ("+", InvalidSyntax, 1, None),
+            ("-.e1", InvalidSyntax, 2, Some('e')),
+            ("1.2.3", InvalidSyntax, 3, Some('.')),
+            ("nan1", InvalidSyntax, 0, Some('n')),
+            (" 1", InvalidSyntax, 0, Some(' ')),
+            ("1.5\u{e9}", InvalidSyntax, 3, Some('\u{e9}')),
+            ("nan:0x10000000000000", FieldTooLarge, 19, Some('0')),
+            ("-nan:0x00", FieldTooSmall, 7, Some('0')),
+        ];
+        for (s, kind, offset, token) in errors {
+            assert!(s.parse::<f64>().is_err(), "{s}");
+            let err = parse(F64::FORMAT, s, RoundingMode::TiesToEven).unwrap_err();
+            assert_eq!((kind, offset, token), (err.kind(), err.offset(), err.token()), "{s}");
+        }
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(BF16::from_bits(0x3dcd), "0.1".parse().unwrap());
+        assert_eq!(F16::MAX, "65519".parse().unwrap());
+        assert_eq!(F16::INFINITY, "65520".parse().unwrap());
+        assert_eq!(F16::from_bits(1), "0.00000003".parse().unwrap());
+        assert!("NaN".parse::<F16>().unwrap().is_nan());
+
+        const THIRD: F128 = F128::from_bits(0x3ffd5555555555555555555555555555);
+        const TENTH: F128 = F128::from_bits(0x3ffb999999999999999999999999999a);
+        assert_eq!(TENTH, "0.1".parse().unwrap());
+        assert_eq!(THIRD, "0.3333333333333333333333333333333333333333".parse().unwrap());
+        for x in [THIRD, TENTH, F128::MAX, F128::MIN_POSITIVE, F128::from_bits(1), F128::NEG_ONE] {
+            assert_eq!(x, format!("{x}").parse().unwrap());
+        }
+        let down = parse(F128::FORMAT, "0.1", RoundingMode::TowardNegative).unwrap();
+        assert_eq!(TENTH.to_bits() - 1, down);
+
+        assert_eq!(crate::F32::from_bits(0xffc00001), "-nan:0x400001".parse().unwrap());
+        assert_eq!(F16::SNAN, "NaN:0X1".parse().unwrap());
+        for x in [F128::NEG_SNAN, F128::QNAN, F128::from_bits(0x7fff_ffff << 96)] {
+            assert_eq!(x, format!("{x:#}").parse().unwrap());
+        }
+        assert_eq!(F16::NEG_SNAN, "-sNaN".parse().unwrap());
+        assert_eq!(BF16::from_bits(0x7fc0), "QNaN".parse().unwrap());
+        assert_eq!(F128::NEG_INFINITY, "-INFINITY".parse().unwrap());
+        assert_eq!(F128::INFINITY, "+inf".parse().unwrap());
+        for s in ["nan:0x0", "nan:0x", "nan:0x800000", "nan:0x40000g", "nan:1", "snan:0x1", "-+inf"]
+        {
+            assert!(s.parse::<crate::F32>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn parse_small_stack_test() {
+        // The big integers for the formats of up to 32 bits take a few hundred bytes each.
+        let parse_all = || {
+            let long = format!("0.{}1", "3".repeat(200));
+            for s in ["0.1", "-65519", "1e-45", "3.4028235e38", "1e-400", long.as_str()] {
+                let native = s.parse::<f32>().unwrap();
+                assert_eq!(crate::F32::from_float(native), s.parse().unwrap(), "{s}");
+                let soft = parse(crate::F32::FORMAT, s, RoundingMode::TiesToEven).unwrap();
+                assert_eq!(native.to_bits() as u128, soft, "{s}");
+                s.parse::<F16>().unwrap();
+                s.parse::<BF16>().unwrap();
+            }
+        };
+        let thread = std::thread::Builder::new().stack_size(32 * 1024).spawn(parse_all).unwrap();
+        thread.join().unwrap();
+    }
+
+    #[test]
+    fn parse_const_test() {
+        const E: F128 = F128::parse_const("2.718281828459045235360287471352662497757");
+        const TINY: F128 = F128::parse_const("-1e-4940");
+        const HALF: BF16 = BF16::parse_const("0.5");
+        assert_eq!(F128::from_bits(0x40005bf0a8b1457695355fb8ac404e7a), E);
+        assert_eq!("-1e-4940".parse::<F128>().unwrap(), TINY);
+        assert_eq!(BF16::from_bits(0x3f00), HALF);
+        assert_eq!(F64::from_float(0.1), F64::parse_const("0.1"));
+        assert_eq!(F16::NEG_INFINITY, F16::parse_const("-inf"));
+    }
+
+    #[test]
+    #[should_panic = "invalid float literal"]
+    fn parse_const_invalid_test() {
+        F128::parse_const("1.2.3");
+    }
+
+    #[test]
+    fn format_into_test() {
+        let mut buf = [0; 64];
+        assert_eq!("0.1", F64::from_float(0.1).format_into(&mut buf).unwrap());
+        assert_eq!("-65500", F16::MIN.format_into(&mut buf).unwrap());
+        assert_eq!("-0", F128::NEG_ZERO.format_into(&mut buf).unwrap());
+        assert_eq!("-inf", BF16::NEG_INFINITY.format_into(&mut buf).unwrap());
+        assert_eq!("NaN", F128::NEG_QNAN.format_into(&mut buf).unwrap());
+        assert_eq!(Err(BufferTooSmall), F128::MAX.format_into(&mut buf));
+        assert_eq!(Err(BufferTooSmall), F16::NEG_ONE.format_into(&mut buf[..1]));
+        assert_eq!("-1", F16::NEG_ONE.format_into(&mut buf[..2]).unwrap());
+
+        let mut buf = [0; 5000];
+        for bits in [1, 0x3ffd5555555555555555555555555555, 0x7ffeffffffffffffffffffffffffffff] {
+            let x = F128::from_bits(bits);
+            assert_eq!(format!("{x}"), x.format_into(&mut buf).unwrap());
+        }
+    }
+
+    #[test]
+    fn format_into_small_stack_test() {
+        // Formats no wider than 32 bits need about 1 KiB of stack, or 3 KiB without optimization.
+        let format_all = || {
+            let mut buf = [0; 64];
+            for bits in 0..=u16::MAX {
+                F16::from_bits(bits).format_into(&mut buf).unwrap();
+                BF16::from_bits(bits).format_into(&mut buf).unwrap();
+            }
+            for bits in [1, 0x007fffff, 0x00800000, 0x3dcccccd, 0x7f7fffff, 0xff7fffff] {
+                let x = crate::F32::from_bits(bits);
+                assert_eq!(format!("{x}"), x.format_into(&mut buf).unwrap());
+            }
+        };
+        let thread = std::thread::Builder::new().stack_size(16 * 1024).spawn(format_all).unwrap();
+        thread.join().unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn exact_decimal_test() {
+        let tenth = "0.1000000000000000055511151231257827021181583404541015625";
+        assert_eq!(tenth, F64::from_float(0.1).to_exact_decimal());
+        assert_eq!("-65504", F16::MIN.to_exact_decimal());
+        assert_eq!("1", BF16::ONE.to_exact_decimal());
+        assert_eq!("-0", F128::NEG_ZERO.to_exact_decimal());
+        assert_eq!("-inf", F128::NEG_INFINITY.to_exact_decimal());
+        assert_eq!("0.0000000596046447753906250", format!("{:.25}", F16::from_bits(1)));
+        assert_eq!("0.000000059604644775390625", F16::from_bits(1).to_exact_decimal());
+        let tiny = F128::from_bits(1).to_exact_decimal();
+        assert_eq!(2 + 16494, tiny.len());
+        assert_eq!(format!("{:.16494}", F128::from_bits(1)), tiny);
+    }
+
+    #[test]
+    fn cmp_decimal_test() {
+        use Ordering::*;
+        let tenth = F64::from_float(0.1);
+        assert_eq!(Ok(Greater), tenth.cmp_decimal_str("0.1"));
+        assert_eq!(
+            Ok(Less),
+            tenth.cmp_decimal_str("0.1000000000000000055511151231257827021181583404541015626")
+        );
+        assert_eq!(
+            Ok(Equal),
+            tenth.cmp_decimal_str("1000000000000000055511151231257827021181583404541015625e-55")
+        );
+        assert_eq!(Ok(Less), F64::from_float(0.3).cmp_decimal_str("0.3"));
+        assert_eq!(Ok(Greater), crate::F32::from_float(0.1).cmp_decimal_str("+.1"));
+        assert_eq!(Ok(Equal), F16::ONE.cmp_decimal_str("1.000"));
+        let above_one = format!("1.{}1", "0".repeat(12000));
+        assert_eq!(Ok(Less), F16::ONE.cmp_decimal_str(&above_one));
+        assert_eq!(Ok(Greater), F16::NEG_ONE.cmp_decimal_str(&format!("-{above_one}")));
+        assert_eq!(Ok(Less), BF16::MAX.cmp_decimal_str("1e39"));
+        assert_eq!(Ok(Greater), F128::from_bits(1).cmp_decimal_str("1e-5000"));
+        assert_eq!(Ok(Less), F128::MAX.cmp_decimal_str("1e99999999999999999999"));
+        assert_eq!(Ok(Greater), F128::MAX.cmp_decimal_str("-1e99999999999999999999"));
+        assert_eq!(Ok(Less), F64::NEG_ZERO.cmp_decimal_str("0"));
+        assert_eq!(Ok(Equal), F64::NEG_ZERO.cmp_decimal_str("-0.0e5"));
+        assert_eq!(Ok(Greater), F64::ZERO.cmp_decimal_str("-1e-400"));
+        assert_eq!(Ok(Greater), F64::INFINITY.cmp_decimal_str("1e400"));
+        assert_eq!(Ok(Equal), F64::NEG_INFINITY.cmp_decimal_str("-inf"));
+        assert_eq!(Ok(Greater), F64::QNAN.cmp_decimal_str("inf"));
+        assert_eq!(Ok(Less), F64::NEG_QNAN.cmp_decimal_str("-1"));
+        assert_eq!(Ok(Less), F64::ONE.cmp_decimal_str("nan"));
+        assert!(F64::ONE.cmp_decimal_str("").is_err());
+        assert!(F64::ONE.cmp_decimal_str("1.0x").is_err());
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!("0.1", format!("{}", F16::from_bits(0x2e66)));
+        assert_eq!("0.099976", format!("{:.6}", F16::from_bits(0x2e66)));
+        assert_eq!("65500", format!("{}", F16::MAX));
+        assert_eq!("65504", format!("{:.0}", F16::MAX));
+        assert_eq!("-0.00000006", format!("{}", F16::from_bits(0x8001)));
+        assert_eq!("1", format!("{}", BF16::ONE));
+        assert_eq!(format!("339{}", "0".repeat(36)), format!("{}", BF16::MAX));
+        assert_eq!("0.1", format!("{}", BF16::from_bits(0x3dcd)));
+        assert_eq!("0.100", format!("{:.3}", BF16::from_bits(0x3dcd)));
+        assert_eq!("inf", format!("{}", F128::INFINITY));
+        assert_eq!("NaN", format!("{}", F128::NEG_QNAN));
+        assert_eq!("-0", format!("{}", F128::NEG_ZERO));
+        assert_eq!("-0.0", format!("{:#}", F128::NEG_ZERO));
+        assert_eq!("1.0", format!("{:#}", BF16::ONE));
+        assert_eq!("0.1", format!("{:#}", BF16::from_bits(0x3dcd)));
+        assert_eq!("65500.0", format!("{:#}", F16::MAX));
+        assert_eq!("1", format!("{:#.0}", F16::ONE));
+        assert_eq!("+0.100", format!("{:+.3}", BF16::from_bits(0x3dcd)));
+        assert_eq!("__-1.5___", format!("{:_^9}", F16::from_bits(0xbe00)));
+        assert_eq!("-00001.50", format!("{:09.2}", F128::from_bits(0xbfff8 << 108)));
+        assert_eq!("+inf", format!("{:+}", BF16::INFINITY));
+        assert_eq!("NaN  ", format!("{:<5}", F16::NEG_QNAN));
+        assert_eq!("-nan:0x400001", format!("{:#}", crate::F32::from_bits(0xffc00001)));
+        assert_eq!("nan:0x1", format!("{:#}", F16::SNAN));
+        assert_eq!("+nan:0x40", format!("{:+#}", BF16::from_bits(0x7fc0)));
+
+        const THIRD: F128 = F128::from_bits(0x3ffd5555555555555555555555555555);
+        const TENTH: F128 = F128::from_bits(0x3ffb999999999999999999999999999a);
+        assert_eq!("0.3333333333333333333333333333333333", format!("{THIRD}"));
+        assert_eq!("0.333333333333333333333333333333333317", format!("{THIRD:.36}"));
+        assert_eq!("0.1", format!("{TENTH}"));
+        assert_eq!("0.100000000000000000000000000000000005", format!("{TENTH:.36}"));
+        assert_eq!("0.33333", format!("{THIRD:.5}"));
+        assert_eq!(4933, format!("{}", F128::MAX).len());
+        assert_eq!(format!("0.{}6", "0".repeat(4965)), format!("{}", F128::from_bits(1)));
+        // 2^-16494 = 5^16494 / 10^16494, and 5^16494 has 11529 digits.
+        let tiny = format!("{:.16500}", F128::from_bits(1));
+        let zeros = 16494 - 11529;
+        assert!(tiny[2..2 + zeros].bytes().all(|b| b == b'0'));
+        assert_eq!("64751751", &tiny[2 + zeros..2 + zeros + 8]);
+        assert!(tiny.ends_with("515625000000"));
+    }
+}
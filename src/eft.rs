@@ -0,0 +1,94 @@
+//! Error-free transformations, the building blocks of compensated algorithms.
+
+use crate::{F32, F64};
+
+macro_rules! define_error_free {
+    ($ty:ident; $f_ty:ident) => {
+        impl $ty {
+            /// Computes `a + b` together with the exact rounding error of the addition.
+            ///
+            /// Returns `(s, e)` where `s` is the rounded sum and `a + b = s + e` exactly.  This is
+            /// Knuth's branch-free TwoSum algorithm; the identity holds for all finite inputs
+            /// whose sum does not overflow.
+            pub fn two_sum(a: Self, b: Self) -> (Self, Self) {
+                let (a, b) = (a.to_float(), b.to_float());
+                let s = a + b;
+                let bb = s - a;
+                let e = (a - (s - bb)) + (b - bb);
+                (Self::from_float(s), Self::from_float(e))
+            }
+
+            /// Computes `a × b` together with the exact rounding error of the multiplication.
+            ///
+            /// Returns `(p, e)` where `p` is the rounded product and `a × b = p + e` exactly.
+            /// The identity holds for all finite inputs whose product neither overflows nor
+            /// underflows.
+            ///
+            /// # Features
+            ///
+            /// With feature `std` or `libm`, the error term is computed with a fused multiply-add.
+            /// Otherwise, it is computed with Dekker's splitting algorithm, which additionally
+            /// requires that neither input is within a factor of `2^(MANTISSA_DIGITS / 2)` of
+            /// overflowing.
+            pub fn two_product(a: Self, b: Self) -> (Self, Self) {
+                let (a, b) = (a.to_float(), b.to_float());
+                let p = a * b;
+                let e = Self::product_error(a, b, p);
+                (Self::from_float(p), Self::from_float(e))
+            }
+
+            #[cfg(any(feature = "libm", feature = "std"))]
+            fn product_error(a: $f_ty, b: $f_ty, p: $f_ty) -> $f_ty {
+                crate::math::Math::mul_add(a, b, -p)
+            }
+
+            #[cfg(not(any(feature = "libm", feature = "std")))]
+            fn product_error(a: $f_ty, b: $f_ty, p: $f_ty) -> $f_ty {
+                // Splits x into a high half and a low half, each with at most half the digits.
+                fn split(x: $f_ty) -> ($f_ty, $f_ty) {
+                    const FACTOR: $f_ty = ((1u64 << $ty::MANTISSA_DIGITS.div_ceil(2)) + 1) as $f_ty;
+                    let c = FACTOR * x;
+                    let hi = c - (c - x);
+                    (hi, x - hi)
+                }
+                let (ah, al) = split(a);
+                let (bh, bl) = split(b);
+                (((ah * bh - p) + ah * bl) + al * bh) + al * bl
+            }
+        }
+    };
+}
+
+define_error_free!(F32; f32);
+define_error_free!(F64; f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_sum_test() {
+        let f = F64::from_float;
+        assert_eq!((f(1.0), f(1e-20)), F64::two_sum(f(1.0), f(1e-20)));
+        assert_eq!((f(1e-20), F64::ZERO), F64::two_sum(f(1e-20), F64::ZERO));
+        let big = 2.0f64.powi(53);
+        assert_eq!((f(2.0 * big), f(-1.0)), F64::two_sum(f(big), f(big - 1.0)));
+
+        let f = F32::from_float;
+        assert_eq!((f(1.0), f(-1e-10)), F32::two_sum(f(-1e-10), f(1.0)));
+    }
+
+    #[test]
+    fn two_product_test() {
+        let f = F64::from_float;
+        let x = f(1.0 + 2.0f64.powi(-30));
+        assert_eq!((f(1.0 + 2.0f64.powi(-29)), f(2.0f64.powi(-60))), F64::two_product(x, x));
+        assert_eq!((f(6.0), F64::ZERO), F64::two_product(f(2.0), f(3.0)));
+
+        let f = F32::from_float;
+        let x = f(1.0 + 2.0f32.powi(-12));
+        assert_eq!((f(1.0 + 2.0f32.powi(-11)), f(2.0f32.powi(-24))), F32::two_product(x, x));
+        let (p, e) = F32::two_product(f(0.1), f(0.3));
+        assert_eq!(0.1f32 as f64 * 0.3f32 as f64, p.to_float() as f64 + e.to_float() as f64);
+    }
+}
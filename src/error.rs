@@ -0,0 +1,166 @@
+//! Errors reported when parsing floats from text.
+
+use core::fmt;
+
+/// The reason a string could not be parsed as a float.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The string was empty.
+    Empty,
+
+    /// The string did not follow the expected syntax: a character was out of place, or the
+    /// string ended too soon.
+    InvalidSyntax,
+
+    /// A field of the representation, such as a NaN payload, had more bits than the format can
+    /// hold.
+    ///
+    /// This is not reported for decimal values of too great a magnitude, which round to infinity
+    /// as for the Rust floats.
+    FieldTooLarge,
+
+    /// A field of the representation had too few bits, or was zero where zero is not allowed, as
+    /// for a NaN payload.
+    ///
+    /// This is not reported for non-zero decimal values of too small a magnitude, which round to
+    /// zero as for the Rust floats.
+    FieldTooSmall,
+}
+
+/// An error returned when parsing a float from text.
+///
+/// Besides the [kind](ParseErrorKind) of failure, it records the byte offset in the input where
+/// the failure was detected and the character found there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    offset: usize,
+    token: Option<char>,
+}
+
+impl ParseError {
+    /// Creates an error of the given kind, detected at byte `offset` of `s`.
+    pub(crate) const fn new(kind: ParseErrorKind, s: &[u8], offset: usize) -> Self {
+        let token = char_at(s, offset);
+        Self { kind, offset, token }
+    }
+
+    pub(crate) const fn empty() -> Self {
+        Self { kind: ParseErrorKind::Empty, offset: 0, token: None }
+    }
+
+    pub(crate) const fn invalid(s: &[u8], offset: usize) -> Self {
+        Self::new(ParseErrorKind::InvalidSyntax, s, offset)
+    }
+
+    /// Returns the reason for the failure.
+    pub const fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte offset in the input where the failure was detected.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the character at [`Self::offset`], or `None` if the failure was detected at the
+    /// end of the input.
+    pub const fn token(&self) -> Option<char> {
+        self.token
+    }
+
+    /// Panics with a message describing the kind of failure.
+    pub(crate) const fn panic(self) -> ! {
+        match self.kind {
+            ParseErrorKind::Empty => panic!("cannot parse float from empty string"),
+            ParseErrorKind::InvalidSyntax => panic!("invalid float literal"),
+            ParseErrorKind::FieldTooLarge => {
+                panic!("float literal has a field too large for the format")
+            },
+            ParseErrorKind::FieldTooSmall => {
+                panic!("float literal has a field too small for the format")
+            },
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = match self.kind {
+            ParseErrorKind::Empty => return f.write_str("cannot parse float from empty string"),
+            ParseErrorKind::InvalidSyntax => "invalid float literal",
+            ParseErrorKind::FieldTooLarge => "field too large for the format",
+            ParseErrorKind::FieldTooSmall => "field too small for the format",
+        };
+        match self.token {
+            Some(c) => write!(f, "{what}: found {c:?} at byte {}", self.offset),
+            None => write!(f, "{what}: unexpected end at byte {}", self.offset),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// Decodes the character starting at byte `i` of the UTF-8 string `s`, if any.
+const fn char_at(s: &[u8], i: usize) -> Option<char> {
+    if i >= s.len() {
+        return None;
+    }
+    let b = s[i] as u32;
+    let (len, mut c) = if b < 0x80 {
+        (1, b)
+    } else if b < 0xe0 {
+        (2, b & 0x1f)
+    } else if b < 0xf0 {
+        (3, b & 0x0f)
+    } else {
+        (4, b & 0x07)
+    };
+    let mut j = 1;
+    while j < len && i + j < s.len() {
+        c = (c << 6) | (s[i + j] as u32 & 0x3f);
+        j += 1;
+    }
+    char::from_u32(c)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use super::*;
+    use crate::{BF16, F16, F32, F64, F128};
+
+    #[test]
+    fn parse_error_test() {
+        let err = "1.5x".parse::<F16>().unwrap_err();
+        assert_eq!("invalid float literal: found 'x' at byte 3", err.to_string());
+        let err = "1e".parse::<BF16>().unwrap_err();
+        assert_eq!("invalid float literal: unexpected end at byte 2", err.to_string());
+        let err = "".parse::<F128>().unwrap_err();
+        assert_eq!("cannot parse float from empty string", err.to_string());
+        assert_eq!(Ok(F128::INFINITY), "1e5000".parse());
+        assert_eq!(Ok(F16::ZERO), "1e-10".parse());
+
+        // F32 and F64 keep the error type of the standard library.
+        let err: core::num::ParseFloatError = "1.5x".parse::<F32>().unwrap_err();
+        assert_eq!("1.5x".parse::<f32>().unwrap_err(), err);
+        assert_eq!("nan:0x1".parse::<f64>().unwrap_err(), "nan:0x0".parse::<F64>().unwrap_err());
+        let err = "nan:0x400".parse::<F16>().unwrap_err();
+        assert_eq!(ParseErrorKind::FieldTooLarge, err.kind());
+        assert_eq!("field too large for the format: found '0' at byte 8", err.to_string());
+
+        let err = F16::from_bit_string("0".repeat(17).as_str()).unwrap_err();
+        assert_eq!((ParseErrorKind::FieldTooLarge, 16), (err.kind(), err.offset()));
+        let err = F16::from_bit_string("0101").unwrap_err();
+        assert_eq!(
+            (ParseErrorKind::FieldTooSmall, 4, None),
+            (err.kind(), err.offset(), err.token())
+        );
+        let err = F16::from_bit_string("0101_0101_0101_0102").unwrap_err();
+        assert_eq!((ParseErrorKind::InvalidSyntax, Some('2')), (err.kind(), err.token()));
+    }
+}
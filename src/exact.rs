@@ -0,0 +1,176 @@
+//! Operations between values of different formats.
+
+use core::cmp::Ordering;
+
+use crate::soft::{Format, Unpacked, compare, convert_with_flags, total_compare, unpack};
+use crate::{FloatBits, RoundingMode, StatusFlags};
+
+/// Compares two values of any formats in a single total order that spans all the formats.
+///
+/// Values are ordered by the real numbers that they represent, with no rounding, extending the
+/// IEEE 754 `totalOrder` predicate that [`F64::total_cmp`](crate::F64::total_cmp) follows within
+/// one format:
+///
+/// * negative NaNs come first and positive NaNs last; NaNs of the same sign are ordered by their
+///   payloads, aligned at the most significant bit, so that signaling NaNs are nearer to the
+///   infinities than quiet NaNs, as within one format
+/// * `-0.0` comes before `+0.0`
+/// * values that are otherwise equal, like `1.0` in two formats or two NaNs whose payloads align,
+///   form a cohort that is ordered by format, narrowest first: by total width, then by exponent
+///   width, so [`F16`](crate::F16) comes before [`BF16`](crate::BF16)
+///
+/// So the result is `Equal` only for the same bits in the same format, and within a single format
+/// it agrees with `total_cmp`.  This makes it suitable as the key order of a sorted index that
+/// holds values of several formats.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::{BF16, F32, F64, total_cmp_across};
+/// # use core::cmp::Ordering;
+///
+/// assert_eq!(Ordering::Less, total_cmp_across(F64::from_float(0.1), F32::from_float(0.1)));
+/// assert_eq!(Ordering::Less, total_cmp_across(F32::ONE, F64::ONE));
+/// assert_eq!(Ordering::Greater, total_cmp_across(BF16::QNAN, F64::INFINITY));
+/// ```
+pub fn total_cmp_across(a: impl FloatBits, b: impl FloatBits) -> Ordering {
+    let (a_shape, b_shape) = (shape(&a), shape(&b));
+    total_compare(unpack_any(a), unpack_any(b)).then(a_shape.cmp(&b_shape))
+}
+
+/// An adapter that compares floats with the semantics of IEEE 754 comparison operators.
+///
+/// The float types implement [`Eq`] and [`Ord`] by bits and by `totalOrder`, which suits sorting
+/// and hashing, but not arithmetic code that expects `NaN != NaN` and `-0.0 == +0.0`.  Wrapping a
+/// value in `IeeeCmp` gives it [`PartialEq`] and [`PartialOrd`] with the semantics of the native
+/// `f32` and `f64` operators, while the storage stays the bit type.  Like
+/// [`Reverse`](core::cmp::Reverse), the wrapper has a public field and no invariant.
+///
+/// The comparison is exact, so values of different formats can be compared, without either one
+/// being rounded.  There is no [`Hash`](core::hash::Hash) implementation, since none could agree
+/// with this equality.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::{F16, F64, IeeeCmp};
+///
+/// assert!(IeeeCmp(F64::NAN) != IeeeCmp(F64::NAN));
+/// assert!(IeeeCmp(F64::NEG_ZERO) == IeeeCmp(F64::ZERO));
+/// assert!(IeeeCmp(F16::ONE) == IeeeCmp(F64::ONE));
+/// assert!(!(IeeeCmp(F64::NAN) < IeeeCmp(F64::INFINITY)));
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+#[repr(transparent)]
+pub struct IeeeCmp<T>(pub T);
+
+impl<T: FloatBits, U: FloatBits> PartialEq<IeeeCmp<U>> for IeeeCmp<T> {
+    fn eq(&self, other: &IeeeCmp<U>) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<T: FloatBits, U: FloatBits> PartialOrd<IeeeCmp<U>> for IeeeCmp<T> {
+    fn partial_cmp(&self, other: &IeeeCmp<U>) -> Option<Ordering> {
+        compare(unpack_any(self.0), unpack_any(other.0))
+    }
+}
+
+/// Returns the total width and exponent width of the format of `x`.
+fn shape<T: FloatBits>(_: &T) -> (usize, usize) {
+    (T::BITS, T::EXP_BITS)
+}
+
+/// Unpacks a value of any format.
+pub(crate) fn unpack_any<T: FloatBits>(x: T) -> Unpacked {
+    unpack(Format::new(T::BITS, T::EXP_BITS), x.to_bits().into())
+}
+
+/// Converts a value of any format to any other, rounding according to `mode`, and returns the
+/// result with the flags raised.
+pub(crate) fn convert_any_with_flags<T: FloatBits, U: FloatBits>(
+    x: T,
+    mode: RoundingMode,
+) -> (U, StatusFlags) {
+    let (from, to) = (Format::new(T::BITS, T::EXP_BITS), Format::new(U::BITS, U::EXP_BITS));
+    let (bits, flags) = convert_with_flags(from, to, x.to_bits().into(), mode);
+    match U::Bits::try_from(bits) {
+        Ok(bits) => (U::from_bits(bits), flags),
+        Err(_) => unreachable!("the result has the width of the format"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cmp::Ordering::*;
+
+    use crate::{BF16, F16, F32, F64, F128};
+
+    #[test]
+    fn ieee_cmp_test() {
+        use super::IeeeCmp;
+
+        assert_ne!(IeeeCmp(F32::NAN), IeeeCmp(F32::NAN));
+        assert_eq!(IeeeCmp(F32::NEG_ZERO), IeeeCmp(F32::ZERO));
+        assert_eq!(IeeeCmp(BF16::ZERO), IeeeCmp(F128::NEG_ZERO));
+        assert_eq!(None, IeeeCmp(F64::QNAN).partial_cmp(&IeeeCmp(F64::QNAN)));
+        assert_eq!(Some(Less), IeeeCmp(F64::NEG_INFINITY).partial_cmp(&IeeeCmp(F16::MIN)));
+        // 0.1 rounds up in F32 but down in F64.
+        assert!(IeeeCmp(F32::from_float(0.1)) > IeeeCmp(F64::from_float(0.1)));
+        assert!(IeeeCmp(F16::ONE) >= IeeeCmp(F64::ONE));
+    }
+
+    #[test]
+    fn cmp_exact_test() {
+        // 0.1 rounds up in BF16 and F32 but down in F64.
+        let (b, s, d) = (BF16::from_bits(0x3dcd), F32::from_float(0.1), F64::from_float(0.1));
+        assert_eq!(Some(Greater), b.cmp_exact(d));
+        assert_eq!(Some(Greater), s.cmp_exact(d));
+        assert_eq!(Some(Less), d.cmp_exact(s));
+        assert_eq!(Some(Greater), b.cmp_exact(s));
+        assert_eq!(Some(Equal), F64::from_float(0.1f32 as f64).cmp_exact(s));
+
+        assert_eq!(Some(Equal), F16::NEG_ZERO.cmp_exact(F128::ZERO));
+        assert_eq!(Some(Less), F16::from_bits(0x8001).cmp_exact(F128::ZERO));
+        assert_eq!(Some(Greater), F16::from_bits(1).cmp_exact(F64::from_bits(1)));
+        assert_eq!(Some(Less), F16::MAX.cmp_exact(F128::MAX));
+        assert_eq!(Some(Equal), F16::INFINITY.cmp_exact(F128::INFINITY));
+        assert_eq!(Some(Less), F64::NEG_INFINITY.cmp_exact(BF16::MIN));
+        assert_eq!(Some(Greater), F32::NEG_ONE.cmp_exact(F64::from_float(-1.0000001)));
+        assert_eq!(None, F32::QNAN.cmp_exact(F32::QNAN));
+        assert_eq!(None, F32::ONE.cmp_exact(F128::NEG_QNAN));
+    }
+
+    #[test]
+    fn total_cmp_across_test() {
+        use super::total_cmp_across as cmp;
+
+        let d = F64::from_float(0.1);
+        assert_eq!(Less, cmp(d, F32::from_float(0.1)));
+        assert_eq!(Less, cmp(F32::from_float(0.1), BF16::from_bits(0x3dcd)));
+        assert_eq!(Less, cmp(F64::NEG_ZERO, F16::ZERO));
+        assert_eq!(Greater, cmp(F16::NEG_ZERO, F128::from_bits(1 << 127 | 1)));
+
+        // Cohorts of equal values are ordered by format.
+        assert_eq!(Less, cmp(F16::ONE, BF16::ONE));
+        assert_eq!(Less, cmp(BF16::ONE, F32::ONE));
+        assert_eq!(Greater, cmp(F128::NEG_ONE, F64::NEG_ONE));
+        assert_eq!(Equal, cmp(F64::NEG_ONE, F64::NEG_ONE));
+        assert_eq!(Equal, cmp(F16::QNAN, F16::QNAN));
+
+        // NaNs sit beyond the infinities and order by aligned payload.
+        assert_eq!(Greater, cmp(F16::SNAN, F128::INFINITY));
+        assert_eq!(Less, cmp(F128::NEG_SNAN, BF16::NEG_INFINITY));
+        assert_eq!(Less, cmp(F64::SNAN, F16::QNAN));
+        assert_eq!(Less, cmp(F64::NEG_QNAN, F64::NEG_SNAN));
+        assert_eq!(Less, cmp(F32::from_bits(0x7fc00000), F64::from_bits(0x7ff8000000000001)));
+
+        // Within one format, this is `total_cmp`.
+        let xs = [F32::NEG_QNAN, F32::MIN, F32::NEG_ZERO, F32::ZERO, F32::from_bits(1), F32::SNAN];
+        for a in xs {
+            for b in xs {
+                assert_eq!(a.total_cmp(b), cmp(a, b), "{a:?} {b:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,324 @@
+//! Iteration over every value of a format, or of a range, for exhaustive testing.
+
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use crate::{BF16, F8E5M2, F16, F32, F64, F128, FloatBits};
+
+/// An iterator over every bit pattern of an 8-bit or 16-bit format, in the order of `total_cmp`.
+///
+/// It starts at the negative NaN with the greatest payload, passes through `-0.0` and `+0.0`, and
+/// ends at the positive NaN with the greatest payload, yielding all 256 or 65536 values.  It is
+/// created by the `all_values` method of each of those types, such as [`F16::all_values`] and
+/// [`F8E4M3::all_values`].
+#[derive(Debug, Clone)]
+pub struct AllValues<T> {
+    // The remaining signed total order keys, and the value of each key.
+    keys: Range<i32>,
+    from_key: fn(i32) -> T,
+}
+
+impl<T> AllValues<T> {
+    pub(crate) const fn new(bits: usize, from_key: fn(i32) -> T) -> Self {
+        Self { keys: -(1 << (bits - 1))..1 << (bits - 1), from_key }
+    }
+}
+
+/// Returns the value whose total order key is `key`, undoing `sort_bits`.
+fn from_key<T: FloatBits>(key: i128) -> T {
+    let bits = key as u128 & (u128::MAX >> (128 - T::BITS));
+    let mask = if key < 0 { (1 << (T::BITS - 1)) - 1 } else { 0 };
+    match T::Bits::try_from(bits ^ mask) {
+        Ok(bits) => T::from_bits(bits),
+        Err(_) => unreachable!("the key has the width of the format"),
+    }
+}
+
+impl<T> Iterator for AllValues<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.keys.next().map(self.from_key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<T> {
+        self.keys.nth(n).map(self.from_key)
+    }
+}
+
+impl<T> DoubleEndedIterator for AllValues<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.keys.next_back().map(self.from_key)
+    }
+}
+
+impl<T> ExactSizeIterator for AllValues<T> {}
+
+impl<T> FusedIterator for AllValues<T> {}
+
+/// An iterator over every value from one value to another, in the order of `total_cmp`.
+///
+/// Each value is the [`next_up`](F64::next_up) of the one before, except that `-0.0` is followed
+/// by `+0.0`, so that every bit pattern in the range is yielded exactly once.  It is created by
+/// the `values_between` method of each type, such as [`F32::values_between`].
+///
+/// With [`step_by_ulps`](Self::step_by_ulps), it yields only every n-th value instead.
+#[derive(Debug, Clone)]
+pub struct RangeIter<T> {
+    // The signed total order keys of the next values at each end, unless `done`.  The distance
+    // between them is a multiple of `step`.
+    front: i128,
+    back: i128,
+    step: u128,
+    done: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<T> RangeIter<T> {
+    const fn new(front: i128, back: i128) -> Self {
+        Self { front, back, step: 1, done: front > back, _marker: PhantomData }
+    }
+
+    /// Returns an iterator over every `n`-th value of the remaining range, starting with the
+    /// first.
+    ///
+    /// The values are `n` ulps apart, counting both zeros.  Since every binade holds the same
+    /// number of values, a step that is large compared to a binade samples the whole range with
+    /// roughly the same number of values per binade, spaced roughly logarithmically, which suits
+    /// benchmarks and coverage tests over the whole float line.  From the back, the iterator
+    /// starts at the last value reached from the front, as for [`Iterator::step_by`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use float_bits::F64;
+    ///
+    /// let values = F64::values_between(F64::MIN_POSITIVE, F64::MAX).step_by_ulps(1 << 52);
+    /// assert_eq!(2046, values.clone().count());
+    /// assert!(values.map(|x| x.to_bits()).eq((1..2047).map(|e| e << 52)));
+    /// ```
+    pub const fn step_by_ulps(self, n: u128) -> Self {
+        assert!(n != 0, "step must be positive");
+        let back = match self.done {
+            true => self.back,
+            false => self.front.wrapping_add((self.back.abs_diff(self.front) / n * n) as i128),
+        };
+        Self { back, step: n, ..self }
+    }
+
+    /// Returns the number of values left, if it fits in a `u128`.
+    const fn remaining(&self) -> Option<u128> {
+        match self.done {
+            true => Some(0),
+            false => (self.back.abs_diff(self.front) / self.step).checked_add(1),
+        }
+    }
+}
+
+impl<T: FloatBits> Iterator for RangeIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+        let key = self.front;
+        self.done = key == self.back;
+        self.front = key.wrapping_add(self.step as i128);
+        Some(from_key(key))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining().map(usize::try_from) {
+            Some(Ok(n)) => (n, Some(n)),
+            _ => (usize::MAX, None),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<T> {
+        match self.remaining() {
+            Some(len) if len <= n as u128 => {
+                self.done = true;
+                None
+            },
+            _ => {
+                self.front = self.front.wrapping_add((n as u128 * self.step) as i128);
+                self.next()
+            },
+        }
+    }
+}
+
+impl<T: FloatBits> DoubleEndedIterator for RangeIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+        let key = self.back;
+        self.done = key == self.front;
+        self.back = key.wrapping_sub(self.step as i128);
+        Some(from_key(key))
+    }
+}
+
+impl<T: FloatBits> FusedIterator for RangeIter<T> {}
+
+macro_rules! define_values_between {
+    ($ty:ident) => {
+        impl $ty {
+            /// Returns an iterator over every value from `lo` to `hi` inclusive, in the order of
+            /// [`total_cmp`](Self::total_cmp), or an empty iterator if `hi` comes before `lo`.
+            ///
+            /// Both zeros are included in a range that spans zero, and NaNs are included if the
+            /// bounds are NaNs.  The iterator runs from either end, so the values just below
+            /// `hi` are as cheap to reach as those just above `lo`.
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            #[doc = concat!("# use float_bits::", stringify!($ty), ";")]
+            ///
+            #[doc = concat!("let hi = ", stringify!($ty), "::ONE.next_up().next_up();")]
+            #[doc = concat!("let mut values = ", stringify!($ty), "::values_between(", stringify!($ty), "::ONE, hi);")]
+            #[doc = concat!("assert_eq!(Some(", stringify!($ty), "::ONE), values.next());")]
+            /// assert_eq!(Some(hi), values.next_back());
+            /// assert_eq!(Some(hi.next_down()), values.next());
+            /// assert_eq!(None, values.next());
+            /// ```
+            pub const fn values_between(lo: Self, hi: Self) -> RangeIter<Self> {
+                RangeIter::new(lo.sort_bits() as i128, hi.sort_bits() as i128)
+            }
+        }
+    };
+}
+
+define_values_between!(F8E5M2);
+define_values_between!(BF16);
+define_values_between!(F16);
+define_values_between!(F32);
+define_values_between!(F64);
+define_values_between!(F128);
+
+macro_rules! define_all_values {
+    ($ty:ident, $count:literal) => {
+        impl $ty {
+            /// Returns an iterator over every value, NaNs and both zeros included, in the order
+            /// of [`total_cmp`](Self::total_cmp).
+            ///
+            #[doc = concat!("With only ", $count, " values, a function of one argument can be tested on all of them.")]
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            #[doc = concat!("# use float_bits::", stringify!($ty), ";")]
+            ///
+            #[doc = concat!("let values = ", stringify!($ty), "::all_values();")]
+            #[doc = concat!("assert_eq!(", $count, ", values.len());")]
+            /// assert!(values.filter(|x| x.is_finite()).all(|x| x.neg().neg() == x));
+            /// ```
+            pub const fn all_values() -> AllValues<Self> {
+                AllValues::new(Self::BITS, |key| from_key(key as i128))
+            }
+        }
+    };
+}
+
+define_all_values!(F8E5M2, 256);
+define_all_values!(BF16, 65536);
+define_all_values!(F16, 65536);
+
+#[cfg(test)]
+mod tests {
+    use core::cmp::Ordering;
+
+    use super::*;
+
+    #[test]
+    fn all_values_test() {
+        let mut values = F16::all_values();
+        assert_eq!(Some(F16::from_bits(0xffff)), values.next());
+        assert_eq!(Some(F16::from_bits(0x7fff)), values.next_back());
+        assert_eq!(65534, values.len());
+        assert_eq!(Some(F16::NEG_INFINITY), values.nth(1022));
+
+        let mut prev = None;
+        let mut count = 0;
+        for x in BF16::all_values() {
+            if let Some(prev) = prev {
+                assert_eq!(Ordering::Less, BF16::total_cmp(&prev, x));
+            }
+            prev = Some(x);
+            count += 1;
+        }
+        assert_eq!(65536, count);
+
+        let mut zeros = F16::all_values().skip(32767).map(|x| x.to_bits());
+        assert_eq!(Some(F16::NEG_ZERO.to_bits()), zeros.next());
+        assert_eq!(Some(F16::ZERO.to_bits()), zeros.next());
+    }
+
+    #[test]
+    fn values_between_test() {
+        let lo = F32::from_bits(0x8000_0002);
+        let mut values = F32::values_between(lo, F32::from_bits(1));
+        assert_eq!((5, Some(5)), values.size_hint());
+        let bits = [0x8000_0002, 0x8000_0001, 0x8000_0000, 0, 1];
+        assert!(values.clone().map(|x| x.to_bits()).eq(bits));
+        assert!(values.clone().rev().map(|x| x.to_bits()).eq(bits.into_iter().rev()));
+        assert_eq!(Some(F32::ZERO), values.nth(3));
+        assert_eq!(None, values.nth(1));
+        assert_eq!(None, values.next_back());
+
+        assert_eq!(0, F64::values_between(F64::ONE, F64::ZERO).count());
+        assert_eq!(1, F64::values_between(F64::INFINITY, F64::INFINITY).count());
+        assert_eq!(
+            65536,
+            BF16::values_between(BF16::from_bits(0xffff), BF16::from_bits(0x7fff)).count()
+        );
+        assert_eq!((usize::MAX, None), F128::values_between(F128::MIN, F128::MAX).size_hint());
+        let (lo, hi) = (F128::from_bits(u128::MAX), F128::from_bits(i128::MAX as u128));
+        let mut all = F128::values_between(lo, hi);
+        assert_eq!(Some(u128::MAX), all.next().map(|x| x.to_bits()));
+        assert_eq!(Some(i128::MAX as u128), all.next_back().map(|x| x.to_bits()));
+    }
+
+    #[test]
+    fn step_by_ulps_test() {
+        let values = F32::values_between(F32::from_bits(0x8000_0003), F32::from_bits(4));
+        let bits = [0x8000_0003, 0x8000_0000, 2];
+        assert!(values.clone().step_by_ulps(3).map(|x| x.to_bits()).eq(bits));
+        assert!(
+            values.clone().step_by_ulps(3).rev().map(|x| x.to_bits()).eq(bits.into_iter().rev())
+        );
+        assert_eq!(1, values.clone().step_by_ulps(100).count());
+        assert_eq!(Some(F32::ZERO), values.clone().step_by_ulps(2).nth(2));
+
+        let mut values = values.step_by_ulps(2);
+        values.next();
+        assert_eq!(Some(F32::from_bits(4)), values.step_by_ulps(3).next_back());
+
+        let all =
+            F128::values_between(F128::from_bits(u128::MAX), F128::from_bits(i128::MAX as u128));
+        let mut halves = all.step_by_ulps(1 << 127);
+        assert_eq!((2, Some(2)), halves.size_hint());
+        assert_eq!(Some(u128::MAX), halves.next().map(|x| x.to_bits()));
+        assert_eq!(Some(0), halves.next().map(|x| x.to_bits()));
+        assert_eq!(None, halves.next());
+        assert_eq!(1, F16::values_between(F16::ZERO, F16::ONE).step_by_ulps(u128::MAX).count());
+    }
+
+    #[test]
+    #[should_panic = "step must be positive"]
+    fn step_by_ulps_zero_test() {
+        F16::values_between(F16::ZERO, F16::ONE).step_by_ulps(0);
+    }
+}
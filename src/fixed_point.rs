@@ -0,0 +1,143 @@
+//! Conversions between the float types and the fixed-point numbers of the `fixed` crate.
+//!
+//! [`FromFixed`] rounds exactly once, to nearest with ties to even, for every format.  A result too
+//! large for the format is infinite; the checked, saturating, and overflowing variants treat that
+//! as overflow.  [`ToFixed`] widens the float exactly to a type that `fixed` supports (`f32` for
+//! [`BF16`] and [`F16`], `fixed::F128` for [`F128`]) and converts from there, so it behaves as
+//! `fixed` does for the native floats, panics included.
+//!
+//! The float types have inherent `from_fixed` and `to_fixed` methods for raw Q-format values,
+//! which shadow the trait methods; call these through `fixed`'s own `to_num` and `from_num`, or
+//! through the trait by name.
+
+use fixed::traits::{Fixed, FromFixed, ToFixed};
+
+use crate::soft::{convert, from_int};
+use crate::{BF16, F16, F32, F64, F128, RoundingMode};
+
+/// Returns the sign and magnitude of the raw bits of `src`.
+fn fixed_parts<F: Fixed>(src: F) -> (bool, u128) {
+    let bits = src.to_bits();
+    if F::IS_SIGNED {
+        let raw: i128 = match bits.try_into() {
+            Ok(raw) => raw,
+            Err(_) => unreachable!(),
+        };
+        (raw < 0, raw.unsigned_abs())
+    } else {
+        match bits.try_into() {
+            Ok(raw) => (false, raw),
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+macro_rules! conversions {
+    ($( $ty:ident => |$x:ident| $widen:expr ),*) => {
+        $(
+            impl FromFixed for $ty {
+                fn from_fixed<F: Fixed>(src: F) -> Self {
+                    let (sign, mag) = fixed_parts(src);
+                    let exp = -(F::FRAC_NBITS as i64);
+                    let bits = from_int($ty::FORMAT, sign, mag, exp, RoundingMode::TiesToEven);
+                    $ty::from_bits(bits as _)
+                }
+
+                fn checked_from_fixed<F: Fixed>(src: F) -> Option<Self> {
+                    match Self::overflowing_from_fixed(src) {
+                        (val, false) => Some(val),
+                        (_, true) => None,
+                    }
+                }
+
+                fn saturating_from_fixed<F: Fixed>(src: F) -> Self {
+                    match Self::overflowing_from_fixed(src) {
+                        (val, false) => val,
+                        (val, true) => $ty::MAX.copysign(val),
+                    }
+                }
+
+                fn wrapping_from_fixed<F: Fixed>(src: F) -> Self {
+                    <Self as FromFixed>::from_fixed(src)
+                }
+
+                fn overflowing_from_fixed<F: Fixed>(src: F) -> (Self, bool) {
+                    let val = <Self as FromFixed>::from_fixed(src);
+                    (val, val.is_infinite())
+                }
+            }
+
+            impl ToFixed for $ty {
+                fn to_fixed<F: Fixed>(self) -> F {
+                    let $x = self;
+                    $widen.to_fixed()
+                }
+
+                fn checked_to_fixed<F: Fixed>(self) -> Option<F> {
+                    let $x = self;
+                    $widen.checked_to_fixed()
+                }
+
+                fn saturating_to_fixed<F: Fixed>(self) -> F {
+                    let $x = self;
+                    $widen.saturating_to_fixed()
+                }
+
+                fn wrapping_to_fixed<F: Fixed>(self) -> F {
+                    let $x = self;
+                    $widen.wrapping_to_fixed()
+                }
+
+                fn overflowing_to_fixed<F: Fixed>(self) -> (F, bool) {
+                    let $x = self;
+                    $widen.overflowing_to_fixed()
+                }
+            }
+        )*
+    };
+}
+
+conversions!(
+    BF16 => |x| f32::from_bits(convert(BF16::FORMAT, F32::FORMAT, x.to_bits() as u128) as u32),
+    F16 => |x| f32::from_bits(convert(F16::FORMAT, F32::FORMAT, x.to_bits() as u128) as u32),
+    F32 => |x| x.to_float(),
+    F64 => |x| x.to_float(),
+    F128 => |x| fixed::F128::from_bits(x.to_bits())
+);
+
+#[cfg(test)]
+mod tests {
+    use fixed::traits::{FromFixed, ToFixed};
+    use fixed::types::{I1F15, I1F31, I16F16, I32F32, U0F128};
+
+    use crate::{BF16, F16, F32, F64, F128};
+
+    #[test]
+    fn from_fixed_test() {
+        assert_eq!(F16::from_bits(0xb800), I1F15::from_num(-0.5).to_num());
+        assert_eq!(BF16::NEG_ONE, I1F31::MIN.to_num());
+        assert_eq!(F32::from_ratio(6554, -16), I16F16::from_num(0.1).to_num());
+        assert_eq!(F64::ONE, U0F128::MAX.to_num());
+        assert_eq!(F128::ONE, U0F128::MAX.to_num());
+        assert_eq!(F128::from_ratio(1, -128), U0F128::DELTA.to_num());
+
+        // 2049 is halfway between two F16 values, and ties go to even.
+        assert_eq!(F16::from_bits(0x6800), I16F16::from_num(2049).to_num());
+        assert_eq!(F16::from_bits(0x6801), I16F16::from_num(2049.5).to_num());
+        assert_eq!(F16::INFINITY, I32F32::MAX.to_num());
+        assert_eq!(None, I32F32::MAX.checked_to_num::<F16>());
+        assert_eq!(F16::MIN, I32F32::MIN.saturating_to_num());
+        assert_eq!((F16::INFINITY, true), F16::overflowing_from_fixed(I32F32::MAX));
+    }
+
+    #[test]
+    fn to_fixed_test() {
+        assert_eq!(I1F15::from_num(-0.5), I1F15::from_num(F16::from_bits(0xb800)));
+        assert_eq!(Some(I16F16::from_num(3.25)), I16F16::checked_from_num(BF16::from_bits(0x4050)));
+        assert_eq!(None, I16F16::checked_from_num(F16::INFINITY));
+        assert_eq!(I1F15::MAX, I1F15::saturating_from_num(F32::ONE));
+        assert_eq!(I16F16::from_num(0.1), I16F16::from_num(F64::from_float(0.1)));
+        let x = F128::from_ratio(3, -128);
+        assert_eq!((U0F128::from_bits(3), false), ToFixed::overflowing_to_fixed(x));
+    }
+}
@@ -0,0 +1,339 @@
+//! The OCP FP8 E4M3 format, which trades the infinities of IEEE 754 for a wider range.
+
+use core::cmp::Ordering;
+use core::num::FpCategory;
+
+use crate::{AllValues, F32};
+
+define_head! {
+    /// A newtype containing the raw bits of an OCP FP8 E4M3 floating point number.
+    ///
+    /// The format has 4 exponent bits and 3 fraction bits, but unlike the IEEE 754 formats, it has
+    /// no infinities and a single NaN of each sign: the exponent field of all ones holds finite
+    /// numbers up to `±448`, except for `0x7f` and `0xff`, which are NaN.  This is the format
+    /// called `float8_e4m3fn` by PyTorch and `ml_dtypes`.
+    ///
+    /// Since the format does not follow the IEEE 754 rules, this type has only the operations
+    /// below, and does not implement [`FloatBits`](crate::FloatBits).
+    ///
+    /// Values of this type are hashable and have a well-defined total order: the one given by
+    /// [`Self::total_cmp`].  As a consequence, `+0.0` is not equal to `-0.0`, and NaN compares
+    /// equal to NaN if both NaN values have exactly the same bit pattern.
+    pub struct F8E4M3;
+    u8
+}
+
+impl F8E4M3 {
+    /// Number of total bits in the representation.
+    pub const BITS: usize = 8;
+
+    /// Number of bits in the exponent representation.
+    pub const EXP_BITS: usize = 4;
+
+    /// Number of bits in the mantissa representation.
+    pub const MANTISSA_BITS: usize = 3;
+
+    /// Number of significant digits in base 2.
+    pub const MANTISSA_DIGITS: usize = 4;
+
+    /// Positive zero (`+0.0`).
+    pub const ZERO: Self = Self::from_bits(0x00);
+
+    /// Negative zero (`−0.0`).
+    pub const NEG_ZERO: Self = Self::from_bits(0x80);
+
+    /// Positive one (`+1.0`).
+    pub const ONE: Self = Self::from_bits(0x38);
+
+    /// Negative one (`−1.0`).
+    pub const NEG_ONE: Self = Self::from_bits(0xb8);
+
+    /// The difference between `1.0` and the next larger representable number, `2^−3`.
+    pub const EPSILON: Self = Self::from_bits(0x20);
+
+    /// The greatest finite value, `448`.
+    pub const MAX: Self = Self::from_bits(0x7e);
+
+    /// The least finite value, `−448`.
+    pub const MIN: Self = Self::from_bits(0xfe);
+
+    /// The positive normal value with the least possible absolute magnitude, `2^−6`.
+    pub const MIN_POSITIVE: Self = Self::from_bits(0x08);
+
+    /// The positive subnormal value with the least possible absolute magnitude, `2^−9`.
+    pub const MIN_POSITIVE_SUBNORMAL: Self = Self::from_bits(0x01);
+
+    /// Not a Number (NaN) with sign bit 0, the only positive NaN of the format.
+    pub const NAN: Self = Self::from_bits(0x7f);
+
+    /// Not a Number (NaN) with sign bit 1, the only negative NaN of the format.
+    pub const NEG_NAN: Self = Self::from_bits(0xff);
+
+    /// Returns `true` if self has a positive sign, including `+0.0` and [`Self::NAN`].
+    pub const fn is_sign_positive(&self) -> bool {
+        self.bits & 0x80 == 0
+    }
+
+    /// Returns `true` if self has a negative sign, including `-0.0` and [`Self::NEG_NAN`].
+    pub const fn is_sign_negative(&self) -> bool {
+        !self.is_sign_positive()
+    }
+
+    /// Returns the floating point category of the number.
+    ///
+    /// The result is never [`FpCategory::Infinite`], since the format has no infinities.
+    pub const fn classify(&self) -> FpCategory {
+        match (self.bits & 0x78, self.bits & 0x07) {
+            (0, 0) => FpCategory::Zero,
+            (0, _) => FpCategory::Subnormal,
+            (0x78, 0x07) => FpCategory::Nan,
+            _ => FpCategory::Normal,
+        }
+    }
+
+    /// Returns `true` if this value is NaN.
+    pub const fn is_nan(&self) -> bool {
+        self.bits & 0x7f == 0x7f
+    }
+
+    /// Returns `true` if this value is not NaN.  Every other value is finite.
+    pub const fn is_finite(&self) -> bool {
+        !self.is_nan()
+    }
+
+    /// Computes the absolute value of `self`.
+    ///
+    /// The result is always exact.
+    pub const fn abs(&self) -> Self {
+        Self::from_bits(self.bits & 0x7f)
+    }
+
+    /// Computes the negation of `self`.
+    ///
+    /// The result is always exact.
+    pub const fn neg(&self) -> Self {
+        Self::from_bits(self.bits ^ 0x80)
+    }
+
+    const fn sort_bits(&self) -> i8 {
+        let mask = if self.is_sign_negative() { 0x7f } else { 0 };
+        (self.bits ^ mask) as i8
+    }
+
+    /// Returns the ordering between `self` and `rhs`.
+    ///
+    /// The values are ordered from the negative NaN through the negative numbers, `-0.0`, `+0.0`,
+    /// and the positive numbers to the positive NaN, as the IEEE 754 `totalOrder` predicate
+    /// orders the values of the other formats.
+    pub const fn total_cmp(&self, rhs: Self) -> Ordering {
+        let lhs = self.sort_bits();
+        let rhs = rhs.sort_bits();
+        if lhs == rhs {
+            Ordering::Equal
+        } else if lhs < rhs {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        }
+    }
+
+    /// Returns an iterator over all 256 values, NaNs and both zeros included, in the order of
+    /// [`total_cmp`](Self::total_cmp).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use float_bits::F8E4M3;
+    ///
+    /// let mut values = F8E4M3::all_values();
+    /// assert_eq!(256, values.len());
+    /// assert_eq!(Some(F8E4M3::NEG_NAN), values.next());
+    /// assert_eq!(Some(F8E4M3::MIN), values.next());
+    /// assert_eq!(Some(F8E4M3::NAN), values.next_back());
+    /// ```
+    pub const fn all_values() -> AllValues<Self> {
+        AllValues::new(Self::BITS, |key| {
+            let mask = if key < 0 { 0x7f } else { 0 };
+            Self::from_bits(key as u8 ^ mask)
+        })
+    }
+
+    /// Converts an `f32` to E4M3, rounding to nearest with ties to even.
+    ///
+    /// Magnitudes above `464`, the midpoint between [`Self::MAX`] and the next power of two, have
+    /// no finite value to round to, so they and the infinities become NaN with their sign, as in
+    /// PyTorch.  Use [`Self::from_f32_saturating`] to clamp them to the greatest finite value
+    /// instead.
+    pub const fn from_f32_round(float: f32) -> Self {
+        Self::from_bits(Self::round_f32_bits(float.to_bits(), false))
+    }
+
+    /// Converts an `f32` to E4M3, rounding to nearest with ties to even and saturating at
+    /// [`Self::MIN`] and [`Self::MAX`].
+    ///
+    /// This is the saturating conversion of the OCP specification, which scaled quantization
+    /// relies on: only NaN becomes NaN.
+    pub const fn from_f32_saturating(float: f32) -> Self {
+        Self::from_bits(Self::round_f32_bits(float.to_bits(), true))
+    }
+
+    // Written without branches on the value, so that loops over it vectorize.
+    pub(crate) const fn round_f32_bits(bits: u32, saturate: bool) -> u8 {
+        let sign = (bits >> 24) & 0x80;
+        let abs = bits & 0x7fff_ffff;
+
+        // Rebias the exponent from 127 to 7, then round away the low 20 bits.
+        let normal = abs.wrapping_sub(0x3c00_0000);
+        let normal = normal.wrapping_add(0x7_ffff + ((normal >> 20) & 1)) >> 20;
+
+        // Subnormal results are multiples of 2^-9, so shift by the distance to that.
+        let mant = (abs & 0x007f_ffff) | 0x0080_0000;
+        let exp = if abs >> 23 < 120 { abs >> 23 } else { 120 };
+        let shift = if 141 - exp < 31 { 141 - exp } else { 31 };
+        let subnormal = (mant + (1 << (shift - 1)) - 1 + ((mant >> shift) & 1)) >> shift;
+
+        let overflow = if saturate { 0x7e } else { 0x7f };
+        let out = if abs < 0x3c80_0000 { subnormal } else { normal };
+        let out = if abs > 0x43e8_0000 { overflow } else { out };
+        let out = if abs > 0x7f80_0000 { 0x7f } else { out };
+        (out | sign) as u8
+    }
+
+    /// Converts every element of `src` with [`Self::from_f32_round`], writing the results to
+    /// `dst`.
+    ///
+    /// See [`BF16::convert_slice`](crate::BF16::convert_slice) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    pub fn convert_slice(src: &[F32], dst: &mut [F8E4M3]) {
+        assert_eq!(src.len(), dst.len(), "slices must have the same length");
+        for (d, s) in dst.iter_mut().zip(src) {
+            *d = Self::from_bits(Self::round_f32_bits(s.bits, false));
+        }
+    }
+
+    /// Converts `self` to an `f32`.  The result is always exact, and NaN becomes a quiet NaN
+    /// with the same sign.
+    pub const fn to_f32(&self) -> f32 {
+        let sign = ((self.bits & 0x80) as u32) << 24;
+        let exp = ((self.bits >> 3) & 0x0f) as u32;
+        let mant = (self.bits & 0x07) as u32;
+        let abs = if self.is_nan() {
+            0x7fc0_0000
+        } else if exp != 0 {
+            ((exp + 120) << 23) | (mant << 20)
+        } else if mant != 0 {
+            // Normalize, moving the leading 1 into the implicit bit.
+            let top = 31 - mant.leading_zeros();
+            ((top + 118) << 23) | ((mant ^ (1 << top)) << (23 - top))
+        } else {
+            0
+        };
+        f32::from_bits(sign | abs)
+    }
+}
+
+impl core::fmt::Debug for F8E4M3 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("F8E4M3").field("bits", &self.bits).finish()
+    }
+}
+
+/// Prints the exact value, which every E4M3 value has in few enough digits.
+impl core::fmt::Display for F8E4M3 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.to_f32(), f)
+    }
+}
+
+define_bits_fmt!(F8E4M3; Binary, Octal, LowerHex, UpperHex);
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::format;
+
+    use super::*;
+
+    #[test]
+    fn to_f32_test() {
+        assert_eq!(1.0, F8E4M3::ONE.to_f32());
+        assert_eq!(-1.0, F8E4M3::NEG_ONE.to_f32());
+        assert_eq!(448.0, F8E4M3::MAX.to_f32());
+        assert_eq!(-448.0, F8E4M3::MIN.to_f32());
+        assert_eq!(0.015625, F8E4M3::MIN_POSITIVE.to_f32());
+        assert_eq!(0.001953125, F8E4M3::MIN_POSITIVE_SUBNORMAL.to_f32());
+        assert_eq!(0.013671875, F8E4M3::from_bits(0x07).to_f32());
+        assert_eq!(0.125, F8E4M3::EPSILON.to_f32());
+        assert_eq!(0x8000_0000, F8E4M3::NEG_ZERO.to_f32().to_bits());
+        assert!(F8E4M3::NAN.to_f32().is_nan());
+        assert!(F8E4M3::NEG_NAN.to_f32().is_sign_negative());
+
+        assert_eq!(FpCategory::Normal, F8E4M3::MAX.classify());
+        assert_eq!(FpCategory::Nan, F8E4M3::NEG_NAN.classify());
+        assert_eq!(FpCategory::Subnormal, F8E4M3::from_bits(0x87).classify());
+        assert_eq!(FpCategory::Zero, F8E4M3::NEG_ZERO.classify());
+        assert_eq!(
+            "-448 0.001953125 NaN",
+            format!("{} {} {}", F8E4M3::MIN, F8E4M3::from_bits(1), F8E4M3::NAN)
+        );
+    }
+
+    #[test]
+    fn from_f32_test() {
+        // Every value converts back to itself.
+        for x in (0..=255).map(F8E4M3::from_bits) {
+            let y = F8E4M3::from_f32_round(x.to_f32());
+            assert_eq!(x.to_bits(), y.to_bits(), "{:#x}", x.bits);
+        }
+
+        // Every midpoint rounds to the neighbor with the even mantissa, and anything above or below
+        // it to the nearer neighbor.
+        let positive = (0..=0x7e).map(F8E4M3::from_bits);
+        for (lo, hi) in positive.clone().zip(positive.skip(1)) {
+            let mid = (lo.to_f32() + hi.to_f32()) / 2.0;
+            let even = if lo.bits & 1 == 0 { lo } else { hi };
+            assert_eq!(even, F8E4M3::from_f32_round(mid), "{mid}");
+            assert_eq!(lo, F8E4M3::from_f32_round(mid.next_down()), "{mid}");
+            assert_eq!(hi, F8E4M3::from_f32_round(mid.next_up()), "{mid}");
+            assert_eq!(hi.neg(), F8E4M3::from_f32_round(-mid.next_up()), "{mid}");
+        }
+
+        assert_eq!(F8E4M3::MAX, F8E4M3::from_f32_round(464.0));
+        assert_eq!(F8E4M3::NAN, F8E4M3::from_f32_round(464.0f32.next_up()));
+        assert_eq!(F8E4M3::NEG_NAN, F8E4M3::from_f32_round(f32::NEG_INFINITY));
+        assert_eq!(F8E4M3::NAN, F8E4M3::from_f32_round(f32::NAN));
+        assert_eq!(F8E4M3::MAX, F8E4M3::from_f32_saturating(1e10));
+        assert_eq!(F8E4M3::MIN, F8E4M3::from_f32_saturating(f32::NEG_INFINITY));
+        assert!(F8E4M3::from_f32_saturating(f32::NAN).is_nan());
+        assert_eq!(F8E4M3::ZERO, F8E4M3::from_f32_round(0.0009765625));
+        assert_eq!(F8E4M3::MIN_POSITIVE_SUBNORMAL, F8E4M3::from_f32_round(0.0009765626));
+        assert_eq!(F8E4M3::NEG_ZERO, F8E4M3::from_f32_round(-f32::from_bits(1)));
+        assert_eq!(F8E4M3::MIN_POSITIVE, F8E4M3::from_f32_round(0.0151));
+
+        let src = [0.1, -500.0, 3.0, f32::INFINITY].map(F32::from_float);
+        let mut dst = [F8E4M3::ZERO; 4];
+        F8E4M3::convert_slice(&src, &mut dst);
+        assert_eq!([0x1d, 0xff, 0x44, 0x7f], dst.map(|x| x.to_bits()));
+    }
+
+    #[test]
+    fn all_values_test() {
+        let mut prev = None;
+        for x in F8E4M3::all_values() {
+            if let Some(prev) = prev {
+                assert_eq!(Ordering::Less, F8E4M3::total_cmp(&prev, x));
+                if prev.is_finite() && x.is_finite() {
+                    assert!(prev.to_f32() <= x.to_f32());
+                }
+            }
+            prev = Some(x);
+        }
+        let mut zeros = F8E4M3::all_values().skip(127);
+        assert_eq!(Some(F8E4M3::NEG_ZERO), zeros.next());
+        assert_eq!(Some(F8E4M3::ZERO), zeros.next());
+    }
+}
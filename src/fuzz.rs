@@ -0,0 +1,84 @@
+//! Arbitrary floats for fuzzing, biased toward the values where bugs hide.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::RoundingMode;
+use crate::soft::{self, Format, Unpacked};
+
+/// Generates the bits of an arbitrary float of `fmt` from `u`.
+///
+/// Uniform bit patterns are nearly always ordinary normal numbers, so only a quarter of the values
+/// are uniform.  The rest are drawn evenly from zeros, subnormals, infinities, NaNs with arbitrary
+/// payloads and either value of the quiet bit, normals with the least or greatest exponent, and
+/// small integers.  The sign is arbitrary in every case.
+pub(crate) fn arbitrary_bits(fmt: Format, u: &mut Unstructured<'_>) -> Result<u128> {
+    let sign_bit = if bool::arbitrary(u)? { fmt.sign_mask() } else { 0 };
+    let mant_mask = fmt.mant_mask();
+    let magnitude = match u.int_in_range(0..=7u8)? {
+        0 => 0,
+        1 => u.int_in_range(1..=mant_mask)?,
+        2 => fmt.exp_mask(),
+        3 => fmt.exp_mask() | u.int_in_range(1..=mant_mask)?,
+        4 => {
+            let max_field = fmt.exp_mask() >> fmt.mant_bits();
+            let field = if bool::arbitrary(u)? { 1 } else { max_field - 1 };
+            (field << fmt.mant_bits()) | u.int_in_range(0..=mant_mask)?
+        },
+        5 => {
+            let n = u.int_in_range(1..=1024u128)?;
+            let shift = n.leading_zeros();
+            let value = Unpacked::finite(false, 127 - shift as i32, n << shift);
+            soft::round(fmt, value, false, RoundingMode::TiesToEven)
+        },
+        _ => u.int_in_range(0..=fmt.exp_mask() | mant_mask)?,
+    };
+    Ok(sign_bit | magnitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::FpCategory;
+
+    use arbitrary::{Arbitrary, Unstructured};
+
+    use crate::{F16, F32, F128};
+
+    #[test]
+    fn arbitrary_test() {
+        // A fixed pseudo-random input, so that the test is deterministic.
+        let mut state = 0x2545f4914f6cdd1du64;
+        let data: [u8; 8192] = core::array::from_fn(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 24) as u8
+        });
+
+        let mut u = Unstructured::new(&data);
+        let mut counts = [0; 5];
+        let (mut payloads, mut negative) = ([0u32; 4], 0);
+        for _ in 0..500 {
+            let x = F32::arbitrary(&mut u).unwrap();
+            let class = x.classify();
+            counts[class as usize] += 1;
+            if class == FpCategory::Nan {
+                payloads[(x.bits & 3) as usize] += 1;
+            }
+            negative += x.is_sign_negative() as u32;
+        }
+        assert!(counts.iter().all(|&n| n >= 25), "{counts:?}");
+        assert!(payloads.iter().all(|&n| n > 0), "{payloads:?}");
+        assert!((150..350).contains(&negative), "{negative}");
+        assert!(counts[FpCategory::Normal as usize] < 350, "{counts:?}");
+
+        let mut u = Unstructured::new(&data[4096..]);
+        let f16s: [F16; 100] = core::array::from_fn(|_| F16::arbitrary(&mut u).unwrap());
+        let small_int = |x: &F16| matches!(x.to_ratio(), Some((m, e)) if m != 0 && e >= 0 && m.abs() << e <= 1024);
+        assert!(f16s.iter().any(small_int));
+        assert!(f16s.iter().any(|x| x.bits & 0x7c00 == 0x7800));
+
+        // Exhausted input still gives values.
+        let mut u = Unstructured::new(&[]);
+        assert_eq!(F128::ZERO, F128::arbitrary(&mut u).unwrap());
+    }
+}
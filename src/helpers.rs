@@ -29,3 +29,10 @@ pub(crate) const fn is_nan(category: FpCategory) -> bool {
 pub(crate) const fn is_finite(category: FpCategory) -> bool {
     matches!(category, C_ZERO | C_SUB | C_NORM)
 }
+
+/// Returns `n × log10(2)`, rounded toward zero.
+///
+/// The fraction is precise enough to be exact for every exponent of every format.
+pub(crate) const fn log10_pow2(n: i32) -> i32 {
+    (n as i64 * 301_029_995_664 / 1_000_000_000_000) as i32
+}
@@ -0,0 +1,238 @@
+//! Hexadecimal float formatting and parsing of the raw bit representation.
+
+use core::fmt;
+
+use crate::RoundingMode;
+use crate::decimal::{pad, parse_special, split_sign};
+use crate::error::ParseError;
+use crate::soft::{self, Format, Kind, Unpacked};
+
+/// An adapter that formats a float in hexadecimal, like the C99 `%a` conversion.
+///
+/// Returned by the `fmt_hex` method of each float type.  The output is exact, so it parses back
+/// to the same value in any language that reads C99 hexadecimal floats:
+///
+/// * normal numbers print as `0x1.<hex digits>p<exponent>`, with trailing zero digits omitted
+/// * subnormal numbers print as `0x0.<hex digits>p<minimum exponent>`
+/// * zero prints as `0x0p+0`
+/// * infinity and NaN print as `inf` and `nan`, or as `nan:0x…` with the `#` flag, giving the
+///   mantissa field in hex as [`Display`](fmt::Display) does for the float itself
+///
+/// Each value is preceded by `-` if its sign bit is set, or by `+` if the `+` flag is given.  The
+/// width, fill, and alignment of the formatter are honored; the precision is ignored.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::F64;
+///
+/// let x = F64::from_bits(0x3fb999999999999a);
+/// assert_eq!("0x1.999999999999ap-4", x.fmt_hex().to_string());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct LowerHexFloat {
+    fmt: Format,
+    bits: u128,
+}
+
+impl LowerHexFloat {
+    pub(crate) const fn new(fmt: Format, bits: u128) -> Self {
+        Self { fmt, bits }
+    }
+}
+
+impl fmt::Display for LowerHexFloat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fmt = self.fmt;
+        // The `0x` prefix is written with the sign, so that zero padding goes after it.
+        let (sign, prefix) = if (self.bits & fmt.sign_mask()) != 0 {
+            ("-", "-0x")
+        } else if f.sign_plus() {
+            ("+", "+0x")
+        } else {
+            ("", "0x")
+        };
+        let exp_field = ((self.bits & fmt.exp_mask()) >> fmt.mant_bits()) as i32;
+        let mant = self.bits & fmt.mant_mask();
+        if exp_field == (fmt.exp_mask() >> fmt.mant_bits()) as i32 {
+            return match mant {
+                0 => pad(f, sign, |w| w.write_str("inf")),
+                _ if f.alternate() => pad(f, sign, |w| write!(w, "nan:{mant:#x}")),
+                _ => pad(f, sign, |w| w.write_str("nan")),
+            };
+        }
+        let (lead, exp) = match (exp_field, mant) {
+            (0, 0) => (0, 0),
+            (0, _) => (0, fmt.emin()),
+            _ => (1, exp_field - fmt.bias()),
+        };
+
+        // Left-align the mantissa field to a whole number of hex digits.
+        let len = fmt.mant_bits().div_ceil(4);
+        let frac = mant << (len * 4 - fmt.mant_bits());
+        let used = len - frac.trailing_zeros().min(len * 4) / 4;
+        pad(f, prefix, |w| {
+            write!(w, "{lead}")?;
+            if used != 0 {
+                w.write_char('.')?;
+                for i in (len - used..len).rev() {
+                    let digit = (frac >> (i * 4)) as u32 & 0xf;
+                    w.write_char(char::from_digit(digit, 16).unwrap())?;
+                }
+            }
+            write!(w, "p{exp:+}")
+        })
+    }
+}
+
+/// Parses a C99 hexadecimal float and rounds it to `fmt`.
+///
+/// The syntax is an optional sign, then `0x` or `0X`, then hex digits with an optional point, then
+/// an optional binary exponent introduced by `p` or `P`.  The spellings of infinity and NaN
+/// accepted by decimal parsing are accepted too.
+pub(crate) fn parse(fmt: Format, s: &str, mode: RoundingMode) -> Result<u128, ParseError> {
+    let s = s.as_bytes();
+    let (sign, start) = split_sign(s)?;
+    let sign_bit = if sign { fmt.sign_mask() } else { 0 };
+    if let Some(result) = parse_special(fmt, sign_bit, s, start) {
+        return result;
+    }
+    let mut i = start;
+    for expected in *b"0x" {
+        if i == s.len() || s[i].to_ascii_lowercase() != expected {
+            return Err(ParseError::invalid(s, i));
+        }
+        i += 1;
+    }
+
+    // The value is `acc × 2^scale`, plus a little more if `sticky` is set.
+    let (mut acc, mut scale, mut sticky) = (0u128, 0i64, false);
+    let (mut mant_digits, mut in_frac) = (0, false);
+    while i < s.len() {
+        if let Some(digit) = (s[i] as char).to_digit(16) {
+            if (acc >> 124) == 0 {
+                acc = (acc << 4) | digit as u128;
+                scale -= 4 * in_frac as i64;
+            } else {
+                sticky |= digit != 0;
+                scale += 4 * !in_frac as i64;
+            }
+            mant_digits += 1;
+        } else if s[i] == b'.' && !in_frac {
+            in_frac = true;
+        } else {
+            break;
+        }
+        i += 1;
+    }
+    if mant_digits == 0 {
+        return Err(ParseError::invalid(s, i));
+    }
+    if i < s.len() && matches!(s[i], b'p' | b'P') {
+        i += 1;
+        let neg = i < s.len() && s[i] == b'-';
+        if i < s.len() && matches!(s[i], b'+' | b'-') {
+            i += 1;
+        }
+        if i == s.len() {
+            return Err(ParseError::invalid(s, i));
+        }
+        let mut exp = 0i64;
+        while i < s.len() {
+            if !s[i].is_ascii_digit() {
+                return Err(ParseError::invalid(s, i));
+            }
+            exp = (exp * 10 + (s[i] - b'0') as i64).min(1 << 40);
+            i += 1;
+        }
+        scale += if neg { -exp } else { exp };
+    }
+    if i < s.len() {
+        return Err(ParseError::invalid(s, i));
+    }
+
+    let value = if acc == 0 {
+        Unpacked::special(sign, Kind::Zero)
+    } else {
+        // Exponents this far out of range overflow or underflow in every format.
+        let shift = acc.leading_zeros();
+        let exp = (scale + 127 - shift as i64).clamp(-(1 << 20), 1 << 20);
+        Unpacked::finite(sign, exp as i32, acc << shift)
+    };
+    Ok(soft::round(fmt, value, sticky, mode))
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::format;
+
+    use crate::{BF16, F16, F32, F64, F128, ParseErrorKind};
+
+    #[test]
+    fn fmt_hex_test() {
+        assert_eq!("0x1.999999999999ap-4", format!("{}", F64::from_float(0.1).fmt_hex()));
+        assert_eq!("0x1.99999ap-4", format!("{}", F32::from_float(0.1).fmt_hex()));
+        assert_eq!("0x1p+0", format!("{}", F64::ONE.fmt_hex()));
+        assert_eq!("-0x1.8p+1", format!("{}", F16::from_bits(0xc200).fmt_hex()));
+        assert_eq!("0x1.ffcp+15", format!("{}", F16::MAX.fmt_hex()));
+        assert_eq!("0x1.fep+127", format!("{}", BF16::MAX.fmt_hex()));
+        assert_eq!("0x1p-126", format!("{}", BF16::MIN_POSITIVE.fmt_hex()));
+        assert_eq!("0x0.0000000000001p-1022", format!("{}", F64::from_bits(1).fmt_hex()));
+        assert_eq!("0x0.000002p-126", format!("{}", F32::from_bits(1).fmt_hex()));
+        assert_eq!("0x1.ffffffffffffffffffffffffffffp+16383", format!("{}", F128::MAX.fmt_hex()));
+        assert_eq!("0x0p+0", format!("{}", F64::ZERO.fmt_hex()));
+        assert_eq!("-0x0p+0", format!("{}", F64::NEG_ZERO.fmt_hex()));
+        assert_eq!("-inf", format!("{}", F128::NEG_INFINITY.fmt_hex()));
+        assert_eq!("nan", format!("{}", F32::QNAN.fmt_hex()));
+        assert_eq!("-nan:0x1", format!("{:#}", F64::from_bits(0xfff0000000000001).fmt_hex()));
+        assert_eq!("+0x1p+0", format!("{:+}", F64::ONE.fmt_hex()));
+        assert_eq!("  -0x1p+0", format!("{:>9}", F64::NEG_ONE.fmt_hex()));
+        assert_eq!("-0x0001p+0", format!("{:010}", F64::NEG_ONE.fmt_hex()));
+    }
+
+    #[test]
+    fn from_hex_str_test() {
+        assert_eq!(F64::from_float(12.0), F64::from_hex_str("0x1.8p3").unwrap());
+        assert_eq!(F64::from_float(0.1), F64::from_hex_str("0x1.999999999999ap-4").unwrap());
+        assert_eq!(F64::from_float(-0.5), F64::from_hex_str("-0X.8").unwrap());
+        assert_eq!(F64::from_float(255.0), F64::from_hex_str("+0xfF.P0").unwrap());
+        assert_eq!(F64::from_bits(1), F64::from_hex_str("0x0.0000000000001p-1022").unwrap());
+        assert_eq!(F64::NEG_ZERO, F64::from_hex_str("-0x0p+99999999999999999999").unwrap());
+        assert_eq!(F64::INFINITY, F64::from_hex_str("0x1p1024").unwrap());
+        assert_eq!(F64::ZERO, F64::from_hex_str("0x1p-1076").unwrap());
+        assert_eq!(F64::NEG_INFINITY, F64::from_hex_str("-Infinity").unwrap());
+        assert!(F64::from_hex_str("nan").unwrap().is_nan());
+
+        // 1 + 2^-24 is halfway between two F32 values, so ties to even rounds down, and any
+        // nonzero digit beyond it rounds up, no matter how far away.
+        assert_eq!(F32::ONE, F32::from_hex_str("0x1.000001").unwrap());
+        assert_eq!(F32::from_bits(0x3f800001), F32::from_hex_str("0x1.0000018").unwrap());
+        let far = format!("0x1.000001{}1", "0".repeat(100));
+        assert_eq!(F32::from_bits(0x3f800001), F32::from_hex_str(&far).unwrap());
+        assert_eq!(BF16::from_bits(0x3f80), BF16::from_hex_str("0x1.01p0").unwrap());
+        assert_eq!(F16::MAX, F16::from_hex_str("0x1.ffdfffp15").unwrap());
+        assert_eq!(F16::INFINITY, F16::from_hex_str("0x1.ffep15").unwrap());
+
+        let errors = [
+            ("", ParseErrorKind::Empty, 0, None),
+            ("0x", ParseErrorKind::InvalidSyntax, 2, None),
+            ("1.8p3", ParseErrorKind::InvalidSyntax, 0, Some('1')),
+            ("0x.", ParseErrorKind::InvalidSyntax, 3, None),
+            ("0x1p", ParseErrorKind::InvalidSyntax, 4, None),
+            ("0x1p+", ParseErrorKind::InvalidSyntax, 5, None),
+            ("0x1.8q3", ParseErrorKind::InvalidSyntax, 5, Some('q')),
+            ("0x1.2.3", ParseErrorKind::InvalidSyntax, 5, Some('.')),
+            ("-", ParseErrorKind::InvalidSyntax, 1, None),
+            (" 0x1", ParseErrorKind::InvalidSyntax, 0, Some(' ')),
+        ];
+        for (s, kind, offset, token) in errors {
+            let err = F64::from_hex_str(s).unwrap_err();
+            assert_eq!((kind, offset, token), (err.kind(), err.offset(), err.token()), "{s}");
+        }
+        for x in [F128::MAX, F128::from_bits(1), F128::NEG_ONE, F128::from_bits(0x3ffb9999 << 96)] {
+            assert_eq!(x, F128::from_hex_str(&format!("{}", x.fmt_hex())).unwrap());
+        }
+    }
+}
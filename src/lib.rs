@@ -6,6 +6,11 @@
 //! suite of operations are available that operate directly on the raw bit representation,
 //! bypassing the need for Rust support.
 //!
+//! Two 8-bit formats of the OCP FP8 specification, used for machine learning, are included as well:
+//! [`F8E5M2`] follows the IEEE 754 rules like the wider types, while [`F8E4M3`] gives up its
+//! infinities for range, and so has only conversions, classification, and ordering.  The
+//! [`quant::fp8`] module quantizes `f32` tensors to it.
+//!
 //! # Example
 //!
 //! ```rust
@@ -17,16 +22,199 @@
 //! assert_eq!(x, z);
 //! assert_eq!(0x3fb999999999999a, y.to_bits());
 //! ```
+//!
+//! # Serde
+//!
+//! With crate feature `serde`, each type serializes as a struct with a single `bits` field.  The
+//! adapters in [`serde`](mod@serde) choose the representation of a single field instead: for
+//! example, [`as_human_readable`](serde::as_human_readable) gives human-readable formats such as
+//! JSON the shortest decimal string that parses back to the same bits, like `"0.1"` or
+//! `"nan:0x1"`, while binary formats keep the raw bits, and [`lenient`](serde::lenient) keeps the
+//! struct but also reads decimal strings and plain numbers.
+//!
+//! With crate feature `schemars`, each type implements `JsonSchema` to match the struct.
+//!
+//! # Byte casts
+//!
+//! Each type is `#[repr(transparent)]` over its unsigned integer.  With crate feature `zerocopy`,
+//! each type implements `FromBytes`, `IntoBytes`, `KnownLayout`, and `Immutable`, so that a byte
+//! buffer can be viewed as a slice of floats without copying.  With crate feature `bytemuck`, each
+//! type implements `Pod` and `Zeroable`, so that functions like `bytemuck::cast_slice` accept it.
+//!
+//! # Embedded logging
+//!
+//! With crate feature `defmt`, each type implements `defmt::Format`, printing its name, its bits
+//! in hex, and its approximate value, like `F16(0x3c00 ≈ 1.0)`.  With crate feature `ufmt`, each
+//! type implements `uDisplay`, writing the same shortest decimal as `Display`, and `uDebug`,
+//! writing the same struct as `Debug`, without the `core::fmt` machinery.  Like
+//! [`F16::format_into`], `uDisplay` computes the digits with big integers on the stack, sized for
+//! the format: in optimized builds, about 1 KiB for formats no wider than `F32`, 2 KiB for `F64`,
+//! and 16 KiB for `F128`.
+//!
+//! # Fuzzing
+//!
+//! With crate feature `arbitrary`, each type implements `arbitrary::Arbitrary`.  Only a quarter of
+//! the generated values have uniform bits; the rest favor zeros, subnormals, infinities, NaNs with
+//! varied payloads, extreme exponents, and small integers, which uniform bits rarely reach.
+//!
+//! # Random numbers
+//!
+//! With crate feature `rand`, each type can be sampled from `rand`'s `StandardUniform`, `Open01`,
+//! and `OpenClosed01` distributions, and every type except [`F128`] can be sampled from a range
+//! with `Rng::random_range`; see `UniformFloatBits`.  The `BitUniform` distribution instead
+//! gives every bit pattern with equal probability.
+//!
+//! # Generic numeric code
+//!
+//! The [`FloatBits`] trait, implemented by every type, exposes the API that the formats share, such
+//! as the raw bits, the special constants, classification, and `total_cmp`, so that code can be
+//! generic over the format.  Its object-safe counterpart, [`DynFloat`], covers classification,
+//! the sign, the [`FloatFormat`], and a lossy conversion to `f64`, so values of different formats
+//! can be handled together through `&dyn DynFloat`.
+//!
+//! With crate feature `num-traits`, each type with arithmetic (see the `f16` and `f128` features)
+//! implements `Zero`, `One`, `Num`, `Bounded`, and `Signed` from `num_traits`.  `BF16` has no
+//! arithmetic, so it implements none of them.  [`F32`] and [`F64`] also implement `NumCast` and
+//! `FloatCore`, and with crate feature `libm` or `std`, `Float`, each by way of the native float.
+//! Every type, `BF16` included, implements `ToPrimitive` and `FromPrimitive`, rounding exactly
+//! in software.
+//!
+//! With crate feature `approx`, each type with arithmetic implements `AbsDiffEq`, `RelativeEq`,
+//! and `UlpsEq`, so that `approx::assert_relative_eq!` and its kin accept the types directly.
+//!
+//! # Ordered floats
+//!
+//! With crate feature `ordered-float`, [`F32`] and [`F64`] convert to and from `OrderedFloat`, and
+//! from `NotNan`, keeping the bits exactly; the conversion to `NotNan` is `TryFrom` and fails for
+//! NaN.  The orderings differ: these types order by the IEEE 754 `totalOrder` predicate, so `-0.0`
+//! sorts before `+0.0` and each NaN has its own place by sign and payload, with negative NaNs
+//! before negative infinity.  `OrderedFloat` treats the two zeros as equal and every NaN as equal
+//! to every other and greater than positive infinity, and `NotNan` treats the two zeros as equal.
+//! Code that relies on equality or hashing may therefore see different keys after migrating.
+//!
+//! # Compiler interop
+//!
+//! With crate feature `rustc_apfloat`, each type converts to and from its counterpart among the
+//! IEEE types of `rustc_apfloat`, copying the bits exactly, so that a compiler or interpreter can
+//! do arithmetic in apfloat and store the results as these types.
+//!
+//! # Fixed point
+//!
+//! Every type has `from_fixed` and `to_fixed` methods that convert raw Q-format values, such as
+//! Q15 and Q31 samples, with an explicit rounding mode.  With crate feature `fixed`, each type
+//! also implements `FromFixed` and `ToFixed` from the `fixed` crate, so that its `to_num` and
+//! `from_num` accept these types.
+//!
+//! # SIMD
+//!
+//! With crate feature `portable-simd`, which requires a nightly compiler, the bulk slice
+//! operations [`BF16::convert_slice`], [`F16::convert_slice`], [`F32::classify_slice`],
+//! [`total_keys_f32`], and [`total_keys_f64`] are vectorized with `core::simd`.  The results are
+//! identical to those of the scalar code.
 
 #![no_std]
 #![cfg_attr(feature = "f16", feature(f16))]
 #![cfg_attr(feature = "f128", feature(f128))]
+#![cfg_attr(feature = "portable-simd", feature(portable_simd))]
 #![allow(missing_docs)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 #[macro_use]
 mod macros;
 
+#[cfg(feature = "rustc_apfloat")]
+mod apfloat;
+mod atomic;
+pub mod codec;
+pub mod consts;
+mod context;
+mod dd;
+mod decimal;
+mod eft;
+mod error;
+mod exact;
+mod exhaustive;
+#[cfg(feature = "fixed")]
+mod fixed_point;
+mod fp8;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
 mod helpers;
+mod hex;
+#[cfg(feature = "macros")]
+mod literals;
+#[cfg(any(feature = "libm", feature = "std"))]
+mod math;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+pub mod quant;
+#[cfg(feature = "rand")]
+mod random;
+pub mod refined;
+mod rounding;
+mod sanitize;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod sign;
+#[cfg(feature = "portable-simd")]
+mod simd;
+mod soft;
+mod sort;
+mod stats;
+mod status;
+mod sum;
+mod traits;
+mod wide;
+
+#[cfg(target_has_atomic = "32")]
+pub use atomic::AtomicF32Bits;
+#[cfg(target_has_atomic = "64")]
+pub use atomic::AtomicF64Bits;
+pub use context::FloatContext;
+pub use dd::DD128;
+pub use decimal::BufferTooSmall;
+pub use error::{ParseError, ParseErrorKind};
+pub use exact::{IeeeCmp, total_cmp_across};
+pub use exhaustive::{AllValues, RangeIter};
+pub use fp8::F8E4M3;
+pub use hex::LowerHexFloat;
+#[cfg(feature = "rand")]
+pub use random::{BitUniform, UniformFloatBits};
+pub use rounding::RoundingMode;
+pub use sign::Sign;
+#[cfg(feature = "alloc")]
+pub use sort::radix_sort_total;
+pub use sort::{
+    sort_total_f32,
+    sort_total_f64,
+    total_key_f32,
+    total_key_f64,
+    total_keys_f32,
+    total_keys_f64,
+};
+pub use stats::SliceStats;
+pub use status::StatusFlags;
+pub use sum::{CompensatedSum, ExactSum, exact_dot};
+pub use traits::{DynFloat, FloatBits, FloatFormat};
+
+define! {
+    #[doc = "A newtype containing the raw bits of an OCP FP8 E5M2 floating point number."]
+    #[doc = ""]
+    #[doc = "The format follows the IEEE 754 rules, with 5 exponent bits and 2 fraction bits, "]
+    #[doc = "so it has infinities, NaNs, and subnormals like the wider formats."]
+    #[doc = ""]
+    #[doc = "Values of this type are hashable and have a well-defined total order: the one given "]
+    #[doc = "by [`Self::total_cmp`].  As a consequence, `+0.0` is not equal to `-0.0`, and NaN "]
+    #[doc = "compares equal to NaN if both NaN values have exactly the same bit pattern."]
+    pub struct F8E5M2;
+    size 8 bits;
+    exp 5 bits;
+    repr u8 / i8;
+}
 
 define! {
     #[doc = "A newtype containing the raw bits of a Google BFloat16 floating point number."]
@@ -104,35 +292,373 @@ define! {
 
 impl core::fmt::Display for F64 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let val = self.to_float();
-        core::fmt::Display::fmt(&val, f)
+        // The standard library ignores the alternate flag, which prints NaN payloads and a
+        // fractional digit here.
+        if f.alternate() {
+            decimal::display(Self::FORMAT, self.bits as u128, f)
+        } else {
+            core::fmt::Display::fmt(&self.to_float(), f)
+        }
     }
 }
 
 impl core::str::FromStr for F64 {
     type Err = core::num::ParseFloatError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self::from_float(s.parse()?))
+        // The standard library is much faster, and rejects only the extra spellings of NaN.  Any
+        // other error is reported as the standard library reports it.
+        match s.parse() {
+            Ok(float) => Ok(Self::from_float(float)),
+            Err(err) => match decimal::parse(Self::FORMAT, s, RoundingMode::TiesToEven) {
+                Ok(bits) => Ok(Self::from_bits(bits as u64)),
+                Err(_) => Err(err),
+            },
+        }
+    }
+}
+
+impl core::fmt::Display for F128 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        decimal::display(Self::FORMAT, self.bits, f)
+    }
+}
+
+impl core::str::FromStr for F128 {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bits = decimal::parse(Self::FORMAT, s, RoundingMode::TiesToEven)?;
+        Ok(Self::from_bits(bits))
+    }
+}
+
+impl core::fmt::Display for F8E5M2 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        decimal::display(Self::FORMAT, self.bits as u128, f)
+    }
+}
+
+impl core::str::FromStr for F8E5M2 {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bits = decimal::parse(Self::FORMAT, s, RoundingMode::TiesToEven)?;
+        Ok(Self::from_bits(bits as u8))
+    }
+}
+
+impl core::fmt::Display for F16 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        decimal::display(Self::FORMAT, self.bits as u128, f)
+    }
+}
+
+impl core::str::FromStr for F16 {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bits = decimal::parse(Self::FORMAT, s, RoundingMode::TiesToEven)?;
+        Ok(Self::from_bits(bits as u16))
+    }
+}
+
+impl core::fmt::Display for BF16 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        decimal::display(Self::FORMAT, self.bits as u128, f)
+    }
+}
+
+impl core::str::FromStr for BF16 {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bits = decimal::parse(Self::FORMAT, s, RoundingMode::TiesToEven)?;
+        Ok(Self::from_bits(bits as u16))
     }
 }
 
 impl core::fmt::Display for F32 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let val = self.to_float();
-        core::fmt::Display::fmt(&val, f)
+        // The standard library ignores the alternate flag, which prints NaN payloads and a
+        // fractional digit here.
+        if f.alternate() {
+            decimal::display(Self::FORMAT, self.bits as u128, f)
+        } else {
+            core::fmt::Display::fmt(&self.to_float(), f)
+        }
     }
 }
 
 impl core::str::FromStr for F32 {
     type Err = core::num::ParseFloatError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self::from_float(s.parse()?))
+        // The standard library is much faster, and rejects only the extra spellings of NaN.  Any
+        // other error is reported as the standard library reports it.
+        match s.parse() {
+            Ok(float) => Ok(Self::from_float(float)),
+            Err(err) => match decimal::parse(Self::FORMAT, s, RoundingMode::TiesToEven) {
+                Ok(bits) => Ok(Self::from_bits(bits as u32)),
+                Err(_) => Err(err),
+            },
+        }
+    }
+}
+
+impl BF16 {
+    /// Converts an `f32` to BF16 by dropping the low 16 bits of its representation.
+    ///
+    /// This rounds toward zero, as the truncating conversion of many machine learning frameworks
+    /// does.  Every NaN is quieted, keeping its sign and the high bits of its payload, since a
+    /// NaN whose payload lies entirely in the dropped bits would otherwise become infinite.
+    pub const fn from_f32_truncate(float: f32) -> Self {
+        let bits = (float.to_bits() >> 16) as u16;
+        if float.is_nan() {
+            Self::from_bits(bits | Self::QUIET_MASK)
+        } else {
+            Self::from_bits(bits)
+        }
+    }
+
+    /// Converts an `f32` to BF16, rounding to nearest with ties to even.
+    ///
+    /// This matches the hardware conversion instructions for BF16 bit for bit: values too large
+    /// for BF16 round to infinity, and NaN is quieted with its sign and the high bits of its
+    /// payload preserved.
+    pub const fn from_f32_round(float: f32) -> Self {
+        Self::from_bits(Self::round_f32_bits(float.to_bits()))
+    }
+
+    // Written without branches on the value, so that loops over it vectorize.
+    const fn round_f32_bits(bits: u32) -> u16 {
+        let is_nan = bits & 0x7fff_ffff > 0x7f80_0000;
+        let quieted = (bits >> 16) as u16 | Self::QUIET_MASK;
+        let round = 0x7fff + ((bits >> 16) & 1);
+        let rounded = (bits.wrapping_add(round) >> 16) as u16;
+        if is_nan { quieted } else { rounded }
+    }
+
+    /// Converts every element of `src` with [`Self::from_f32_round`], writing the results to
+    /// `dst`.
+    ///
+    /// The loop is free of branches on the values, so the compiler can vectorize it, which makes
+    /// this much faster than converting one element at a time for large tensors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use float_bits::{BF16, F32};
+    ///
+    /// let src = [1.0, 1.00390625, -3.0e38].map(F32::from_float);
+    /// let mut dst = [BF16::ZERO; 3];
+    /// BF16::convert_slice(&src, &mut dst);
+    /// assert_eq!([0x3f80, 0x3f80, 0xff62], dst.map(|x| x.to_bits()));
+    /// ```
+    pub fn convert_slice(src: &[F32], dst: &mut [BF16]) {
+        assert_eq!(src.len(), dst.len(), "slices must have the same length");
+        #[cfg(feature = "portable-simd")]
+        let (src, dst) = {
+            let done = simd::f32_to_bf16(src, dst);
+            (&src[done..], &mut dst[done..])
+        };
+        for (d, s) in dst.iter_mut().zip(src) {
+            *d = Self::from_bits(Self::round_f32_bits(s.bits));
+        }
+    }
+
+    /// Converts `self` to an `f32`.  The result is always exact.
+    pub const fn to_f32(&self) -> f32 {
+        f32::from_bits((self.bits as u32) << 16)
+    }
+}
+
+impl F16 {
+    /// Converts an `f32` to F16, rounding to nearest with ties to even.
+    ///
+    /// This matches the hardware conversion instructions for F16 bit for bit: values too large
+    /// for F16 round to infinity, and NaN is quieted with its sign and the high bits of its
+    /// payload preserved.
+    pub const fn from_f32_round(float: f32) -> Self {
+        Self::from_bits(Self::round_f32_bits(float.to_bits()))
+    }
+
+    // Written without branches on the value, so that loops over it vectorize.  The kernel in
+    // `simd` computes the same thing.
+    const fn round_f32_bits(bits: u32) -> u16 {
+        let sign = (bits >> 16) & 0x8000;
+        let abs = bits & 0x7fff_ffff;
+
+        let quieted = ((abs >> 13) & 0x03ff) | 0x7e00;
+
+        // Rebias the exponent from 127 to 15, then round away the low 13 bits.
+        let normal = abs.wrapping_sub(0x3800_0000);
+        let normal = normal.wrapping_add(0x0fff + ((normal >> 13) & 1)) >> 13;
+
+        // Subnormal results are multiples of 2^-24, so shift by the distance to that.
+        let mant = (abs & 0x007f_ffff) | 0x0080_0000;
+        let exp = if abs >> 23 < 112 { abs >> 23 } else { 112 };
+        let shift = if 126 - exp < 31 { 126 - exp } else { 31 };
+        let subnormal = (mant + (1 << (shift - 1)) - 1 + ((mant >> shift) & 1)) >> shift;
+
+        let out = if abs < 0x3880_0000 { subnormal } else { normal };
+        let out = if abs >= 0x477f_f000 { 0x7c00 } else { out };
+        let out = if abs > 0x7f80_0000 { quieted } else { out };
+        (out | sign) as u16
+    }
+
+    /// Converts every element of `src` with [`Self::from_f32_round`], writing the results to
+    /// `dst`.
+    ///
+    /// See [`BF16::convert_slice`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    pub fn convert_slice(src: &[F32], dst: &mut [F16]) {
+        assert_eq!(src.len(), dst.len(), "slices must have the same length");
+        #[cfg(feature = "portable-simd")]
+        let (src, dst) = {
+            let done = simd::f32_to_f16(src, dst);
+            (&src[done..], &mut dst[done..])
+        };
+        for (d, s) in dst.iter_mut().zip(src) {
+            *d = Self::from_bits(Self::round_f32_bits(s.bits));
+        }
+    }
+}
+
+impl F8E5M2 {
+    /// Converts an `f32` to E5M2, rounding to nearest with ties to even.
+    ///
+    /// Values too large for E5M2 round to infinity, and NaN is quieted with its sign and the
+    /// high bit of its payload preserved, as for [`F16::from_f32_round`].  Rounding through F16
+    /// instead would round twice, which gives the wrong result just above the midpoints.
+    pub const fn from_f32_round(float: f32) -> Self {
+        Self::from_bits(Self::round_f32_bits(float.to_bits()))
+    }
+
+    // Written without branches on the value, so that loops over it vectorize.  E5M2 has the
+    // exponent of F16, so this is the F16 conversion with 21 bits rounded away instead of 13.
+    const fn round_f32_bits(bits: u32) -> u8 {
+        let sign = (bits >> 24) & 0x80;
+        let abs = bits & 0x7fff_ffff;
+
+        let quieted = ((abs >> 21) & 0x03) | 0x7e;
+
+        // Rebias the exponent from 127 to 15, then round away the low 21 bits.
+        let normal = abs.wrapping_sub(0x3800_0000);
+        let normal = normal.wrapping_add(0x0f_ffff + ((normal >> 21) & 1)) >> 21;
+
+        // Subnormal results are multiples of 2^-16, so shift by the distance to that.
+        let mant = (abs & 0x007f_ffff) | 0x0080_0000;
+        let exp = if abs >> 23 < 112 { abs >> 23 } else { 112 };
+        let shift = if 134 - exp < 31 { 134 - exp } else { 31 };
+        let subnormal = (mant + (1 << (shift - 1)) - 1 + ((mant >> shift) & 1)) >> shift;
+
+        let out = if abs < 0x3880_0000 { subnormal } else { normal };
+        let out = if abs >= 0x4770_0000 { 0x7c } else { out };
+        let out = if abs > 0x7f80_0000 { quieted } else { out };
+        (out | sign) as u8
+    }
+
+    /// Converts every element of `src` with [`Self::from_f32_round`], writing the results to
+    /// `dst`.
+    ///
+    /// See [`BF16::convert_slice`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    pub fn convert_slice(src: &[F32], dst: &mut [F8E5M2]) {
+        assert_eq!(src.len(), dst.len(), "slices must have the same length");
+        for (d, s) in dst.iter_mut().zip(src) {
+            *d = Self::from_bits(Self::round_f32_bits(s.bits));
+        }
+    }
+}
+
+impl F128 {
+    /// Returns the square root of `self`, correctly rounded.
+    ///
+    /// Returns NaN if `self` is a negative number other than `-0.0`.
+    ///
+    /// This is computed in software on the raw bits, so it is available without the `f128`
+    /// feature.
+    pub const fn sqrt(&self) -> Self {
+        self.sqrt_rounded(RoundingMode::TiesToEven)
+    }
+
+    /// Returns the square root of `self`, rounded according to `mode`.
+    ///
+    /// See [`Self::sqrt`] for details.
+    pub const fn sqrt_rounded(&self, mode: RoundingMode) -> Self {
+        let bits = soft::sqrt(Self::FORMAT, self.bits, mode);
+        Self::from_bits(bits)
+    }
+
+    /// Returns `e^(self)`, the exponential function.
+    ///
+    /// This is computed in software on the raw bits, so it is available without the `f128`
+    /// feature.  The computation is carried out with 256 bits of working precision and rounded
+    /// once, so the result is faithfully rounded.
+    pub const fn exp(&self) -> Self {
+        self.exp_rounded(RoundingMode::TiesToEven)
+    }
+
+    /// Returns `e^(self)`, rounded according to `mode`.
+    ///
+    /// See [`Self::exp`] for details.  Thanks to the working precision, the result lies on the
+    /// side of the exact value that `mode` asks for, unless the exact value agrees with a
+    /// representable number to more than about 240 bits.
+    pub const fn exp_rounded(&self, mode: RoundingMode) -> Self {
+        let bits = wide::exp_bits(Self::FORMAT, self.bits, mode);
+        Self::from_bits(bits)
+    }
+
+    /// Returns the natural logarithm of `self`.
+    ///
+    /// This is computed in software on the raw bits, so it is available without the `f128`
+    /// feature.  The computation is carried out with 256 bits of working precision and rounded
+    /// once, so the result is faithfully rounded.
+    pub const fn ln(&self) -> Self {
+        self.ln_rounded(RoundingMode::TiesToEven)
+    }
+
+    /// Returns the natural logarithm of `self`, rounded according to `mode`.
+    ///
+    /// See [`Self::ln`] for details, and [`Self::exp_rounded`] for the direction of rounding.
+    pub const fn ln_rounded(&self, mode: RoundingMode) -> Self {
+        let bits = wide::ln_bits(Self::FORMAT, self.bits, mode);
+        Self::from_bits(bits)
+    }
+
+    /// Raises `self` to the floating point power `n`.
+    ///
+    /// Special cases follow the `pow` operation of IEEE 754 (2019 revision), e.g. `x.pow(±0.0)`
+    /// and `1.0.pow(y)` are `+1.0` even for NaN.  Negative values raised to a non-integer power
+    /// give NaN.
+    ///
+    /// This is computed in software on the raw bits, so it is available without the `f128`
+    /// feature.  The computation is carried out with 256 bits of working precision and rounded
+    /// once, so the result is faithfully rounded.
+    pub const fn pow(&self, n: Self) -> Self {
+        self.pow_rounded(n, RoundingMode::TiesToEven)
+    }
+
+    /// Raises `self` to the floating point power `n`, rounded according to `mode`.
+    ///
+    /// See [`Self::pow`] for details, and [`Self::exp_rounded`] for the direction of rounding.
+    pub const fn pow_rounded(&self, n: Self, mode: RoundingMode) -> Self {
+        let bits = wide::pow_bits(Self::FORMAT, self.bits, n.bits, mode);
+        Self::from_bits(bits)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    extern crate std;
+
     use core::num::FpCategory;
+    use std::format;
 
     use super::*;
     use crate::helpers::{C_INF, C_NAN, C_NORM, C_ZERO};
@@ -226,4 +752,1136 @@ mod tests {
             assert_eq!(bits, val.to_bits());
         }
     }
+
+    #[test]
+    fn powi_test() {
+        let two = BF16::from_bits(0x4000);
+        assert_eq!(0x4480, two.powi(10).to_bits());
+        assert_eq!(0x3e80, two.powi(-2).to_bits());
+        assert_eq!(0xc100, two.neg().powi(3).to_bits());
+        assert_eq!(BF16::ONE, BF16::NAN.powi(0));
+        assert!(BF16::NAN.powi(3).is_nan());
+        assert_eq!(BF16::INFINITY, BF16::ZERO.powi(-1));
+        assert_eq!(BF16::NEG_INFINITY, BF16::NEG_ZERO.powi(-1));
+        assert_eq!(BF16::ZERO, BF16::NEG_ZERO.powi(2));
+        assert_eq!(BF16::NEG_ZERO, BF16::NEG_INFINITY.powi(-3));
+
+        let two = F64::from_float(2.0);
+        assert_eq!(1, two.powi(-1074).to_bits());
+        assert_eq!(F64::ZERO, two.powi(-1075));
+        assert_eq!(F64::MIN_POSITIVE, two.powi(-1022));
+        assert_eq!(F64::INFINITY, two.powi(1024));
+        assert_eq!(F64::MAX, two.powi_rounded(1024, RoundingMode::TowardZero));
+        assert_eq!(F64::INFINITY, two.powi_rounded(1024, RoundingMode::TowardPositive));
+        assert_eq!(F64::MAX, two.powi_rounded(1024, RoundingMode::TowardNegative));
+        assert_eq!(F64::MIN, two.neg().powi_rounded(1025, RoundingMode::TowardPositive));
+        assert_eq!(F64::NEG_INFINITY, two.neg().powi_rounded(1025, RoundingMode::TowardNegative));
+        assert_eq!(1, two.powi_rounded(-1080, RoundingMode::TowardPositive).to_bits());
+        assert_eq!(F64::NEG_ZERO, two.neg().powi_rounded(-1081, RoundingMode::TowardPositive));
+        assert_eq!(F64::INFINITY, two.powi(i32::MAX));
+        assert_eq!(F64::ZERO, two.powi(i32::MIN));
+
+        let x = F64::from_float(1.1);
+        assert_eq!(1.1 * 1.1, x.powi(2).to_float());
+        assert_eq!(1.0 / 3.0, F64::from_float(3.0).powi(-1).to_float());
+        assert_eq!(F32::from_float(1.0 / 3.0), F32::from_float(3.0).powi(-1));
+        let three = F32::from_float(3.0);
+        assert_eq!(0x3eaaaaab, three.powi_rounded(-1, RoundingMode::TowardPositive).to_bits());
+        assert_eq!(0x3eaaaaaa, three.powi_rounded(-1, RoundingMode::TowardNegative).to_bits());
+        assert_eq!(
+            0xbeaaaaaa,
+            three.neg().powi_rounded(-1, RoundingMode::TowardPositive).to_bits()
+        );
+    }
+
+    #[test]
+    fn flush_subnormals_test() {
+        assert_eq!(BF16::NEG_ZERO, BF16::from_bits(0x8001).flush_subnormals_to_zero());
+        assert_eq!(F128::ZERO, F128::from_bits(1).flush_subnormals_to_zero());
+        assert_eq!(F16::MIN_POSITIVE, F16::MIN_POSITIVE.flush_subnormals_to_zero());
+        assert_eq!(F16::NEG_SNAN, F16::NEG_SNAN.flush_subnormals_to_zero());
+
+        let tiny = F32::MIN_POSITIVE;
+        let half = F32::from_float(0.5);
+        assert_eq!(F32::ZERO, F32::from_float_ftz(tiny.to_float() * half.to_float()));
+        assert_eq!(tiny, F32::from_float_ftz(tiny.to_float() * 1.0));
+        let sub = F64::from_bits(0x000fffffffffffff);
+        assert_eq!(0.0, sub.to_float_daz() + 0.0);
+        assert_eq!(F64::MIN_POSITIVE, F64::from_float_ftz(sub.to_float() + f64::from_bits(1)));
+    }
+
+    #[test]
+    fn bf16_from_f32_test() {
+        type Row = (u32, u16, u16);
+        const ROWS: [Row; 10] = [
+            (0x3f800000, 0x3f80, 0x3f80),
+            (0x3f808000, 0x3f80, 0x3f80),
+            (0x3f818000, 0x3f81, 0x3f82),
+            (0x3f808001, 0x3f80, 0x3f81),
+            (0xbf80ffff, 0xbf80, 0xbf81),
+            (0x7f7fffff, 0x7f7f, 0x7f80),
+            (0x00008000, 0x0000, 0x0000),
+            (0x8001ffff, 0x8001, 0x8002),
+            (0x7f800001, 0x7fc0, 0x7fc0),
+            (0xffa00000, 0xffe0, 0xffe0),
+        ];
+        for (bits, truncated, rounded) in ROWS {
+            let float = f32::from_bits(bits);
+            assert_eq!(truncated, BF16::from_f32_truncate(float).to_bits(), "{bits:#x}");
+            assert_eq!(rounded, BF16::from_f32_round(float).to_bits(), "{bits:#x}");
+        }
+        assert_eq!(1.0, BF16::ONE.to_f32());
+        assert_eq!(f32::from_bits(0x80010000), BF16::from_bits(0x8001).to_f32());
+    }
+
+    #[test]
+    fn f16_from_f32_test() {
+        type Row = (u32, u16);
+        const ROWS: [Row; 13] = [
+            (0x3f800000, 0x3c00),
+            (0x3f801000, 0x3c00),
+            (0x3f803000, 0x3c02),
+            (0xbf801001, 0xbc01),
+            (0x477fe000, 0x7bff),
+            (0x477ff000, 0x7c00),
+            (0x38800000, 0x0400),
+            (0x387fe000, 0x0400),
+            (0x33800000, 0x0001),
+            (0x33000000, 0x0000),
+            (0xb3800001, 0x8001),
+            (0x7f800001, 0x7e00),
+            (0xffa00000, 0xff00),
+        ];
+        for (bits, rounded) in ROWS {
+            let float = f32::from_bits(bits);
+            assert_eq!(rounded, F16::from_f32_round(float).to_bits(), "{bits:#x}");
+        }
+        for i in 0..0x10000u32 {
+            let float = f32::from_bits(i.wrapping_mul(0x9e3779b9));
+            if !float.is_nan() {
+                let expected = soft::convert(F32::FORMAT, F16::FORMAT, float.to_bits() as u128);
+                assert_eq!(expected as u16, F16::from_f32_round(float).to_bits(), "{float}");
+            }
+        }
+    }
+
+    #[test]
+    fn f16_convert_slice_test() {
+        let src: [F32; 67] = core::array::from_fn(|i| {
+            F32::from_bits((i as u32).wrapping_mul(0x9e3779b9) >> (i % 9))
+        });
+        let mut dst = [F16::ZERO; 67];
+        F16::convert_slice(&src, &mut dst);
+        for (s, d) in src.iter().zip(dst) {
+            assert_eq!(F16::from_f32_round(s.to_float()), d, "{:#x}", s.bits);
+        }
+    }
+
+    #[test]
+    fn f8e5m2_test() {
+        for x in (0..=255).map(F8E5M2::from_bits) {
+            // E5M2 is F16 with the low 8 bits dropped.
+            let wide = F16::from_bits((x.to_bits() as u16) << 8);
+            assert_eq!(wide.classify(), x.classify());
+            if x.is_finite() {
+                assert_eq!(format!("{wide}").parse::<F8E5M2>(), Ok(x));
+                assert_eq!(format!("{x}").parse::<F8E5M2>(), Ok(x));
+            }
+        }
+        assert_eq!(
+            "60000 0.00002 -inf",
+            format!("{} {} {}", F8E5M2::MAX, F8E5M2::from_bits(1), F8E5M2::NEG_INFINITY)
+        );
+
+        assert_eq!(0x7b, F8E5M2::from_f32_round(61439.996).to_bits());
+        assert_eq!(0x7c, F8E5M2::from_f32_round(61440.0).to_bits());
+        assert_eq!(0x7e, F8E5M2::from_f32_round(f32::NAN).to_bits());
+        assert_eq!(0x3c, F8E5M2::from_f32_round(1.125).to_bits());
+        assert_eq!(0x3e, F8E5M2::from_f32_round(1.375).to_bits());
+        for i in 0..0x10000u32 {
+            let float = f32::from_bits(i.wrapping_mul(0x9e3779b9));
+            if !float.is_nan() {
+                let expected = soft::convert(F32::FORMAT, F8E5M2::FORMAT, float.to_bits() as u128);
+                assert_eq!(expected as u8, F8E5M2::from_f32_round(float).to_bits(), "{float}");
+            }
+        }
+
+        let src = [1.0, 1.25, -3.0e38, 1e-8].map(F32::from_float);
+        let mut dst = [F8E5M2::ZERO; 4];
+        F8E5M2::convert_slice(&src, &mut dst);
+        assert_eq!([0x3c, 0x3d, 0xfc, 0x00], dst.map(|x| x.to_bits()));
+    }
+
+    #[test]
+    fn bf16_convert_slice_test() {
+        let src: [F32; 64] =
+            core::array::from_fn(|i| F32::from_bits((i as u32).wrapping_mul(0x9e3779b9)));
+        let mut dst = [BF16::ZERO; 64];
+        BF16::convert_slice(&src, &mut dst);
+        for (s, d) in src.iter().zip(dst) {
+            assert_eq!(BF16::from_f32_round(s.to_float()), d, "{:#x}", s.bits);
+        }
+        BF16::convert_slice(&[], &mut []);
+    }
+
+    #[test]
+    #[should_panic = "slices must have the same length"]
+    fn bf16_convert_slice_length_test() {
+        BF16::convert_slice(&[F32::ONE], &mut [BF16::ZERO; 2]);
+    }
+
+    #[test]
+    fn limits_test() {
+        assert_eq!(F32::from_float(f32::EPSILON), F32::EPSILON);
+        assert_eq!(F64::from_float(f64::EPSILON), F64::EPSILON);
+        assert_eq!(F16::from_bits(0x1400), F16::EPSILON);
+        assert_eq!(BF16::from_bits(0x3c00), BF16::EPSILON);
+        assert_eq!(F64::ONE.next_up(), F64::ONE + F64::EPSILON);
+        assert_eq!(F128::from_bits(0x3f8f << 112), F128::EPSILON);
+
+        let f32_limits =
+            (f32::DIGITS, f32::MIN_EXP, f32::MAX_EXP, f32::MIN_10_EXP, f32::MAX_10_EXP);
+        assert_eq!(
+            f32_limits,
+            (F32::DIGITS, F32::MIN_EXP, F32::MAX_EXP, F32::MIN_10_EXP, F32::MAX_10_EXP)
+        );
+        let f64_limits =
+            (f64::DIGITS, f64::MIN_EXP, f64::MAX_EXP, f64::MIN_10_EXP, f64::MAX_10_EXP);
+        assert_eq!(
+            f64_limits,
+            (F64::DIGITS, F64::MIN_EXP, F64::MAX_EXP, F64::MIN_10_EXP, F64::MAX_10_EXP)
+        );
+        assert_eq!(
+            (3, -13, 16, -4, 4),
+            (F16::DIGITS, F16::MIN_EXP, F16::MAX_EXP, F16::MIN_10_EXP, F16::MAX_10_EXP)
+        );
+        assert_eq!(
+            (2, -125, 128, -37, 38),
+            (BF16::DIGITS, BF16::MIN_EXP, BF16::MAX_EXP, BF16::MIN_10_EXP, BF16::MAX_10_EXP)
+        );
+        assert_eq!(
+            (33, -16381, 16384, -4931, 4932),
+            (F128::DIGITS, F128::MIN_EXP, F128::MAX_EXP, F128::MIN_10_EXP, F128::MAX_10_EXP)
+        );
+        assert_eq!(2, F16::RADIX);
+    }
+
+    #[test]
+    fn with_flags_native_test() {
+        let mode = RoundingMode::TiesToEven;
+        let specials = [0.0, -0.0, 1.0, -1.5, f32::MAX, f32::MIN_POSITIVE, 1e-45, f32::INFINITY];
+        let values: std::vec::Vec<f32> = specials
+            .into_iter()
+            .chain((0..300u32).map(|i| f32::from_bits(i.wrapping_mul(0x9e3779b9))))
+            .collect();
+        let same = |expected: f32, (actual, _): (F32, StatusFlags)| {
+            assert_eq!(expected.is_nan(), actual.is_nan());
+            if !expected.is_nan() {
+                assert_eq!(expected.to_bits(), actual.to_bits());
+            }
+        };
+        for &a in &values {
+            let x = F32::from_float(a);
+            same(a.sqrt(), x.sqrt_with_flags(mode));
+            for &b in &values {
+                let y = F32::from_float(b);
+                same(a + b, x.add_with_flags(y, mode));
+                same(a - b, x.sub_with_flags(y, mode));
+                same(a * b, x.mul_with_flags(y, mode));
+                same(a / b, x.div_with_flags(y, mode));
+            }
+        }
+    }
+
+    #[test]
+    fn with_flags_modes_test() {
+        use RoundingMode::*;
+
+        // Values with nearby exponents, so that their exact sums fit a ratio.
+        let sample = |i: u32| {
+            let h = i.wrapping_mul(0x9e3779b9);
+            F32::from_bits(h & 0x807f_ffff | (40 + (h >> 24) % 60) << 23)
+        };
+        let exact_sum = |x: F32, y: F32| {
+            let ((ma, ea), (mb, eb)) = (x.to_ratio().unwrap(), y.to_ratio().unwrap());
+            let e = ea.min(eb);
+            ((ma << (ea - e)) + (mb << (eb - e)), e)
+        };
+        let exact_product = |x: F32, y: F32| {
+            let ((ma, ea), (mb, eb)) = (x.to_ratio().unwrap(), y.to_ratio().unwrap());
+            (ma * mb, ea + eb)
+        };
+        for i in 0..200 {
+            for j in 0..200 {
+                let (x, y) = (sample(i), sample(j + 1000));
+                for (op, exact) in [
+                    (F32::add_with_flags as fn(&F32, F32, RoundingMode) -> _, exact_sum(x, y)),
+                    (F32::mul_with_flags, exact_product(x, y)),
+                ] {
+                    let (m, e) = exact;
+                    let down = F32::from_ratio_rounded(m, e, TowardNegative);
+                    let up = F32::from_ratio_rounded(m, e, TowardPositive);
+                    let tiny = F32::from_ratio_rounded(m, e, TowardZero).abs() < F32::MIN_POSITIVE;
+                    let mut expected = StatusFlags::NONE;
+                    if down != up {
+                        expected |= StatusFlags::INEXACT;
+                        if tiny {
+                            expected |= StatusFlags::UNDERFLOW;
+                        }
+                    }
+                    for mode in [TiesToEven, TiesToAway, TowardZero, TowardPositive, TowardNegative]
+                    {
+                        let (result, flags) = op(&x, y, mode);
+                        assert_eq!(F32::from_ratio_rounded(m, e, mode), result, "{x} {y} {mode:?}");
+                        assert_eq!(expected, flags, "{x} {y} {mode:?}");
+                    }
+                }
+
+                let (down, flags) = x.div_with_flags(y, TowardNegative);
+                let (up, _) = x.div_with_flags(y, TowardPositive);
+                let (near, _) = x.div_with_flags(y, TiesToEven);
+                assert_eq!(flags.contains(StatusFlags::INEXACT), down != up);
+                assert!(down == up || down.next_up() == up);
+                assert!(near == down || near == up);
+            }
+        }
+    }
+
+    #[test]
+    fn with_flags_exceptions_test() {
+        use RoundingMode::*;
+
+        let overflow = StatusFlags::OVERFLOW | StatusFlags::INEXACT;
+        assert_eq!((F32::INFINITY, overflow), F32::MAX.add_with_flags(F32::MAX, TiesToEven));
+        assert_eq!((F32::MAX, overflow), F32::MAX.mul_with_flags(F32::MAX, TowardZero));
+        assert_eq!((F16::MIN, overflow), F16::MIN.mul_with_flags(F16::MAX, TowardPositive));
+        let two = F64::from_float(2.0);
+        let underflow = StatusFlags::UNDERFLOW | StatusFlags::INEXACT;
+        let (tiny, flags) = F64::MIN_POSITIVE_SUBNORMAL.div_with_flags(two, TiesToEven);
+        assert_eq!((F64::ZERO, underflow), (tiny, flags));
+        let (half, flags) = F64::MIN_POSITIVE.div_with_flags(two, TiesToEven);
+        assert_eq!((F64::from_bits(1 << 51), StatusFlags::NONE), (half, flags));
+
+        let (nan, flags) = F64::INFINITY.sub_with_flags(F64::INFINITY, TiesToEven);
+        assert!(nan.is_nan() && flags == StatusFlags::INVALID);
+        assert_eq!(StatusFlags::INVALID, F64::ZERO.div_with_flags(F64::ZERO, TiesToEven).1);
+        assert_eq!(StatusFlags::INVALID, F64::INFINITY.mul_with_flags(F64::ZERO, TiesToEven).1);
+        assert_eq!(StatusFlags::INVALID, F64::NEG_ONE.sqrt_with_flags(TiesToEven).1);
+        let (nan, flags) = F64::ONE.add_with_flags(F64::SNAN, TiesToEven);
+        assert_eq!((F64::SNAN.to_bits() | 1 << 51, StatusFlags::INVALID), (nan.to_bits(), flags));
+        let (nan, flags) = F64::NEG_QNAN.mul_with_flags(F64::SNAN, TiesToEven);
+        assert_eq!((F64::NEG_QNAN.to_bits(), StatusFlags::INVALID), (nan.to_bits(), flags));
+        assert_eq!(StatusFlags::NONE, F64::QNAN.sub_with_flags(F64::ONE, TiesToEven).1);
+        assert_eq!(
+            (F64::NEG_INFINITY, StatusFlags::DIV_BY_ZERO),
+            F64::NEG_ONE.div_with_flags(F64::ZERO, TiesToEven)
+        );
+        assert_eq!(
+            (F64::ZERO, StatusFlags::NONE),
+            F64::ONE.div_with_flags(F64::INFINITY, TowardZero)
+        );
+
+        let one = F64::ONE;
+        assert_eq!(F64::ZERO.to_bits(), one.sub_with_flags(one, TowardPositive).0.to_bits());
+        assert_eq!(F64::NEG_ZERO.to_bits(), one.sub_with_flags(one, TowardNegative).0.to_bits());
+        let neg_zero = F64::NEG_ZERO;
+        assert_eq!(neg_zero.to_bits(), neg_zero.add_with_flags(neg_zero, TiesToEven).0.to_bits());
+        assert_eq!(neg_zero.to_bits(), neg_zero.sqrt_with_flags(TiesToEven).0.to_bits());
+
+        let ulp = F128::from_ratio(1, -112);
+        assert_eq!(
+            (F128::ONE.next_up(), StatusFlags::NONE),
+            F128::ONE.add_with_flags(ulp, TiesToEven)
+        );
+        let half_ulp = F128::from_ratio(1, -113);
+        let (sum, flags) = F128::ONE.add_with_flags(half_ulp, TiesToEven);
+        assert_eq!((F128::ONE, StatusFlags::INEXACT), (sum, flags));
+        assert_eq!(F128::ONE.next_up(), F128::ONE.add_with_flags(half_ulp, TiesToAway).0);
+        let (diff, flags) = F128::ONE.sub_with_flags(half_ulp, TiesToEven);
+        assert_eq!((F128::ONE.next_down(), StatusFlags::NONE), (diff, flags));
+        let two = F128::from_ratio(2, 0);
+        let (root, flags) = two.sqrt_with_flags(TiesToEven);
+        assert_eq!((consts::f128::SQRT_2, StatusFlags::INEXACT), (root, flags));
+        assert_eq!(
+            (F128::from_ratio(3, 0), StatusFlags::NONE),
+            F128::from_ratio(9, 0).sqrt_with_flags(TiesToEven)
+        );
+    }
+
+    #[test]
+    fn total_cmp_abs_test() {
+        use core::cmp::Ordering::*;
+
+        assert_eq!(Equal, F64::NEG_ZERO.total_cmp_abs(F64::ZERO));
+        assert_eq!(Less, F64::ONE.total_cmp_abs(F64::from_float(-2.0)));
+        assert_eq!(Greater, F32::NEG_INFINITY.total_cmp_abs(F32::MAX));
+        assert_eq!(Greater, BF16::NEG_SNAN.total_cmp_abs(BF16::INFINITY));
+        assert_eq!(Less, F16::SNAN.total_cmp_abs(F16::NEG_QNAN));
+        assert_eq!(Equal, F128::QNAN.total_cmp_abs(F128::NEG_QNAN));
+        for x in F16::all_values() {
+            assert_eq!(x.abs().total_cmp(F16::ONE), x.total_cmp_abs(F16::NEG_ONE));
+        }
+    }
+
+    #[test]
+    fn sign_test() {
+        assert_eq!(Sign::Positive, F64::ZERO.sign());
+        assert_eq!(Sign::Negative, F16::NEG_QNAN.sign());
+        assert_eq!(Sign::Positive, BF16::QNAN.sign());
+        assert_eq!(F128::NEG_SNAN, F128::SNAN.with_sign(Sign::Negative));
+        assert_eq!(F32::ONE, F32::NEG_ONE.with_sign(Sign::Positive));
+        assert_eq!(F32::ONE, F32::ONE.with_sign(F32::ONE.sign()));
+        assert_eq!(Sign::Negative, Sign::from(true));
+        assert!(!bool::from(Sign::Positive));
+        assert_eq!(Sign::Negative, -Sign::default());
+        assert!(Sign::Negative.is_negative() && Sign::Positive.is_positive());
+    }
+
+    #[test]
+    fn copysign_from_test() {
+        assert_eq!(F64::NEG_ONE, F64::ONE.copysign_from(-0.0f64));
+        assert_eq!(F32::ONE, F32::NEG_ONE.copysign_from(f32::NAN));
+        assert_eq!(BF16::NEG_ZERO.to_bits(), BF16::ZERO.copysign_from(F128::NEG_QNAN).to_bits());
+        assert_eq!(F16::MIN, F16::MAX.copysign_from(true));
+        assert_eq!(F128::ONE, F128::NEG_ONE.copysign_from(Sign::Positive));
+    }
+
+    #[test]
+    fn neg_operator_test() {
+        assert_eq!(BF16::NEG_ONE, -BF16::ONE);
+        assert_eq!(F16::NEG_ZERO.to_bits(), (-F16::ZERO).to_bits());
+        assert_eq!(F32::INFINITY, -F32::NEG_INFINITY);
+        assert_eq!(F64::from_float(-0.5), -F64::from_float(0.5));
+        assert_eq!(F128::NEG_SNAN.to_bits(), (-F128::SNAN).to_bits());
+        let x = F128::from_ratio(3, -1);
+        assert_eq!(x, -(-x));
+    }
+
+    #[test]
+    fn power_of_two_test() {
+        assert_eq!(Some(0), F64::ONE.checked_log2());
+        assert_eq!(Some(-1074), F64::MIN_POSITIVE_SUBNORMAL.checked_log2());
+        assert_eq!(Some(-1023), F64::from_bits(1 << 51).checked_log2());
+        assert_eq!(Some(127), F32::from_float(2f32.powi(127)).checked_log2());
+        assert_eq!(Some(-133), BF16::MIN_POSITIVE_SUBNORMAL.checked_log2());
+        assert_eq!(Some(-16494), F128::MIN_POSITIVE_SUBNORMAL.checked_log2());
+        assert_eq!(Some(F16::MIN_EXP - 1), F16::MIN_POSITIVE.checked_log2());
+        for x in [F64::ZERO, F64::NEG_ONE, F64::INFINITY, F64::QNAN, F64::MAX, F64::MAX_SUBNORMAL] {
+            assert_eq!(None, x.checked_log2(), "{x}");
+            assert!(!x.is_power_of_two());
+        }
+        for x in F16::all_values() {
+            let exact = x.checked_log2().map(|k| F16::from_ratio(1, k));
+            assert_eq!(x.is_power_of_two(), exact.is_some());
+            if let Some(exact) = exact {
+                assert_eq!(x.to_bits(), exact.to_bits());
+            }
+        }
+        assert_eq!(40, F16::all_values().filter(|x| x.is_power_of_two()).count());
+    }
+
+    #[test]
+    fn safe_integer_test() {
+        assert_eq!(1 << 53, F64::MAX_SAFE_INTEGER);
+        assert_eq!(1 << 24, F32::MAX_SAFE_INTEGER);
+        assert_eq!(256, BF16::MAX_SAFE_INTEGER);
+        assert_eq!(1 << 113, F128::MAX_SAFE_INTEGER);
+        for n in [0, 1, -1, 2047, -2048, 65504, -65504] {
+            assert!(F16::represents_integer_exactly(n), "{n}");
+        }
+        for n in [2049, -2049, 65505, 65536, i128::MAX] {
+            assert!(!F16::represents_integer_exactly(n), "{n}");
+        }
+        let safe = F64::MAX_SAFE_INTEGER;
+        assert!(F64::represents_integer_exactly(safe) && F64::represents_integer_exactly(-safe));
+        assert!(!F64::represents_integer_exactly(safe + 1));
+        assert!(F64::represents_integer_exactly(safe + 2));
+        assert!(F32::represents_integer_exactly(i128::MIN));
+        assert!(!BF16::represents_integer_exactly(i128::MAX));
+        assert!(F128::represents_integer_exactly(i64::MAX as i128));
+        for n in (-70000..70000).step_by(7) {
+            let exact = BF16::from_ratio(n, 0).to_ratio().map(|(m, e)| m << e) == Some(n);
+            assert_eq!(exact, BF16::represents_integer_exactly(n), "{n}");
+        }
+    }
+
+    #[test]
+    fn subnormal_limits_test() {
+        assert_eq!(f32::from_bits(1), F32::MIN_POSITIVE_SUBNORMAL.to_float());
+        assert_eq!(-f64::from_bits(1), F64::MAX_NEGATIVE_SUBNORMAL.to_float());
+        assert_eq!(F16::MIN_POSITIVE.next_down(), F16::MAX_SUBNORMAL);
+        assert_eq!(BF16::from_bits(0x807f), BF16::MIN_SUBNORMAL);
+        assert_eq!(F128::ZERO.next_up(), F128::MIN_POSITIVE_SUBNORMAL);
+        assert!(F128::MAX_SUBNORMAL.is_subnormal() && F128::MIN_SUBNORMAL.is_subnormal());
+        assert!(F64::MIN_SUBNORMAL < F64::MAX_NEGATIVE_SUBNORMAL);
+    }
+
+    #[test]
+    fn bytes_test() {
+        const LE: [u8; 2] = BF16::ONE.to_le_bytes();
+        assert_eq!([0x80, 0x3f], LE);
+        assert_eq!([0x3f, 0x80], BF16::ONE.to_be_bytes());
+        assert_eq!(BF16::ONE, BF16::from_le_bytes(LE));
+        assert_eq!(F16::ONE, F16::from_be_bytes([0x3c, 0x00]));
+        assert_eq!(1.0f32.to_le_bytes(), F32::ONE.to_le_bytes());
+        assert_eq!(F64::NEG_ONE, F64::from_ne_bytes((-1.0f64).to_ne_bytes()));
+
+        let bytes = F128::ONE.to_be_bytes();
+        assert_eq!([0x3f, 0xff, 0x00], [bytes[0], bytes[1], bytes[15]]);
+        assert_eq!(F128::ONE, F128::from_be_bytes(bytes));
+        let mut bytes = F128::MIN_POSITIVE.to_le_bytes();
+        assert_eq!(F128::MIN_POSITIVE, F128::from_le_bytes(bytes));
+        bytes.reverse();
+        assert_eq!(F128::MIN_POSITIVE, F128::from_be_bytes(bytes));
+        assert_eq!(F128::MIN_POSITIVE.to_ne_bytes(), F128::MIN_POSITIVE.bits.to_ne_bytes());
+    }
+
+    #[test]
+    fn sortable_bytes_test() {
+        const KEY: [u8; 2] = BF16::ONE.to_sortable_bytes();
+        assert_eq!([0xbf, 0x80], KEY);
+        assert_eq!([0x40, 0x7f], BF16::NEG_ONE.to_sortable_bytes());
+        assert_eq!([0x7f, 0xff], F16::NEG_ZERO.to_sortable_bytes());
+        assert_eq!([0x80, 0x00], F16::ZERO.to_sortable_bytes());
+
+        let values = [
+            F64::NEG_QNAN,
+            F64::NEG_SNAN,
+            F64::NEG_INFINITY,
+            F64::MIN,
+            F64::NEG_ONE,
+            F64::from_bits(0x8000_0000_0000_0001),
+            F64::NEG_ZERO,
+            F64::ZERO,
+            F64::from_bits(1),
+            F64::ONE,
+            F64::MAX,
+            F64::INFINITY,
+            F64::SNAN,
+            F64::QNAN,
+        ];
+        for pair in values.windows(2) {
+            assert!(pair[0].total_cmp(pair[1]).is_lt());
+            assert!(pair[0].to_sortable_bytes() < pair[1].to_sortable_bytes());
+        }
+        for x in values {
+            assert_eq!(x.bits, F64::from_sortable_bytes(x.to_sortable_bytes()).bits);
+        }
+
+        let x = F128::NEG_ONE.next_up();
+        assert_eq!(x, F128::from_sortable_bytes(x.to_sortable_bytes()));
+        assert!(x.to_sortable_bytes() < F128::ZERO.to_sortable_bytes());
+    }
+
+    #[test]
+    fn memcomparable_bytes_test() {
+        const KEY: [u8; 8] = F64::ONE.to_memcomparable_bytes();
+        assert_eq!([0xbf, 0xf0, 0, 0, 0, 0, 0, 0], KEY);
+        assert_eq!(
+            [0x40, 0x0f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+            F64::NEG_ONE.to_memcomparable_bytes()
+        );
+        assert_eq!([0x80, 0, 0, 0, 0, 0, 0, 0], F64::ZERO.to_memcomparable_bytes());
+        assert_eq!([0x80, 0, 0, 0, 0, 0, 0, 0], F64::NEG_ZERO.to_memcomparable_bytes());
+        assert_eq!([0x80, 0, 0, 0], F32::NEG_ZERO.to_memcomparable_bytes());
+
+        assert_eq!(F64::ONE, F64::from_memcomparable_bytes(KEY));
+        assert!(
+            F64::from_memcomparable_bytes(F64::NEG_ZERO.to_memcomparable_bytes())
+                .is_sign_positive()
+        );
+        let x = F64::from_float(-2.5);
+        assert_eq!(x.to_sortable_bytes(), x.to_memcomparable_bytes());
+        assert_eq!(x, F64::from_memcomparable_bytes(x.to_memcomparable_bytes()));
+        assert!(x.to_memcomparable_bytes() < F64::NEG_ZERO.to_memcomparable_bytes());
+
+        // A positive NaN keeps the order of `to_sortable_bytes` rather than having every bit
+        // flipped, which would give `80 07 ff ff ff ff ff ff`.
+        let nan = F64::from_float(f64::NAN).to_memcomparable_bytes();
+        assert_eq!([0xff, 0xf8, 0, 0, 0, 0, 0, 0], nan);
+        assert!(F64::INFINITY.to_memcomparable_bytes() < nan);
+        assert_eq!(f64::NAN.to_bits(), F64::from_memcomparable_bytes(nan).to_bits());
+        assert_eq!(
+            [0, 7, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe],
+            F64::NEG_QNAN.to_memcomparable_bytes()
+        );
+    }
+
+    #[test]
+    fn slice_casts_test() {
+        let mut floats = [1.0f32, -0.5, f32::INFINITY];
+        assert_eq!([F32::ONE, F32::from_float(-0.5), F32::INFINITY], *F32::from_array(&floats));
+        assert_eq!(F32::INFINITY, F32::from_slice(&floats[1..])[1]);
+        F32::from_mut_slice(&mut floats)[0] = F32::NEG_ONE;
+        F32::from_mut_array(&mut floats)[2] = F32::ZERO;
+        assert_eq!([-1.0, -0.5, 0.0], floats);
+
+        let mut values = [F64::ONE, F64::MAX];
+        assert_eq!([1.0, f64::MAX], *F64::as_float_array(&values));
+        assert_eq!(&[f64::MAX], F64::as_float_slice(&values[1..]));
+        F64::as_float_mut_slice(&mut values)[0] *= 3.0;
+        F64::as_float_mut_array(&mut values)[1] = -2.0;
+        assert_eq!([F64::from_float(3.0), F64::from_float(-2.0)], values);
+
+        const EMPTY: &[F64] = F64::from_slice(&[]);
+        assert!(EMPTY.is_empty());
+    }
+
+    #[test]
+    fn bits_fmt_test() {
+        let x = F32::from_float(-1.5);
+        assert_eq!("0b10111111110000000000000000000000", format!("{x:#034b}"));
+        assert_eq!("bfc00000", format!("{x:x}"));
+        assert_eq!("0xBFC00000", format!("{x:#X}"));
+        assert_eq!("27760000000", format!("{x:o}"));
+        assert_eq!("0x0001", format!("{:#06x}", BF16::from_bits(1)));
+        assert_eq!("7fff8000000000000000000000000001", format!("{:x}", F128::QNAN));
+    }
+
+    #[test]
+    fn bit_string_test() {
+        let mut buf = [0; F32::BIT_STRING_LEN];
+        let five = F32::from_float(5.0);
+        assert_eq!("0 10000001 01000000000000000000000", five.to_bit_string(&mut buf));
+        assert_eq!(five, F32::from_bit_string("0 10000001 01000000000000000000000").unwrap());
+        assert_eq!(five, F32::from_bit_string("0100_0000_1010_0000_0000_0000_0000_0000").unwrap());
+
+        let mut buf = [0; 20];
+        assert_eq!("1 01111 1000000000", F16::from_bits(0xbe00).to_bit_string(&mut buf));
+        assert_eq!("0 11111111 1000000", BF16::from_bits(0x7fc0).to_bit_string(&mut buf));
+        for x in [F128::MAX, F128::NEG_ZERO, F128::from_bits(1)] {
+            let mut buf = [0; F128::BIT_STRING_LEN];
+            assert_eq!(x, F128::from_bit_string(x.to_bit_string(&mut buf)).unwrap());
+        }
+        assert!(F16::from_bit_string("").is_err());
+        assert!(F16::from_bit_string("0 01111 000000000").is_err());
+        assert!(F16::from_bit_string("0 01111 00000000000").is_err());
+        assert!(F16::from_bit_string("0 01111 000000000x").is_err());
+    }
+
+    #[test]
+    fn ratio_test() {
+        assert_eq!(Some((3, -1)), F64::from_float(1.5).to_ratio());
+        assert_eq!(Some((-0xccccccccccccd, -55)), F64::from_float(-0.1).to_ratio());
+        assert_eq!(Some((1, -16494)), F128::from_bits(1).to_ratio());
+        assert_eq!(Some(((1 << 113) - 1, 16271)), F128::MAX.to_ratio());
+        assert_eq!(Some((0, 0)), F16::NEG_ZERO.to_ratio());
+        assert_eq!(None, BF16::INFINITY.to_ratio());
+        assert_eq!(None, F32::QNAN.to_ratio());
+        for x in [F128::MAX, F128::from_bits(1), F128::NEG_ONE, F128::from_bits(0x3ffb9999 << 96)] {
+            let (mantissa, exponent) = x.to_ratio().unwrap();
+            assert_eq!(x, F128::from_ratio(mantissa, exponent));
+        }
+
+        // 2^24 + 1 is halfway between two F32 values, and ties go to the even one.
+        assert_eq!(F32::from_float(16777216.0), F32::from_ratio(0x1000001, 0));
+        assert_eq!(F32::from_float(16777220.0), F32::from_ratio(0x1000003, 0));
+        assert_eq!(
+            F32::from_float(16777218.0),
+            F32::from_ratio_rounded(0x1000001, 0, RoundingMode::TowardPositive)
+        );
+        assert_eq!(F64::from_bits(1), F64::from_ratio(3, -1076));
+        assert_eq!(F64::NEG_ZERO, F64::from_ratio(-1, -1076));
+        assert_eq!(F16::INFINITY, F16::from_ratio(1, i32::MAX));
+        assert_eq!(F16::ZERO, F16::from_ratio(i128::MAX, i32::MIN));
+        assert_eq!(F128::ZERO, F128::from_ratio(0, 5));
+    }
+
+    #[test]
+    fn debug_test() {
+        assert_eq!("F16 { bits: 15360 }", format!("{:?}", F16::ONE));
+        let expected = "\
+BF16 {
+    bits: 0x8001,
+    sign: 1,
+    exponent: 0x0 (0),
+    mantissa: 0x1,
+    class: Subnormal,
+    value: -0.0000000000000000000000000000000000000001,
+}";
+        assert_eq!(expected, format!("{:#?}", BF16::from_bits(0x8001)));
+        let expected = "\
+F64 {
+    bits: 0x7ff8000000000000,
+    sign: 0,
+    exponent: 0x7ff (2047),
+    mantissa: 0x8000000000000,
+    class: Nan,
+    value: NaN,
+}";
+        assert_eq!(expected, format!("{:#?}", F64::from_float(f64::NAN)));
+    }
+
+    #[test]
+    fn min_max_test() {
+        const P_ZERO: F32 = F32::ZERO;
+        const N_ZERO: F32 = F32::NEG_ZERO;
+        const ONE: F32 = F32::ONE;
+        const SNAN: F32 = F32::SNAN;
+        const QNAN: F32 = F32::from_bits(0x7fc00001);
+
+        type Row = (F32, F32, F32, F32, F32, F32);
+        const ROWS: [Row; 5] = [
+            (N_ZERO, P_ZERO, N_ZERO, P_ZERO, N_ZERO, P_ZERO),
+            (P_ZERO, N_ZERO, N_ZERO, P_ZERO, N_ZERO, P_ZERO),
+            (ONE, N_ZERO, N_ZERO, ONE, N_ZERO, ONE),
+            (ONE, SNAN, QNAN, QNAN, ONE, ONE),
+            (SNAN, SNAN, QNAN, QNAN, QNAN, QNAN),
+        ];
+        for (x, y, min, max, min_num, max_num) in ROWS {
+            assert_eq!(min, x.minimum(y));
+            assert_eq!(max, x.maximum(y));
+            assert_eq!(min_num, x.minimum_number(y));
+            assert_eq!(max_num, x.maximum_number(y));
+            assert_eq!(min_num, y.minimum_number(x));
+            assert_eq!(max_num, y.maximum_number(x));
+        }
+    }
+
+    #[test]
+    fn min_max_magnitude_test() {
+        const N_ONE: F64 = F64::NEG_ONE;
+        const P_ONE: F64 = F64::ONE;
+        const N_TWO: F64 = F64::from_float(-2.0);
+        const SNAN: F64 = F64::SNAN;
+        const QNAN: F64 = F64::from_bits(0x7ff8000000000001);
+
+        type Row = (F64, F64, F64, F64, F64, F64);
+        const ROWS: [Row; 5] = [
+            (N_TWO, P_ONE, P_ONE, N_TWO, P_ONE, N_TWO),
+            (N_ONE, P_ONE, N_ONE, P_ONE, N_ONE, P_ONE),
+            (F64::NEG_ZERO, F64::ZERO, F64::NEG_ZERO, F64::ZERO, F64::NEG_ZERO, F64::ZERO),
+            (N_TWO, SNAN, QNAN, QNAN, N_TWO, N_TWO),
+            (SNAN, SNAN, QNAN, QNAN, QNAN, QNAN),
+        ];
+        for (x, y, min, max, min_num, max_num) in ROWS {
+            assert_eq!(min, x.minimum_magnitude(y));
+            assert_eq!(max, x.maximum_magnitude(y));
+            assert_eq!(min, y.minimum_magnitude(x));
+            assert_eq!(max, y.maximum_magnitude(x));
+            assert_eq!(min_num, x.minimum_magnitude_number(y));
+            assert_eq!(max_num, x.maximum_magnitude_number(y));
+            assert_eq!(min_num, y.minimum_magnitude_number(x));
+            assert_eq!(max_num, y.maximum_magnitude_number(x));
+        }
+    }
+
+    #[test]
+    fn checked_clamp_test() {
+        let x = F32::from_float(1.5);
+        assert_eq!(Some(F32::ONE), x.checked_clamp(F32::NEG_ONE, F32::ONE));
+        assert_eq!(Some(x), x.checked_clamp(F32::NEG_ONE, F32::INFINITY));
+        assert_eq!(Some(F32::NEG_ZERO), F32::NEG_ONE.checked_clamp(F32::NEG_ZERO, F32::ZERO));
+        assert!(F32::NAN.checked_clamp(F32::NEG_ONE, F32::ONE).unwrap().is_nan());
+        assert_eq!(None, x.checked_clamp(F32::ONE, F32::NEG_ONE));
+        assert_eq!(None, x.checked_clamp(F32::ZERO, F32::NEG_ZERO));
+        assert_eq!(None, x.checked_clamp(F32::NAN, F32::ONE));
+        assert_eq!(None, x.checked_clamp(F32::ONE, F32::NAN));
+    }
+
+    #[test]
+    fn native_ops_test() {
+        let x = F64::from_float(0.1);
+        let y = F64::from_float(0.2);
+        assert_eq!(F64::from_float(0.1 + 0.2), x + y);
+        assert_eq!(F64::from_float(0.1 - 0.2), x - y);
+        assert_eq!(F64::from_float(0.1 * 0.2), x * y);
+        assert_eq!(F64::from_float(0.1 / 0.2), x / y);
+        assert_eq!(F64::from_float(0.1 % 0.2), x % y);
+        assert_eq!(F64::NEG_ZERO, -F64::ZERO);
+
+        let x = F32::from_float(7.5);
+        let y = F32::from_float(2.0);
+        assert_eq!(F32::from_float(1.5), x % y);
+        assert_eq!(F32::from_float(-7.5), -x);
+        assert!((F32::INFINITY - F32::INFINITY).is_nan());
+    }
+
+    #[test]
+    fn native_assign_ops_test() {
+        let mut acc = F64::ZERO;
+        for _ in 0..10 {
+            acc += F64::from_float(0.1);
+        }
+        assert_eq!(0x3fefffffffffffff, acc.to_bits());
+        acc -= F64::ONE;
+        assert_eq!(F64::from_float(-f64::EPSILON / 2.0), acc);
+        acc *= F64::from_float(-2.0);
+        assert_eq!(F64::from_float(f64::EPSILON), acc);
+        acc /= F64::from_float(f64::EPSILON);
+        assert_eq!(F64::ONE, acc);
+        acc %= F64::ONE;
+        assert_eq!(F64::ZERO, acc);
+    }
+
+    #[cfg(any(feature = "libm", feature = "std"))]
+    #[test]
+    fn math_test() {
+        let two = F64::from_float(2.0);
+        assert_eq!(F64::from_float(core::f64::consts::SQRT_2), two.sqrt());
+        assert_eq!(F64::ONE, F64::ZERO.exp());
+        assert_eq!(F64::ZERO, F64::ONE.ln());
+        assert_eq!(F64::ONE, two.log2());
+        assert_eq!(F64::ZERO, F64::ZERO.sin());
+        assert_eq!(F64::ONE, F64::ZERO.cos());
+        assert_eq!(F64::NEG_ZERO, F64::NEG_ZERO.tan());
+        assert_eq!(F64::from_float(core::f64::consts::FRAC_PI_4), F64::ONE.atan2(F64::ONE));
+        assert_eq!(F64::from_float(5.0), F64::from_float(3.0).hypot(F64::from_float(4.0)));
+        assert_eq!(F64::from_float(8.0), two.powf(F64::from_float(3.0)));
+        assert!(F32::NEG_ONE.sqrt().is_nan());
+        assert_eq!(F32::from_float(3.0), F32::from_float(9.0).sqrt());
+    }
+
+    #[test]
+    fn f128_math_test() {
+        const TWO: F128 = F128::from_bits(0x40000000000000000000000000000000);
+        const THREE: F128 = F128::from_bits(0x40008000000000000000000000000000);
+        const TEN: F128 = F128::from_bits(0x40024000000000000000000000000000);
+        const HALF: F128 = F128::from_bits(0x3ffe0000000000000000000000000000);
+        const E: u128 = 0x40005bf0a8b1457695355fb8ac404e7a;
+        const LN_2: u128 = 0x3ffe62e42fefa39ef35793c7673007e6;
+        const LN_10: u128 = 0x400026bb1bbb5551582dd4adac5705a6;
+        const SQRT_2: u128 = 0x3fff6a09e667f3bcc908b2fb1366ea95;
+        const SQRT_3: u128 = 0x3fffbb67ae8584caa73b25742d7078b8;
+
+        assert_eq!(E, F128::ONE.exp().to_bits());
+        assert_eq!(LN_2, TWO.ln().to_bits());
+        assert_eq!(LN_2 | 1 << 127, HALF.ln().to_bits());
+        assert_eq!(LN_10, TEN.ln().to_bits());
+        assert_eq!(SQRT_2, TWO.sqrt().to_bits());
+        assert_eq!(SQRT_2, TWO.sqrt_rounded(RoundingMode::TowardNegative).to_bits());
+        assert_eq!(SQRT_2 + 1, TWO.sqrt_rounded(RoundingMode::TowardPositive).to_bits());
+        assert_eq!(
+            TWO,
+            F128::from_bits(0x40010000000000000000000000000000)
+                .sqrt_rounded(RoundingMode::TowardPositive)
+        );
+        assert_eq!(SQRT_3, THREE.pow(HALF).to_bits());
+
+        use RoundingMode::{TowardNegative as Down, TowardPositive as Up};
+        assert_eq!(E, F128::ONE.exp_rounded(Down).to_bits());
+        assert_eq!(E + 1, F128::ONE.exp_rounded(Up).to_bits());
+        assert_eq!(LN_2 - 1, TWO.ln_rounded(Down).to_bits());
+        assert_eq!(LN_2, TWO.ln_rounded(Up).to_bits());
+        assert_eq!((LN_2 - 1) | 1 << 127, HALF.ln_rounded(Up).to_bits());
+        assert_eq!(LN_2 | 1 << 127, HALF.ln_rounded(Down).to_bits());
+        assert_eq!(SQRT_3, THREE.pow_rounded(HALF, Down).to_bits());
+        assert_eq!(SQRT_3 + 1, THREE.pow_rounded(HALF, Up).to_bits());
+        for mode in [Down, Up] {
+            assert_eq!(F128::ONE, F128::ZERO.exp_rounded(mode));
+            assert_eq!(F128::ZERO, F128::ONE.ln_rounded(mode));
+            assert_eq!(
+                F128::from_bits(0x40020000000000000000000000000000),
+                TWO.pow_rounded(THREE, mode)
+            );
+        }
+        assert_eq!(
+            F128::MAX,
+            F128::from_bits(0x400d0000000000000000000000000000).exp_rounded(Down)
+        );
+        assert_eq!(
+            F128::from_bits(1),
+            F128::from_bits(0xc00d0000000000000000000000000000).exp_rounded(Up)
+        );
+        assert_eq!(F128::from_bits(0x40020000000000000000000000000000), TWO.pow(THREE));
+        assert_eq!(THREE, THREE.powi(2).sqrt());
+        assert_eq!(F128::ONE, F128::ZERO.exp());
+        assert_eq!(F128::ZERO, F128::ONE.ln());
+        assert_eq!(F128::INFINITY, F128::from_bits(0x400d0000000000000000000000000000).exp());
+        assert_eq!(F128::ZERO, F128::from_bits(0xc00d0000000000000000000000000000).exp());
+
+        assert!(F128::NEG_ONE.sqrt().is_nan());
+        assert!(F128::NEG_ONE.ln().is_nan());
+        assert_eq!(F128::NEG_ZERO, F128::NEG_ZERO.sqrt());
+        assert_eq!(F128::NEG_INFINITY, F128::ZERO.ln());
+        assert_eq!(F128::ONE, F128::NAN.pow(F128::ZERO));
+        assert_eq!(F128::ONE, F128::ONE.pow(F128::NAN));
+        assert_eq!(F128::ONE, F128::NEG_ONE.pow(F128::INFINITY));
+        assert!(F128::NEG_ONE.pow(HALF).is_nan());
+        assert_eq!(F128::NEG_ONE, F128::NEG_ONE.pow(THREE));
+        assert_eq!(F128::NEG_INFINITY, F128::NEG_ZERO.pow(THREE.neg()));
+        assert_eq!(F128::ZERO, HALF.pow(F128::INFINITY));
+        assert_eq!(F128::INFINITY, HALF.pow(F128::NEG_INFINITY));
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn json_schema_test() {
+        use schemars::generate::SchemaSettings;
+        use schemars::{JsonSchema, json_schema};
+
+        let bits = json_schema!({
+            "type": "object",
+            "properties": {
+                "bits": { "type": "integer", "format": "uint16", "minimum": 0, "maximum": 65535 },
+            },
+            "required": ["bits"],
+            "additionalProperties": false,
+        });
+        let mut generator = SchemaSettings::default().for_serialize().into_generator();
+        assert_eq!(bits, F16::json_schema(&mut generator));
+        let mut generator = SchemaSettings::default().for_deserialize().into_generator();
+        assert_eq!(bits, F16::json_schema(&mut generator));
+        assert_eq!("float_bits::F16", F16::schema_id());
+    }
+
+    #[cfg(feature = "zerocopy")]
+    #[test]
+    fn zerocopy_test() {
+        use zerocopy::{FromBytes, IntoBytes};
+
+        let words = [0x3f800000u32, 0xc0000000];
+        let floats = <[F32]>::ref_from_bytes(words.as_bytes()).unwrap();
+        assert_eq!([F32::ONE, F32::from_float(-2.0)], floats);
+        assert_eq!(words.as_bytes(), floats.as_bytes());
+        assert_eq!(Ok(F16::ONE), F16::read_from_bytes(&0x3c00u16.to_ne_bytes()));
+        assert_eq!(F128::ONE.bits.to_ne_bytes(), F128::ONE.as_bytes());
+        assert!(<[BF16]>::ref_from_bytes(&words.as_bytes()[..3]).is_err());
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bytemuck_test() {
+        let words = [0x3ff0000000000000u64, 0x8000000000000000];
+        let floats: &[F64] = bytemuck::cast_slice(&words);
+        assert_eq!([F64::ONE, F64::NEG_ZERO], floats);
+        assert_eq!(F16::ONE, bytemuck::cast(0x3c00u16));
+        assert_eq!(F128::ZERO, bytemuck::Zeroable::zeroed());
+        let bytes: &[u8] = bytemuck::bytes_of(&BF16::ONE);
+        assert_eq!(0x3f80u16.to_ne_bytes(), bytes);
+    }
+
+    #[cfg(feature = "ufmt")]
+    #[test]
+    fn ufmt_test() {
+        struct Buf(std::string::String);
+
+        impl ufmt::uWrite for Buf {
+            type Error = core::convert::Infallible;
+
+            fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+                self.0.push_str(s);
+                Ok(())
+            }
+        }
+
+        let mut buf = Buf(std::string::String::new());
+        ufmt::uwrite!(buf, "{} {} {:?}", F64::from_float(0.1), F16::NEG_INFINITY, F16::ONE)
+            .unwrap();
+        assert_eq!("0.1 -inf F16 { bits: 15360 }", buf.0);
+        let mut buf = Buf(std::string::String::new());
+        ufmt::uwrite!(buf, "{}", F128::from_bits(1)).unwrap();
+        assert_eq!(format!("{}", F128::from_bits(1)), buf.0);
+
+        // Formats no wider than 32 bits need about 1 KiB of stack, or 3 KiB without optimization.
+        let write_all = || {
+            let mut buf = Buf(std::string::String::new());
+            for bits in 0..=u16::MAX {
+                ufmt::uwrite!(buf, "{} {}", F16::from_bits(bits), BF16::from_bits(bits)).unwrap();
+            }
+            for bits in [1, 0x007fffff, 0x3dcccccd, 0x7f7fffff] {
+                buf.0.clear();
+                ufmt::uwrite!(buf, "{}", F32::from_bits(bits)).unwrap();
+                assert_eq!(format!("{}", F32::from_bits(bits)), buf.0);
+            }
+        };
+        let thread = std::thread::Builder::new().stack_size(16 * 1024).spawn(write_all).unwrap();
+        thread.join().unwrap();
+    }
+
+    #[cfg(feature = "num-traits")]
+    #[test]
+    fn num_traits_test() {
+        use num_traits::{Bounded, Num, One, Signed, Zero};
+
+        fn sum<T: Num + Copy>(xs: &[T]) -> T {
+            xs.iter().fold(T::zero(), |acc, &x| acc + x)
+        }
+
+        assert_eq!(
+            F64::from_float(6.0),
+            sum(&[F64::ONE, F64::from_float(2.0), F64::from_float(3.0)])
+        );
+        assert_eq!(F32::ONE, <F32 as One>::one());
+        assert!(<F64 as Zero>::is_zero(&F64::NEG_ZERO));
+        assert_eq!((F32::MIN, F32::MAX), (F32::min_value(), F32::max_value()));
+        assert_eq!(F64::from_float(0.1), F64::from_str_radix("0.1", 10).unwrap());
+        let err = F64::from_str_radix("ff", 16).unwrap_err();
+        assert_eq!((ParseErrorKind::InvalidSyntax, 0), (err.kind(), err.offset()));
+
+        assert_eq!(F64::ONE, Signed::abs(&F64::NEG_ONE));
+        assert_eq!(F64::ZERO, F64::ONE.abs_sub(&F64::from_float(2.0)));
+        assert_eq!(F64::ONE, F64::from_float(2.0).abs_sub(&F64::ONE));
+        assert!(F64::ONE.abs_sub(&F64::QNAN).is_nan());
+        assert!(F64::NEG_QNAN.abs_sub(&F64::ONE).is_nan());
+        assert!(F32::QNAN.abs_sub(&F32::NEG_INFINITY).is_nan());
+        assert_eq!(F64::ZERO, F64::NEG_ZERO.abs_sub(&F64::ZERO));
+        assert_eq!(F32::NEG_ONE, Signed::signum(&F32::NEG_ZERO));
+        assert!(F64::ZERO.is_positive() && F64::NEG_INFINITY.is_negative());
+        assert!(!F64::NEG_ZERO.is_positive());
+    }
+
+    #[cfg(all(feature = "num-traits", any(feature = "libm", feature = "std")))]
+    #[test]
+    fn num_float_test() {
+        use num_traits::float::FloatCore;
+        use num_traits::{Float, NumCast, ToPrimitive};
+
+        fn hypot<T: Float>(x: T, y: T) -> T {
+            (x * x + y * y).sqrt()
+        }
+
+        fn half_ulp<T: FloatCore>(x: T) -> T {
+            x * T::epsilon() / (T::one() + T::one())
+        }
+
+        assert_eq!(F64::from_float(5.0), hypot(F64::from_float(3.0), F64::from_float(4.0)));
+        assert_eq!(F32::from_float(5.0), hypot(F32::from_float(3.0), F32::from_float(4.0)));
+        assert_eq!(F64::from_float(f64::EPSILON / 2.0), half_ulp(F64::ONE));
+        assert_eq!(F32::from_float(2.0f32.ln()), Float::ln(F32::from_float(2.0)));
+        assert_eq!(F64::from_bits(0x7ff8000000000000), <F64 as Float>::nan());
+        assert_eq!((1 << 52, -52, 1), Float::integer_decode(F64::ONE));
+        assert_eq!(F32::from_float(9.0), FloatCore::powi(F32::from_float(3.0), 2));
+        assert_eq!(
+            F64::from_float(1.5),
+            <F64 as Float>::clamp(F64::from_float(7.0), F64::ZERO, F64::from_float(1.5))
+        );
+
+        assert_eq!(Some(-2), F64::from_float(-2.5).to_i32());
+        assert_eq!(None, F32::INFINITY.to_u64());
+        assert_eq!(Some(0.5), F32::from_float(0.5).to_f64());
+        assert_eq!(Some(F32::from_float(16777216.0)), <F32 as NumCast>::from(16777217u32));
+    }
+
+    #[cfg(feature = "num-traits")]
+    #[test]
+    fn num_primitive_test() {
+        use num_traits::{FromPrimitive, ToPrimitive};
+
+        assert_eq!(Some(-2), F16::from_ratio(-11, -2).to_i8());
+        assert_eq!(Some(-128), BF16::from_bits(0xc300).to_i8());
+        assert_eq!(None, BF16::from_bits(0xc302).to_i8());
+        assert_eq!(Some(0), BF16::from_bits(0xbf00).to_u8());
+        assert_eq!(None, BF16::NEG_ONE.to_u8());
+        assert_eq!(Some(65504), F16::MAX.to_u16());
+        assert_eq!(None, F16::QNAN.to_i64());
+        assert_eq!(None, F128::INFINITY.to_u128());
+        assert_eq!(Some(i128::MIN), F128::from_ratio(-1, 127).to_i128());
+        assert_eq!(None, F128::from_ratio(1, 127).to_i128());
+        assert_eq!(Some(u128::MAX << 15), F128::from_ratio((1 << 113) - 1, 15).to_u128());
+        assert_eq!(Some(65504.0), F16::MAX.to_f32());
+        assert_eq!(Some(0.1), F128::from_f64(0.1).unwrap().to_f64());
+
+        assert_eq!(Some(F16::from_bits(0x6800)), F16::from_u32(2049));
+        assert_eq!(Some(F16::from_bits(0x6802)), F16::from_u32(2051));
+        assert_eq!(Some(F16::INFINITY), F16::from_i32(65520));
+        assert_eq!(Some(BF16::from_bits(0x7f80)), BF16::from_u128(u128::MAX));
+        assert_eq!(Some(F128::from_ratio(1, 128)), F128::from_u128(u128::MAX));
+        for n in [1, 3, 5, 0x7f, 2049] {
+            assert_eq!(Some(F64::from_float(n as f64)), F64::from_u128(n));
+        }
+        assert_eq!(Some(F16::from_bits(0x6802)), F16::from_u128(2051));
+        let n = (1u128 << 113) + 1;
+        assert_eq!(Some(F128::from_ratio(1, 113)), F128::from_u128(n));
+        assert_eq!(Some(F128::from_ratio(1 << 112 | 1, 1)), F128::from_u128(n + 1));
+        assert_eq!(Some(F64::from_ratio(1, 128)), F64::from_u128(u128::MAX));
+        assert_eq!(Some(F128::from_ratio(-1, 127)), F128::from_i128(i128::MIN));
+        assert_eq!(Some(F32::from_float(0.1f64 as f32)), F32::from_f64(0.1));
+        assert_eq!(Some(BF16::from_bits(0x3dcd)), BF16::from_f32(0.1));
+    }
+
+    #[test]
+    fn fixed_test() {
+        use RoundingMode::*;
+
+        let x = F64::from_float(-1.5 / 32768.0);
+        assert_eq!(Some(-2), x.to_fixed::<15>(TiesToEven));
+        assert_eq!(Some(-2), x.to_fixed::<15>(TiesToAway));
+        assert_eq!(Some(-1), x.to_fixed::<15>(TowardZero));
+        assert_eq!(Some(-1), x.to_fixed::<15>(TowardPositive));
+        assert_eq!(Some(-2), x.to_fixed::<15>(TowardNegative));
+        assert_eq!(Some(0), F64::from_float(0.25).to_fixed::<1>(TiesToEven));
+        assert_eq!(Some(1), F64::from_float(0.25).to_fixed::<1>(TowardPositive));
+        assert_eq!(Some(0), F32::from_bits(1).to_fixed::<0>(TowardNegative));
+        assert_eq!(Some(-1), F32::from_bits(0x80000001).to_fixed::<0>(TowardNegative));
+        assert_eq!(Some(i128::MIN), F64::NEG_ONE.to_fixed::<127>(TiesToEven));
+        assert_eq!(None, F64::ONE.to_fixed::<127>(TowardZero));
+        assert_eq!(None, F16::QNAN.to_fixed::<15>(TiesToEven));
+
+        assert_eq!(F32::from_float(0.75), F32::from_fixed::<2>(3, TiesToEven));
+        assert_eq!(BF16::from_bits(0x4380), BF16::from_fixed::<0>(257, TiesToEven));
+        assert_eq!(BF16::from_bits(0x4381), BF16::from_fixed::<0>(257, TowardPositive));
+        assert_eq!(F16::from_bits(0x8001), F16::from_fixed::<40>(-1, TowardNegative));
+        assert_eq!(F16::NEG_ZERO, F16::from_fixed::<40>(-1, TiesToEven));
+        assert_eq!(F128::from_ratio(-1, 127), F128::from_fixed::<0>(i128::MIN, TiesToEven));
+    }
+
+    #[test]
+    fn ulp_distance_test() {
+        assert_eq!(Some(0), F64::ONE.ulp_distance(F64::ONE));
+        assert_eq!(Some(1), F64::ONE.ulp_distance(F64::from_bits(0x3ff0000000000001)));
+        assert_eq!(Some(0), F32::ZERO.ulp_distance(F32::NEG_ZERO));
+        assert_eq!(Some(2), F32::from_bits(1).ulp_distance(F32::from_bits(0x80000001)));
+        assert_eq!(Some(1), F16::MAX.ulp_distance(F16::INFINITY));
+        assert_eq!(Some(0xf800), F16::NEG_INFINITY.ulp_distance(F16::INFINITY));
+        assert_eq!(
+            Some(2 * F128::INFINITY.to_bits()),
+            F128::NEG_INFINITY.ulp_distance(F128::INFINITY)
+        );
+        assert_eq!(None, BF16::ONE.ulp_distance(BF16::QNAN));
+    }
+
+    #[cfg(feature = "approx")]
+    #[test]
+    fn approx_test() {
+        use approx::{
+            AbsDiffEq,
+            assert_abs_diff_eq,
+            assert_relative_eq,
+            assert_ulps_eq,
+            assert_ulps_ne,
+        };
+
+        let third = F64::ONE / F64::from_float(3.0);
+        assert_relative_eq!(F64::ONE, third + third + third);
+        assert_abs_diff_eq!(F32::ONE, F32::from_float(1.05), epsilon = F32::from_float(0.1));
+        assert_eq!(F64::from_float(f64::EPSILON), F64::default_epsilon());
+
+        let next = F64::from_bits(F64::ONE.to_bits() + 4);
+        assert_ulps_eq!(F64::ONE, next);
+        assert_ulps_ne!(F64::ONE, F64::from_bits(F64::ONE.to_bits() + 5));
+        assert_ulps_eq!(F32::ZERO, F32::NEG_ZERO);
+        assert_ulps_ne!(F32::from_bits(1), F32::from_bits(0x80000001), epsilon = F32::ZERO);
+        assert_ulps_ne!(F64::QNAN, F64::QNAN);
+        assert_relative_eq!(F64::INFINITY, F64::INFINITY);
+        assert!(!approx::relative_eq!(F64::INFINITY, F64::MAX));
+
+        let neg_nan = F64::NEG_QNAN;
+        assert!(!approx::abs_diff_eq!(F64::INFINITY, F64::INFINITY));
+        assert!(!approx::abs_diff_eq!(neg_nan, F64::ONE));
+        assert!(!approx::abs_diff_eq!(F32::ONE, F32::QNAN));
+        assert!(!approx::ulps_eq!(neg_nan, F64::ONE));
+        assert!(!approx::relative_eq!(neg_nan, neg_nan));
+        assert!(!approx::relative_eq!(F64::ONE, neg_nan));
+        assert_relative_eq!(F64::ZERO, F64::NEG_ZERO, epsilon = F64::ZERO);
+    }
+
+    #[cfg(feature = "ordered-float")]
+    #[test]
+    fn ordered_float_test() {
+        use ordered_float::{NotNan, OrderedFloat};
+
+        let x = F64::from_bits(0xfff8000000000001);
+        assert_eq!(x, F64::from(OrderedFloat::<f64>::from(x)));
+        assert_eq!(OrderedFloat(0.5f32), OrderedFloat::from(F32::from_float(0.5)));
+        assert_eq!(F32::NEG_ZERO, F32::from(NotNan::new(-0.0f32).unwrap()));
+        assert_eq!(NotNan::new(1.0), NotNan::try_from(F64::ONE));
+        assert!(NotNan::<f64>::try_from(F64::QNAN).is_err());
+
+        // The orderings disagree on zeros and NaNs.
+        assert!(F64::NEG_ZERO < F64::ZERO);
+        assert_eq!(OrderedFloat::<f64>::from(F64::NEG_ZERO), OrderedFloat::<f64>::from(F64::ZERO));
+        assert!(x < F64::NEG_INFINITY);
+        assert!(OrderedFloat::<f64>::from(x) > OrderedFloat::<f64>::from(F64::INFINITY));
+    }
 }
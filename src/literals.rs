@@ -0,0 +1,134 @@
+//! Macros that turn decimal literals into values at compile time.
+//!
+//! Each macro stringifies its literal and hands it to `parse_const` in a const item, so the
+//! parsing and rounding happen entirely at compile time and the expansion is a constant of the
+//! target type.  This works for every format, whether or not Rust supports it natively.
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __float_bits_literal {
+    ($ty:ident; - $lit:literal) => {{
+        const VALUE: $crate::$ty = $crate::$ty::parse_const(concat!("-", stringify!($lit)));
+        VALUE
+    }};
+    ($ty:ident; $lit:literal) => {{
+        const VALUE: $crate::$ty = $crate::$ty::parse_const(stringify!($lit));
+        VALUE
+    }};
+}
+
+/// Converts a decimal literal to a [`BF16`](crate::BF16) at compile time.
+///
+/// The literal is rounded to nearest with ties to even, exactly as by
+/// [`BF16::parse_const`](crate::BF16::parse_const).  A leading `-` is allowed, but type suffixes
+/// and `_` separators are not.
+///
+/// # Features
+///
+/// Requires crate feature `macros`.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::{BF16, bf16_bits};
+///
+/// assert_eq!(BF16::from_bits(0x3dcd), bf16_bits!(0.1));
+/// ```
+#[macro_export]
+macro_rules! bf16_bits {
+    ($($lit:tt)+) => {
+        $crate::__float_bits_literal!(BF16; $($lit)+)
+    };
+}
+
+/// Converts a decimal literal to an [`F16`](crate::F16) at compile time.
+///
+/// The literal is rounded to nearest with ties to even, exactly as by
+/// [`F16::parse_const`](crate::F16::parse_const).  A leading `-` is allowed, but type suffixes
+/// and `_` separators are not.
+///
+/// # Features
+///
+/// Requires crate feature `macros`.
+#[macro_export]
+macro_rules! f16_bits {
+    ($($lit:tt)+) => {
+        $crate::__float_bits_literal!(F16; $($lit)+)
+    };
+}
+
+/// Converts a decimal literal to an [`F32`](crate::F32) at compile time.
+///
+/// The literal is rounded to nearest with ties to even, exactly as by
+/// [`F32::parse_const`](crate::F32::parse_const).  A leading `-` is allowed, but type suffixes
+/// and `_` separators are not.
+///
+/// # Features
+///
+/// Requires crate feature `macros`.
+#[macro_export]
+macro_rules! f32_bits {
+    ($($lit:tt)+) => {
+        $crate::__float_bits_literal!(F32; $($lit)+)
+    };
+}
+
+/// Converts a decimal literal to an [`F64`](crate::F64) at compile time.
+///
+/// The literal is rounded to nearest with ties to even, exactly as by
+/// [`F64::parse_const`](crate::F64::parse_const).  A leading `-` is allowed, but type suffixes
+/// and `_` separators are not.
+///
+/// # Features
+///
+/// Requires crate feature `macros`.
+#[macro_export]
+macro_rules! f64_bits {
+    ($($lit:tt)+) => {
+        $crate::__float_bits_literal!(F64; $($lit)+)
+    };
+}
+
+/// Converts a decimal literal to an [`F128`](crate::F128) at compile time.
+///
+/// The literal is rounded to nearest with ties to even, exactly as by
+/// [`F128::parse_const`](crate::F128::parse_const).  A leading `-` is allowed, but type suffixes
+/// and `_` separators are not.  Digits beyond the precision of `f64` are honored, so long
+/// constants come out exact.
+///
+/// # Features
+///
+/// Requires crate feature `macros`.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::{F128, f128_bits};
+///
+/// const PI: F128 = f128_bits!(3.14159265358979323846264338327950288);
+/// assert_eq!(F128::from_bits(0x4000921fb54442d18469898cc51701b8), PI);
+/// ```
+#[macro_export]
+macro_rules! f128_bits {
+    ($($lit:tt)+) => {
+        $crate::__float_bits_literal!(F128; $($lit)+)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BF16, F16, F32, F64, F128};
+
+    #[test]
+    fn literal_test() {
+        const E: F128 = f128_bits!(2.718281828459045235360287471352662497757);
+        assert_eq!(F128::from_bits(0x40005bf0a8b1457695355fb8ac404e7a), E);
+        assert_eq!(F128::parse_const("-0.1"), f128_bits!(-0.1));
+        assert_eq!(BF16::from_bits(0xbf80), bf16_bits!(-1));
+        assert_eq!(F16::MAX, f16_bits!(65504.0));
+        assert_eq!(F16::INFINITY, f16_bits!(1e5));
+        assert_eq!(F32::from_float(0.1), f32_bits!(0.1));
+        assert_eq!(F64::from_float(-2.5e-300), f64_bits!(-2.5e-300));
+        assert_eq!(F64::NEG_ZERO, f64_bits!(-0.0));
+    }
+}
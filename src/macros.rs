@@ -1,9 +1,19 @@
 macro_rules! define_head {
     ( $( #[$meta:meta] )* $vis:vis struct $ty:ident; $u_ty:ty) => {
         $( #[$meta] )*
-        #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+        #[cfg_attr(feature = "serde", derive(::serde::Deserialize, ::serde::Serialize))]
         #[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
-        #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+        #[derive(Clone, Copy, Hash, PartialEq, Eq)]
+        #[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+        #[cfg_attr(
+            feature = "zerocopy",
+            derive(
+                zerocopy::FromBytes,
+                zerocopy::IntoBytes,
+                zerocopy::KnownLayout,
+                zerocopy::Immutable,
+            )
+        )]
         #[repr(transparent)]
         pub struct $ty {
             /// The raw bits representing this float value.
@@ -20,6 +30,549 @@ macro_rules! define_head {
             pub const fn to_bits(&self) -> $u_ty {
                 self.bits
             }
+
+            /// Returns the raw float bits as a byte array in little-endian order.
+            pub const fn to_le_bytes(&self) -> [u8; core::mem::size_of::<$u_ty>()] {
+                self.bits.to_le_bytes()
+            }
+
+            /// Returns the raw float bits as a byte array in big-endian order.
+            pub const fn to_be_bytes(&self) -> [u8; core::mem::size_of::<$u_ty>()] {
+                self.bits.to_be_bytes()
+            }
+
+            /// Returns the raw float bits as a byte array in native byte order.
+            ///
+            /// Portable code should use [`Self::to_le_bytes`] or [`Self::to_be_bytes`] instead.
+            pub const fn to_ne_bytes(&self) -> [u8; core::mem::size_of::<$u_ty>()] {
+                self.bits.to_ne_bytes()
+            }
+
+            /// Constructs a wrapped float from its raw bits as a byte array in little-endian order.
+            pub const fn from_le_bytes(bytes: [u8; core::mem::size_of::<$u_ty>()]) -> Self {
+                Self::from_bits(<$u_ty>::from_le_bytes(bytes))
+            }
+
+            /// Constructs a wrapped float from its raw bits as a byte array in big-endian order.
+            pub const fn from_be_bytes(bytes: [u8; core::mem::size_of::<$u_ty>()]) -> Self {
+                Self::from_bits(<$u_ty>::from_be_bytes(bytes))
+            }
+
+            /// Constructs a wrapped float from its raw bits as a byte array in native byte order.
+            ///
+            /// Portable code should use [`Self::from_le_bytes`] or [`Self::from_be_bytes`] instead.
+            pub const fn from_ne_bytes(bytes: [u8; core::mem::size_of::<$u_ty>()]) -> Self {
+                Self::from_bits(<$u_ty>::from_ne_bytes(bytes))
+            }
+        }
+
+        define_schemars!($ty; $u_ty);
+    };
+}
+
+// The schema describes the struct with a single `bits` field that serde derives.
+macro_rules! define_schemars {
+    ($ty:ident; $u_ty:ty) => {
+        #[cfg(feature = "schemars")]
+        impl ::schemars::JsonSchema for $ty {
+            fn schema_name() -> alloc::borrow::Cow<'static, str> {
+                stringify!($ty).into()
+            }
+
+            fn schema_id() -> alloc::borrow::Cow<'static, str> {
+                concat!("float_bits::", stringify!($ty)).into()
+            }
+
+            fn json_schema(generator: &mut ::schemars::SchemaGenerator) -> ::schemars::Schema {
+                ::schemars::json_schema!({
+                    "type": "object",
+                    "properties": { "bits": generator.subschema_for::<$u_ty>() },
+                    "required": ["bits"],
+                    "additionalProperties": false,
+                })
+            }
+        }
+    };
+}
+
+// Reinterprets slices and arrays of the Rust float in place.  Every cast is sound because the
+// wrapper is `repr(transparent)` over the bits, which have the size and alignment of the float,
+// and every bit pattern is valid for both.
+macro_rules! define_slice_casts {
+    ($ty:ident; $f_ty:ident) => {
+        impl $ty {
+            /// Reinterprets a slice of Rust floats as a slice of wrapped floats, without copying.
+            pub const fn from_slice(slice: &[$f_ty]) -> &[Self] {
+                // SAFETY: see `define_slice_casts`.
+                unsafe { &*(slice as *const [$f_ty] as *const [Self]) }
+            }
+
+            /// Reinterprets a mutable slice of Rust floats as a mutable slice of wrapped floats,
+            /// without copying.
+            pub const fn from_mut_slice(slice: &mut [$f_ty]) -> &mut [Self] {
+                // SAFETY: see `define_slice_casts`.
+                unsafe { &mut *(slice as *mut [$f_ty] as *mut [Self]) }
+            }
+
+            /// Reinterprets a slice of wrapped floats as a slice of Rust floats, without copying.
+            pub const fn as_float_slice(slice: &[Self]) -> &[$f_ty] {
+                // SAFETY: see `define_slice_casts`.
+                unsafe { &*(slice as *const [Self] as *const [$f_ty]) }
+            }
+
+            /// Reinterprets a mutable slice of wrapped floats as a mutable slice of Rust floats,
+            /// without copying.
+            pub const fn as_float_mut_slice(slice: &mut [Self]) -> &mut [$f_ty] {
+                // SAFETY: see `define_slice_casts`.
+                unsafe { &mut *(slice as *mut [Self] as *mut [$f_ty]) }
+            }
+
+            /// Reinterprets an array of Rust floats as an array of wrapped floats, without copying.
+            pub const fn from_array<const N: usize>(array: &[$f_ty; N]) -> &[Self; N] {
+                // SAFETY: see `define_slice_casts`.
+                unsafe { &*(array as *const [$f_ty; N] as *const [Self; N]) }
+            }
+
+            /// Reinterprets a mutable array of Rust floats as a mutable array of wrapped floats,
+            /// without copying.
+            pub const fn from_mut_array<const N: usize>(array: &mut [$f_ty; N]) -> &mut [Self; N] {
+                // SAFETY: see `define_slice_casts`.
+                unsafe { &mut *(array as *mut [$f_ty; N] as *mut [Self; N]) }
+            }
+
+            /// Reinterprets an array of wrapped floats as an array of Rust floats, without copying.
+            pub const fn as_float_array<const N: usize>(array: &[Self; N]) -> &[$f_ty; N] {
+                // SAFETY: see `define_slice_casts`.
+                unsafe { &*(array as *const [Self; N] as *const [$f_ty; N]) }
+            }
+
+            /// Reinterprets a mutable array of wrapped floats as a mutable array of Rust floats,
+            /// without copying.
+            pub const fn as_float_mut_array<const N: usize>(
+                array: &mut [Self; N],
+            ) -> &mut [$f_ty; N] {
+                // SAFETY: see `define_slice_casts`.
+                unsafe { &mut *(array as *mut [Self; N] as *mut [$f_ty; N]) }
+            }
+        }
+    };
+}
+
+macro_rules! define_native_ops {
+    ($ty:ident; $f_ty:ident; $( $op:ident :: $method:ident / $op_assign:ident :: $method_assign:ident ),*) => {
+        $(
+            impl core::ops::$op for $ty {
+                type Output = Self;
+
+                fn $method(self, rhs: Self) -> Self {
+                    Self::from_float(core::ops::$op::$method(self.to_float(), rhs.to_float()))
+                }
+            }
+
+            impl core::ops::$op_assign for $ty {
+                fn $method_assign(&mut self, rhs: Self) {
+                    *self = core::ops::$op::$method(*self, rhs);
+                }
+            }
+        )*
+    };
+    ($ty:ident; $f_ty:ident) => {
+        define_native_ops!(
+            $ty; $f_ty;
+            Add::add / AddAssign::add_assign,
+            Sub::sub / SubAssign::sub_assign,
+            Mul::mul / MulAssign::mul_assign,
+            Div::div / DivAssign::div_assign,
+            Rem::rem / RemAssign::rem_assign
+        );
+    };
+}
+
+macro_rules! define_num_traits {
+    ($ty:ident) => {
+        #[cfg(feature = "num-traits")]
+        impl num_traits::Zero for $ty {
+            fn zero() -> Self {
+                Self::ZERO
+            }
+
+            fn is_zero(&self) -> bool {
+                Self::is_zero(self)
+            }
+        }
+
+        #[cfg(feature = "num-traits")]
+        impl num_traits::One for $ty {
+            fn one() -> Self {
+                Self::ONE
+            }
+        }
+
+        /// Only radix 10 is supported, with the syntax of [`FromStr`](core::str::FromStr); any
+        /// other radix gives an error of kind
+        /// [`InvalidSyntax`](crate::ParseErrorKind::InvalidSyntax) at offset 0.
+        #[cfg(feature = "num-traits")]
+        impl num_traits::Num for $ty {
+            type FromStrRadixErr = crate::ParseError;
+
+            fn from_str_radix(s: &str, radix: u32) -> Result<Self, crate::ParseError> {
+                match radix {
+                    10 => {
+                        let mode = crate::RoundingMode::TiesToEven;
+                        let bits = crate::decimal::parse(Self::FORMAT, s, mode)?;
+                        Ok(Self::from_bits(bits as _))
+                    },
+                    _ => Err(crate::ParseError::invalid(s.as_bytes(), 0)),
+                }
+            }
+        }
+
+        #[cfg(feature = "num-traits")]
+        impl num_traits::Bounded for $ty {
+            fn min_value() -> Self {
+                Self::MIN
+            }
+
+            fn max_value() -> Self {
+                Self::MAX
+            }
+        }
+
+        /// As for the primitive floats, zeros, infinities, and NaNs count as positive or negative
+        /// by their sign bit.
+        #[cfg(feature = "num-traits")]
+        impl num_traits::Signed for $ty {
+            fn abs(&self) -> Self {
+                Self::abs(self)
+            }
+
+            fn abs_sub(&self, other: &Self) -> Self {
+                // The comparison is numeric, so that NaN operands fall through to a NaN result.
+                if self.to_float() <= other.to_float() { Self::ZERO } else { *self - *other }
+            }
+
+            fn signum(&self) -> Self {
+                Self::signum(self)
+            }
+
+            fn is_positive(&self) -> bool {
+                self.is_sign_positive()
+            }
+
+            fn is_negative(&self) -> bool {
+                self.is_sign_negative()
+            }
+        }
+    };
+}
+
+macro_rules! define_approx {
+    ($ty:ident) => {
+        #[cfg(feature = "approx")]
+        impl approx::AbsDiffEq for $ty {
+            type Epsilon = Self;
+
+            fn default_epsilon() -> Self {
+                Self::from_ratio(1, 1 - Self::MANTISSA_DIGITS as i32)
+            }
+
+            /// Compares by IEEE 754 semantics, as for the primitive floats: a NaN operand, or a
+            /// NaN difference such as `∞ − ∞`, is never within `epsilon`.
+            fn abs_diff_eq(&self, other: &Self, epsilon: Self) -> bool {
+                (*self - *other).abs().to_float() <= epsilon.to_float()
+            }
+        }
+
+        #[cfg(feature = "approx")]
+        impl approx::RelativeEq for $ty {
+            fn default_max_relative() -> Self {
+                <Self as approx::AbsDiffEq>::default_epsilon()
+            }
+
+            fn relative_eq(&self, other: &Self, epsilon: Self, max_relative: Self) -> bool {
+                if self.to_float() == other.to_float() {
+                    return true;
+                }
+                if self.is_infinite() || other.is_infinite() {
+                    return false;
+                }
+                let diff = (*self - *other).abs().to_float();
+                if diff <= epsilon.to_float() {
+                    return true;
+                }
+                let largest = self.abs().maximum_number(other.abs());
+                diff <= (largest * max_relative).to_float()
+            }
+        }
+
+        /// The distance in ULPs is exact, from [`Self::ulp_distance`]; values of opposite sign
+        /// are equal only if they are within `epsilon` of each other.
+        #[cfg(feature = "approx")]
+        impl approx::UlpsEq for $ty {
+            fn default_max_ulps() -> u32 {
+                4
+            }
+
+            fn ulps_eq(&self, other: &Self, epsilon: Self, max_ulps: u32) -> bool {
+                if approx::AbsDiffEq::abs_diff_eq(self, other, epsilon) {
+                    return true;
+                }
+                if self.is_sign_negative() != other.is_sign_negative() {
+                    return false;
+                }
+                match self.ulp_distance(*other) {
+                    Some(ulps) => ulps as u128 <= max_ulps as u128,
+                    None => false,
+                }
+            }
+        }
+    };
+}
+
+macro_rules! define_ordered_float {
+    ($ty:ident; $f_ty:ident) => {
+        #[cfg(feature = "ordered-float")]
+        impl From<$ty> for ordered_float::OrderedFloat<$f_ty> {
+            fn from(val: $ty) -> Self {
+                Self(val.to_float())
+            }
+        }
+
+        #[cfg(feature = "ordered-float")]
+        impl From<ordered_float::OrderedFloat<$f_ty>> for $ty {
+            fn from(val: ordered_float::OrderedFloat<$f_ty>) -> Self {
+                Self::from_float(val.0)
+            }
+        }
+
+        #[cfg(feature = "ordered-float")]
+        impl From<ordered_float::NotNan<$f_ty>> for $ty {
+            fn from(val: ordered_float::NotNan<$f_ty>) -> Self {
+                Self::from_float(val.into_inner())
+            }
+        }
+
+        /// Fails if `val` is NaN.
+        #[cfg(feature = "ordered-float")]
+        impl TryFrom<$ty> for ordered_float::NotNan<$f_ty> {
+            type Error = ordered_float::FloatIsNan;
+
+            fn try_from(val: $ty) -> Result<Self, ordered_float::FloatIsNan> {
+                Self::new(val.to_float())
+            }
+        }
+    };
+}
+
+macro_rules! define_num_float {
+    ($ty:ident; $f_ty:ident) => {
+        define_num_float!(
+            $ty; $f_ty;
+            core {
+                consts {
+                    nan, infinity, neg_infinity, neg_zero, min_value, min_positive_value, epsilon,
+                    max_value
+                }
+                tests {
+                    is_nan, is_infinite, is_finite, is_normal, is_subnormal, is_sign_positive,
+                    is_sign_negative
+                }
+                unary { floor, ceil, round, trunc, fract, abs, signum, recip, to_degrees, to_radians }
+                binary { max, min }
+            }
+            float {
+                unary {
+                    sqrt, exp, exp2, ln, log2, log10, cbrt, sin, cos, tan, asin, acos, atan, exp_m1,
+                    ln_1p, sinh, cosh, tanh, asinh, acosh, atanh
+                }
+                binary { powf, log, abs_sub, hypot, atan2, copysign }
+            }
+        );
+    };
+    (
+        $ty:ident; $f_ty:ident;
+        core {
+            consts { $( $c:ident ),* }
+            tests { $( $t:ident ),* }
+            unary { $( $u:ident ),* }
+            binary { $( $b:ident ),* }
+        }
+        float {
+            unary { $( $fu:ident ),* }
+            binary { $( $fb:ident ),* }
+        }
+    ) => {
+        #[cfg(feature = "num-traits")]
+        impl num_traits::NumCast for $ty {
+            fn from<T: num_traits::ToPrimitive>(n: T) -> Option<Self> {
+                <$f_ty as num_traits::NumCast>::from(n).map(Self::from_float)
+            }
+        }
+
+        #[cfg(feature = "num-traits")]
+        impl num_traits::float::FloatCore for $ty {
+            define_num_float!(@methods num_traits::float::FloatCore; $f_ty;
+                consts { $( $c ),* } tests { $( $t ),* } unary { $( $u ),* } binary { $( $b ),* });
+
+            fn classify(self) -> core::num::FpCategory {
+                Self::classify(&self)
+            }
+
+            fn integer_decode(self) -> (u64, i16, i8) {
+                num_traits::float::FloatCore::integer_decode(self.to_float())
+            }
+
+            fn powi(self, n: i32) -> Self {
+                Self::from_float(num_traits::float::FloatCore::powi(self.to_float(), n))
+            }
+
+            fn clamp(self, min: Self, max: Self) -> Self {
+                Self::from_float(num_traits::float::FloatCore::clamp(
+                    self.to_float(),
+                    min.to_float(),
+                    max.to_float(),
+                ))
+            }
+        }
+
+        /// # Features
+        ///
+        /// Available only with feature `num-traits`, together with `libm` or `std`.
+        #[cfg(all(feature = "num-traits", any(feature = "libm", feature = "std")))]
+        impl num_traits::Float for $ty {
+            define_num_float!(@methods num_traits::Float; $f_ty;
+                consts { $( $c ),* } tests { $( $t ),* } unary { $( $u, )* $( $fu ),* }
+                binary { $( $b, )* $( $fb ),* });
+
+            fn classify(self) -> core::num::FpCategory {
+                Self::classify(&self)
+            }
+
+            fn integer_decode(self) -> (u64, i16, i8) {
+                num_traits::Float::integer_decode(self.to_float())
+            }
+
+            fn powi(self, n: i32) -> Self {
+                Self::from_float(num_traits::Float::powi(self.to_float(), n))
+            }
+
+            fn clamp(self, min: Self, max: Self) -> Self {
+                Self::from_float(num_traits::Float::clamp(self.to_float(), min.to_float(), max.to_float()))
+            }
+
+            fn mul_add(self, a: Self, b: Self) -> Self {
+                Self::from_float(num_traits::Float::mul_add(self.to_float(), a.to_float(), b.to_float()))
+            }
+
+            fn sin_cos(self) -> (Self, Self) {
+                let (sin, cos) = num_traits::Float::sin_cos(self.to_float());
+                (Self::from_float(sin), Self::from_float(cos))
+            }
+        }
+    };
+    (
+        @methods $tr:path; $f_ty:ident;
+        consts { $( $c:ident ),* }
+        tests { $( $t:ident ),* }
+        unary { $( $u:ident ),* }
+        binary { $( $b:ident ),* }
+    ) => {
+        $(
+            fn $c() -> Self {
+                Self::from_float(<$f_ty as $tr>::$c())
+            }
+        )*
+
+        $(
+            fn $t(self) -> bool {
+                <$f_ty as $tr>::$t(self.to_float())
+            }
+        )*
+
+        $(
+            fn $u(self) -> Self {
+                Self::from_float(<$f_ty as $tr>::$u(self.to_float()))
+            }
+        )*
+
+        $(
+            fn $b(self, other: Self) -> Self {
+                Self::from_float(<$f_ty as $tr>::$b(self.to_float(), other.to_float()))
+            }
+        )*
+    };
+}
+
+macro_rules! define_math {
+    ($ty:ident; $f_ty:ident) => {
+        define_math!(
+            $ty; $f_ty;
+            unary {
+                /// Returns the square root of `self`.
+                ///
+                /// Returns NaN if `self` is a negative number other than `-0.0`.
+                fn sqrt;
+
+                /// Returns `e^(self)`, the exponential function.
+                fn exp;
+
+                /// Returns the natural logarithm of `self`.
+                fn ln;
+
+                /// Returns the base 2 logarithm of `self`.
+                fn log2;
+
+                /// Computes the sine of `self`, in radians.
+                fn sin;
+
+                /// Computes the cosine of `self`, in radians.
+                fn cos;
+
+                /// Computes the tangent of `self`, in radians.
+                fn tan;
+            }
+            binary {
+                /// Computes the four quadrant arctangent of `self` (`y`) and `other` (`x`), in
+                /// radians.
+                fn atan2(other);
+
+                /// Computes the length of the hypotenuse of a right-angle triangle given legs of
+                /// length `self` and `other`.
+                fn hypot(other);
+
+                /// Raises `self` to the floating point power `n`.
+                fn powf(n);
+            }
+        );
+    };
+    (
+        $ty:ident; $f_ty:ident;
+        unary { $( $( #[doc = $doc1:literal] )* fn $name1:ident; )* }
+        binary { $( $( #[doc = $doc2:literal] )* fn $name2:ident($arg:ident); )* }
+    ) => {
+        #[cfg(any(feature = "libm", feature = "std"))]
+        impl $ty {
+            $(
+                $( #[doc = $doc1] )*
+                ///
+                /// # Features
+                ///
+                /// Available only with feature: `libm` or `std`.
+                pub fn $name1(&self) -> Self {
+                    Self::from_float(crate::math::Math::$name1(self.to_float()))
+                }
+            )*
+
+            $(
+                $( #[doc = $doc2] )*
+                ///
+                /// # Features
+                ///
+                /// Available only with feature: `libm` or `std`.
+                pub fn $name2(&self, $arg: Self) -> Self {
+                    Self::from_float(crate::math::Math::$name2(self.to_float(), $arg.to_float()))
+                }
+            )*
         }
     };
 }
@@ -37,6 +590,28 @@ macro_rules! define_mid {
             pub const fn to_float(&self) -> $f_ty {
                 <$f_ty>::from_bits(self.bits)
             }
+
+            /// Constructs a wrapped float from the result of a Rust float operation, flushing
+            /// [subnormal] values to zero.
+            ///
+            /// This models hardware with flush-to-zero (FTZ) enabled, which checks for underflow
+            /// after rounding: a result that rounds up to the least normal value is kept.
+            ///
+            /// [subnormal]: https://en.wikipedia.org/wiki/Denormal_number
+            pub const fn from_float_ftz(float: $f_ty) -> Self {
+                Self::from_float(float).flush_subnormals_to_zero()
+            }
+
+            /// Returns the Rust float which this wrapped float represents, treating [subnormal]
+            /// values as zero.
+            ///
+            /// This models hardware with denormals-are-zero (DAZ) enabled, which replaces
+            /// subnormal operands with zero before every operation.
+            ///
+            /// [subnormal]: https://en.wikipedia.org/wiki/Denormal_number
+            pub const fn to_float_daz(&self) -> $f_ty {
+                self.flush_subnormals_to_zero().to_float()
+            }
         }
 
         impl From<$f_ty> for $ty {
@@ -50,6 +625,14 @@ macro_rules! define_mid {
                 val.to_float()
             }
         }
+
+        define_slice_casts!($ty; $f_ty);
+        define_native_ops!($ty; $f_ty);
+        define_num_traits!($ty);
+        define_approx!($ty);
+        define_num_float!($ty; $f_ty);
+        define_ordered_float!($ty; $f_ty);
+        define_math!($ty; $f_ty);
     };
     ($ty:ident; float $f_ty:ident with feature $feature:literal;) => {
         #[cfg(feature = $feature)]
@@ -75,6 +658,37 @@ macro_rules! define_mid {
             pub const fn to_float(&self) -> $f_ty {
                 <$f_ty>::from_bits(self.bits)
             }
+
+            /// Constructs a wrapped float from the result of a Rust float operation, flushing
+            /// [subnormal] values to zero.
+            ///
+            /// This models hardware with flush-to-zero (FTZ) enabled, which checks for underflow
+            /// after rounding: a result that rounds up to the least normal value is kept.
+            ///
+            /// # Features
+            ///
+            /// Available only with feature: `
+            #[doc = $feature]
+            /// `.
+            ///
+            /// [subnormal]: https://en.wikipedia.org/wiki/Denormal_number
+            pub const fn from_float_ftz(float: $f_ty) -> Self {
+                Self::from_float(float).flush_subnormals_to_zero()
+            }
+
+            /// Returns the Rust float which this wrapped float represents, treating [subnormal]
+            /// values as zero.
+            ///
+            /// # Features
+            ///
+            /// Available only with feature: `
+            #[doc = $feature]
+            /// `.
+            ///
+            /// [subnormal]: https://en.wikipedia.org/wiki/Denormal_number
+            pub const fn to_float_daz(&self) -> $f_ty {
+                self.flush_subnormals_to_zero().to_float()
+            }
         }
 
         #[cfg(feature = $feature)]
@@ -90,6 +704,18 @@ macro_rules! define_mid {
                 val.to_float()
             }
         }
+
+        #[cfg(feature = $feature)]
+        define_slice_casts!($ty; $f_ty);
+
+        #[cfg(feature = $feature)]
+        define_native_ops!($ty; $f_ty);
+
+        #[cfg(feature = $feature)]
+        define_num_traits!($ty);
+
+        #[cfg(feature = $feature)]
+        define_approx!($ty);
     };
 }
 
@@ -111,6 +737,41 @@ macro_rules! define_tail {
             /// than this, since the leading 1 is not stored explicitly.
             pub const MANTISSA_DIGITS: usize = Self::BITS - Self::EXP_BITS;
 
+            /// The radix of the internal representation.
+            pub const RADIX: u32 = 2;
+
+            /// Approximate number of significant digits in base 10: every decimal with this many
+            /// significant digits survives a round trip through the format.
+            pub const DIGITS: u32 = crate::helpers::log10_pow2(Self::MANTISSA_BITS as i32) as u32;
+
+            /// One greater than the minimum possible normal power of 2 exponent, matching the
+            /// `MIN_EXP` constants of the Rust floats.
+            pub const MIN_EXP: i32 = 3 - (1 << (Self::EXP_BITS - 1));
+
+            /// Maximum possible power of 2 exponent, plus one, matching the `MAX_EXP` constants of
+            /// the Rust floats: [`MAX`](Self::MAX) is just below `2^MAX_EXP`.
+            pub const MAX_EXP: i32 = 1 << (Self::EXP_BITS - 1);
+
+            /// Minimum `x` for which `10^x` is a normal number.
+            pub const MIN_10_EXP: i32 = crate::helpers::log10_pow2(Self::MIN_EXP - 1);
+
+            /// Maximum `x` for which `10^x` is a finite number.
+            pub const MAX_10_EXP: i32 = crate::helpers::log10_pow2(Self::MAX_EXP);
+
+            /// The difference between `1.0` and the next larger representable number, the machine
+            /// epsilon `2^(1 − MANTISSA_DIGITS)`.
+            pub const EPSILON: Self = Self::from_bits(
+                Self::EXP_ZERO - ((Self::MANTISSA_BITS as $u_ty) << Self::MANTISSA_BITS),
+            );
+
+            /// The largest integer `n` such that every integer from `0` to `n` is exactly
+            /// representable, `2^MANTISSA_DIGITS`.
+            ///
+            /// Unlike `Number.MAX_SAFE_INTEGER` in JavaScript, which is one less, this counts the
+            /// power of two itself: `n + 1` is the first integer that rounds.  The same holds for
+            /// the negative integers down to `-MAX_SAFE_INTEGER`.
+            pub const MAX_SAFE_INTEGER: i128 = 1 << Self::MANTISSA_DIGITS;
+
             // All bits except sign bit.
             const ABS_MASK: $u_ty = <$u_ty>::MAX >> 1;
 
@@ -183,6 +844,18 @@ macro_rules! define_tail {
             /// The negative normal value with the least possible absolute magnitude.
             pub const MAX_NEGATIVE: Self = Self::MIN_POSITIVE.neg();
 
+            /// The positive subnormal value with the least possible absolute magnitude.
+            pub const MIN_POSITIVE_SUBNORMAL: Self = Self::from_bits(1);
+
+            /// The negative subnormal value with the least possible absolute magnitude.
+            pub const MAX_NEGATIVE_SUBNORMAL: Self = Self::MIN_POSITIVE_SUBNORMAL.neg();
+
+            /// The positive subnormal value with the greatest possible absolute magnitude.
+            pub const MAX_SUBNORMAL: Self = Self::from_bits(Self::MANT_MASK);
+
+            /// The negative subnormal value with the greatest possible absolute magnitude.
+            pub const MIN_SUBNORMAL: Self = Self::MAX_SUBNORMAL.neg();
+
             #[doc(hidden)]
             pub const NAN: Self = Self::QNAN;
 
@@ -255,6 +928,32 @@ macro_rules! define_tail {
                 crate::helpers::is_finite(self.classify())
             }
 
+            /// Returns `true` if `self` is `2^k` for some integer `k`, subnormal powers included.
+            ///
+            /// Zeros, negative numbers, infinities, and NaN are not powers of two.
+            pub const fn is_power_of_two(&self) -> bool {
+                self.checked_log2().is_some()
+            }
+
+            /// Returns `k` if `self` is exactly `2^k`, or `None` if it is not a power of two.
+            ///
+            /// The result is read from the bit fields: a normal power of two has a zero mantissa
+            /// field, and a subnormal one has a single mantissa bit set.
+            pub const fn checked_log2(&self) -> Option<i32> {
+                let biased = ((self.bits & Self::EXP_MASK) >> Self::MANTISSA_BITS) as i32;
+                let bias = (Self::EXP_ZERO >> Self::MANTISSA_BITS) as i32;
+                let mant = self.bits & Self::MANT_MASK;
+                if self.bits & Self::SIGN_MASK != 0 || self.bits & Self::EXP_MASK == Self::EXP_MASK {
+                    None
+                } else if biased != 0 {
+                    if mant == 0 { Some(biased - bias) } else { None }
+                } else if mant.is_power_of_two() {
+                    Some(1 - bias - Self::MANTISSA_BITS as i32 + mant.trailing_zeros() as i32)
+                } else {
+                    None
+                }
+            }
+
             /// Computes the absolute value of `self`.
             ///
             /// The result is always exact.  The result will always test `true` with [`Self::is_sign_positive`].
@@ -271,11 +970,120 @@ macro_rules! define_tail {
                 Self { bits }
             }
 
-            /// Returns a number that represents the sign of `self`.
-            ///
-            /// * [`Self::ONE`] if the number is positive, including `+0.0` or `+∞`
-            /// * [`Self::NEG_ONE`] if the number is negative, including `-0.0` or `-∞`
-            /// * `self` if the number is [NaN]
+            /// Returns the least number greater than `self`.
+            ///
+            /// This is the IEEE 754 `nextUp` operation: both zeros step to the smallest positive
+            /// subnormal number, [`Self::MAX`] steps to infinity, and [`Self::NEG_INFINITY`] steps
+            /// to [`Self::MIN`].  Positive infinity and NaN are returned unchanged.
+            pub const fn next_up(&self) -> Self {
+                let abs = self.bits & Self::ABS_MASK;
+                if self.is_nan() || self.bits == Self::INFINITY.bits {
+                    *self
+                } else if abs == 0 {
+                    Self::from_bits(1)
+                } else if self.bits == abs {
+                    Self::from_bits(self.bits + 1)
+                } else {
+                    Self::from_bits(self.bits - 1)
+                }
+            }
+
+            /// Returns the greatest number less than `self`.
+            ///
+            /// This is the IEEE 754 `nextDown` operation, the mirror image of [`Self::next_up`].
+            pub const fn next_down(&self) -> Self {
+                let abs = self.bits & Self::ABS_MASK;
+                if self.is_nan() || self.bits == Self::NEG_INFINITY.bits {
+                    *self
+                } else if abs == 0 {
+                    Self::from_bits(Self::SIGN_MASK | 1)
+                } else if self.bits == abs {
+                    Self::from_bits(self.bits - 1)
+                } else {
+                    Self::from_bits(self.bits + 1)
+                }
+            }
+
+            /// Replaces a [subnormal] value with zero of the same sign.
+            ///
+            /// All other values, including NaN, are returned unchanged.  This models the
+            /// flush-to-zero (FTZ) and denormals-are-zero (DAZ) modes of hardware that does not
+            /// implement gradual underflow.
+            ///
+            /// [subnormal]: https://en.wikipedia.org/wiki/Denormal_number
+            pub const fn flush_subnormals_to_zero(&self) -> Self {
+                if self.is_subnormal() {
+                    let bits = self.bits & Self::SIGN_MASK;
+                    Self { bits }
+                } else {
+                    *self
+                }
+            }
+
+            /// Length of the output of [`Self::to_bit_string`].
+            pub const BIT_STRING_LEN: usize = Self::BITS + 2;
+
+            /// Writes the raw bits into `buf` as a binary string, with the sign, exponent, and
+            /// mantissa fields separated by spaces, and returns the written part of `buf`.
+            ///
+            /// For example, `F32` 5.0 is written as `0 10000001 01000000000000000000000`.  The
+            /// string can be parsed back with [`Self::from_bit_string`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if `buf` is shorter than [`Self::BIT_STRING_LEN`].
+            pub fn to_bit_string<'a>(&self, buf: &'a mut [u8]) -> &'a str {
+                let buf = &mut buf[..Self::BIT_STRING_LEN];
+                let mut pos = 0;
+                for i in (0..Self::BITS).rev() {
+                    if i == Self::BITS - 2 || i == Self::MANTISSA_BITS - 1 {
+                        buf[pos] = b' ';
+                        pos += 1;
+                    }
+                    buf[pos] = b'0' + ((self.bits >> i) & 1) as u8;
+                    pos += 1;
+                }
+                core::str::from_utf8(buf).expect("binary digits are ASCII")
+            }
+
+            /// Parses a binary string of exactly [`Self::BITS`] digits, most significant first.
+            ///
+            /// Spaces and underscores are ignored wherever they appear, so the output of
+            /// [`Self::to_bit_string`] parses back to the same value, as does any other grouping
+            /// of the digits.
+            ///
+            /// Too many digits give an error of kind
+            /// [`Overflow`](crate::ParseErrorKind::FieldTooLarge), and too few, one of kind
+            /// [`Underflow`](crate::ParseErrorKind::FieldTooSmall).
+            pub fn from_bit_string(s: &str) -> Result<Self, crate::ParseError> {
+                use crate::{ParseError, ParseErrorKind};
+                let s = s.as_bytes();
+                if s.is_empty() {
+                    return Err(ParseError::empty());
+                }
+                let (mut bits, mut count): ($u_ty, usize) = (0, 0);
+                for (i, &b) in s.iter().enumerate() {
+                    match b {
+                        b'0' | b'1' if count < Self::BITS => {
+                            bits = (bits << 1) | (b - b'0') as $u_ty;
+                            count += 1;
+                        },
+                        b'0' | b'1' => return Err(ParseError::new(ParseErrorKind::FieldTooLarge, s, i)),
+                        b' ' | b'_' => {},
+                        _ => return Err(ParseError::invalid(s, i)),
+                    }
+                }
+                if count != Self::BITS {
+                    return Err(ParseError::new(ParseErrorKind::FieldTooSmall, s, s.len()));
+                }
+                Ok(Self::from_bits(bits))
+            }
+
+            /// Returns a number that represents the sign of `self`.
+            ///
+            /// * [`Self::ONE`] if the number is positive, including `+0.0` or `+∞`
+            /// * [`Self::NEG_ONE`] if the number is negative, including `-0.0` or `-∞`
+            /// * `self` if the number is [NaN]
             ///
             /// [NaN]: https://en.wikipedia.org/wiki/NaN
             pub const fn signum(&self) -> Self {
@@ -296,6 +1104,33 @@ macro_rules! define_tail {
                 Self { bits }
             }
 
+            /// Returns the sign of `self`, read from the sign bit, so that even zeros and NaNs have
+            /// one.
+            pub const fn sign(&self) -> crate::Sign {
+                crate::Sign::from_bit(self.is_sign_negative())
+            }
+
+            /// Returns `self` with its sign bit set to `sign`, leaving the other bits unchanged.
+            pub const fn with_sign(&self, sign: crate::Sign) -> Self {
+                let sign_bit = if sign.is_negative() { Self::SIGN_MASK } else { 0 };
+                Self::from_bits((self.bits & Self::ABS_MASK) | sign_bit)
+            }
+
+            /// Returns a number composed of the magnitude of `self` and the sign of `sign`, which
+            /// may be a float of any format, a Rust float, or a [`Sign`](crate::Sign).
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            #[doc = concat!("# use float_bits::{", stringify!($ty), ", Sign};")]
+            ///
+            #[doc = concat!("assert_eq!(", stringify!($ty), "::NEG_ONE, ", stringify!($ty), "::ONE.copysign_from(-2.0f64));")]
+            #[doc = concat!("assert_eq!(", stringify!($ty), "::INFINITY, ", stringify!($ty), "::NEG_INFINITY.copysign_from(Sign::Positive));")]
+            /// ```
+            pub fn copysign_from(&self, sign: impl Into<crate::Sign>) -> Self {
+                self.with_sign(sign.into())
+            }
+
             const fn sort_bits(&self) -> $s_ty {
                 let mask = if self.is_sign_negative() { Self::ABS_MASK } else { 0 };
                 let bits = self.bits ^ mask;
@@ -334,6 +1169,95 @@ macro_rules! define_tail {
                 }
             }
 
+            /// Returns the ordering between the absolute values of `self` and `rhs` by the IEEE
+            /// 754 `totalOrder` predicate.
+            ///
+            /// This is the `totalOrderMag` predicate of IEEE 754: the signs are ignored, so `-0.0`
+            /// equals `+0.0` and a NaN of either sign is greater than infinity, with quiet NaNs
+            /// above signaling ones and larger payloads above smaller ones.
+            pub const fn total_cmp_abs(&self, rhs: Self) -> core::cmp::Ordering {
+                self.abs().total_cmp(rhs.abs())
+            }
+
+            /// Returns a byte encoding of `self` whose lexicographic order is the order of
+            /// [`Self::total_cmp`].
+            ///
+            /// The encoding is the raw bits in big-endian order, with the sign bit flipped for
+            /// positive values and every bit flipped for negative values.  This suits the keys of
+            /// ordered key-value stores, which compare keys as byte strings.  Distinct bit patterns
+            /// have distinct encodings, and [`Self::from_sortable_bytes`] inverts it exactly.
+            pub const fn to_sortable_bytes(&self) -> [u8; core::mem::size_of::<$u_ty>()] {
+                let mask = if self.is_sign_negative() { <$u_ty>::MAX } else { Self::SIGN_MASK };
+                (self.bits ^ mask).to_be_bytes()
+            }
+
+            /// Constructs a wrapped float from the encoding produced by
+            /// [`Self::to_sortable_bytes`].
+            ///
+            /// Every byte array is the encoding of some value, so this cannot fail.
+            pub const fn from_sortable_bytes(bytes: [u8; core::mem::size_of::<$u_ty>()]) -> Self {
+                let bits = <$u_ty>::from_be_bytes(bytes);
+                let mask = if bits & Self::SIGN_MASK != 0 { Self::SIGN_MASK } else { <$u_ty>::MAX };
+                Self::from_bits(bits ^ mask)
+            }
+
+            /// Returns the memcomparable encoding of `self`, as used for float keys by TiDB, TiKV,
+            /// and other MySQL-compatible stores.
+            ///
+            /// Byte for byte, the encoding is that of [`Self::to_sortable_bytes`], except that
+            /// `-0.0` is encoded as `+0.0`: the raw bits in big-endian order, with the sign bit set
+            /// if the value is `+0.0`, `-0.0`, or has a positive sign, and with every bit flipped
+            /// otherwise.  For [`F64`](crate::F64), `1.0` is encoded as `bf f0 00 00 00 00 00 00`
+            /// and `-1.0` as `40 0f ff ff ff ff ff ff`.  Because the zeros share an encoding, keys
+            /// compare as SQL compares the values, with the zeros equal.
+            ///
+            /// The one divergence from those stores is a positive NaN.  They test `v >= 0.0`,
+            /// which is false for any NaN, and so flip every bit of it, giving an encoding that
+            /// sorts below `-∞` and decodes to a positive number.  Here a positive NaN has its sign
+            /// bit set like any other positive value, so it sorts above `+∞` and decodes back to
+            /// itself.  Negative NaNs are encoded as those stores encode them.
+            pub const fn to_memcomparable_bytes(&self) -> [u8; core::mem::size_of::<$u_ty>()] {
+                if self.is_zero() {
+                    Self::ZERO.to_sortable_bytes()
+                } else {
+                    self.to_sortable_bytes()
+                }
+            }
+
+            /// Constructs a wrapped float from its memcomparable encoding, as produced by
+            /// [`Self::to_memcomparable_bytes`].
+            ///
+            /// Every byte array decodes to some value; `-0.0` comes back as `+0.0`, which is what
+            /// the encoding records.
+            pub const fn from_memcomparable_bytes(
+                bytes: [u8; core::mem::size_of::<$u_ty>()],
+            ) -> Self {
+                Self::from_sortable_bytes(bytes)
+            }
+
+            /// Returns the number of steps between adjacent representable values from `self` to
+            /// `rhs`, or `None` if either is NaN.
+            ///
+            /// Positive and negative zero are the same point, so the distance between them is 0,
+            /// and the distance from `MIN_POSITIVE.neg()` to `MIN_POSITIVE` counts every subnormal
+            /// number in between.  The infinities are one step beyond [`Self::MAX`] and
+            /// [`Self::MIN`].
+            pub const fn ulp_distance(&self, rhs: Self) -> Option<$u_ty> {
+                if self.is_nan() || rhs.is_nan() {
+                    return None;
+                }
+                let (lhs, rhs) = (self.ulp_key(), rhs.ulp_key());
+                // The true difference is less than `2^BITS`, so wrapping gives it exactly.
+                let diff = if lhs < rhs { rhs.wrapping_sub(lhs) } else { lhs.wrapping_sub(rhs) };
+                Some(diff as $u_ty)
+            }
+
+            // Like `sort_bits`, but with both zeros at 0 and adjacent values one apart.
+            const fn ulp_key(&self) -> $s_ty {
+                let abs = (self.bits & Self::ABS_MASK) as $s_ty;
+                if self.is_sign_negative() { -abs } else { abs }
+            }
+
             /// Restrict a value to a certain interval unless it is NaN.
             ///
             /// Returns `max` if `self` is greater than `max`, and `min` if `self` is less than
@@ -362,6 +1286,154 @@ macro_rules! define_tail {
                     *self
                 }
             }
+
+            /// Restrict a value to a certain interval unless it is NaN, without panicking.
+            ///
+            /// Behaves like [`Self::clamp`], except that instead of panicking on an invalid
+            /// interval, it returns `None` if `min` > `max`, `min` is NaN, or `max` is NaN.
+            pub const fn checked_clamp(&self, min: Self, max: Self) -> Option<Self> {
+                use core::cmp::Ordering;
+                if min.is_nan() || max.is_nan() || matches!(min.total_cmp(max), Ordering::Greater) {
+                    None
+                } else {
+                    Some(self.clamp(min, max))
+                }
+            }
+
+            // Returns `self` with the `is_quiet` bit set.  Only meaningful for NaN values.
+            const fn quieted(&self) -> Self {
+                Self::from_bits(self.bits | Self::QUIET_MASK)
+            }
+
+            /// Returns the lesser of `self` and `rhs`, propagating NaN.
+            ///
+            /// This is the `minimum` operation of IEEE 754 (2019 revision).  If either input is
+            /// NaN, the result is a quiet NaN.  Unlike the comparison operators on Rust floats,
+            /// `-0.0` is considered to be less than `+0.0`.
+            pub const fn minimum(&self, rhs: Self) -> Self {
+                use core::cmp::Ordering;
+                if self.is_nan() {
+                    self.quieted()
+                } else if rhs.is_nan() {
+                    rhs.quieted()
+                } else if matches!(self.total_cmp(rhs), Ordering::Greater) {
+                    rhs
+                } else {
+                    *self
+                }
+            }
+
+            /// Returns the greater of `self` and `rhs`, propagating NaN.
+            ///
+            /// This is the `maximum` operation of IEEE 754 (2019 revision).  If either input is
+            /// NaN, the result is a quiet NaN.  Unlike the comparison operators on Rust floats,
+            /// `+0.0` is considered to be greater than `-0.0`.
+            pub const fn maximum(&self, rhs: Self) -> Self {
+                use core::cmp::Ordering;
+                if self.is_nan() {
+                    self.quieted()
+                } else if rhs.is_nan() {
+                    rhs.quieted()
+                } else if matches!(self.total_cmp(rhs), Ordering::Less) {
+                    rhs
+                } else {
+                    *self
+                }
+            }
+
+            /// Returns the lesser of `self` and `rhs`, ignoring NaN.
+            ///
+            /// This is the `minimumNumber` operation of IEEE 754 (2019 revision).  If exactly one
+            /// input is NaN, the other input is returned; if both are NaN, the result is a quiet
+            /// NaN.  `-0.0` is considered to be less than `+0.0`.
+            pub const fn minimum_number(&self, rhs: Self) -> Self {
+                if self.is_nan() && rhs.is_nan() {
+                    self.quieted()
+                } else if self.is_nan() {
+                    rhs
+                } else if rhs.is_nan() {
+                    *self
+                } else {
+                    self.minimum(rhs)
+                }
+            }
+
+            /// Returns the greater of `self` and `rhs`, ignoring NaN.
+            ///
+            /// This is the `maximumNumber` operation of IEEE 754 (2019 revision).  If exactly one
+            /// input is NaN, the other input is returned; if both are NaN, the result is a quiet
+            /// NaN.  `+0.0` is considered to be greater than `-0.0`.
+            pub const fn maximum_number(&self, rhs: Self) -> Self {
+                if self.is_nan() && rhs.is_nan() {
+                    self.quieted()
+                } else if self.is_nan() {
+                    rhs
+                } else if rhs.is_nan() {
+                    *self
+                } else {
+                    self.maximum(rhs)
+                }
+            }
+
+            /// Returns whichever of `self` and `rhs` has the lesser magnitude, propagating NaN.
+            ///
+            /// This is the `minimumMagnitude` operation of IEEE 754 (2019 revision).  If either
+            /// input is NaN, the result is a quiet NaN.  If the magnitudes are equal, the result
+            /// is [`Self::minimum`] of the two inputs.
+            pub const fn minimum_magnitude(&self, rhs: Self) -> Self {
+                use core::cmp::Ordering;
+                if self.is_nan() || rhs.is_nan() {
+                    return self.minimum(rhs);
+                }
+                match self.total_cmp_abs(rhs) {
+                    Ordering::Less => *self,
+                    Ordering::Greater => rhs,
+                    Ordering::Equal => self.minimum(rhs),
+                }
+            }
+
+            /// Returns whichever of `self` and `rhs` has the greater magnitude, propagating NaN.
+            ///
+            /// This is the `maximumMagnitude` operation of IEEE 754 (2019 revision).  If either
+            /// input is NaN, the result is a quiet NaN.  If the magnitudes are equal, the result
+            /// is [`Self::maximum`] of the two inputs.
+            pub const fn maximum_magnitude(&self, rhs: Self) -> Self {
+                use core::cmp::Ordering;
+                if self.is_nan() || rhs.is_nan() {
+                    return self.maximum(rhs);
+                }
+                match self.total_cmp_abs(rhs) {
+                    Ordering::Less => rhs,
+                    Ordering::Greater => *self,
+                    Ordering::Equal => self.maximum(rhs),
+                }
+            }
+
+            /// Returns whichever of `self` and `rhs` has the lesser magnitude, ignoring NaN.
+            ///
+            /// This is the `minimumMagnitudeNumber` operation of IEEE 754 (2019 revision).  If
+            /// exactly one input is NaN, the other input is returned; if both are NaN, the result
+            /// is a quiet NaN.
+            pub const fn minimum_magnitude_number(&self, rhs: Self) -> Self {
+                if self.is_nan() || rhs.is_nan() {
+                    self.minimum_number(rhs)
+                } else {
+                    self.minimum_magnitude(rhs)
+                }
+            }
+
+            /// Returns whichever of `self` and `rhs` has the greater magnitude, ignoring NaN.
+            ///
+            /// This is the `maximumMagnitudeNumber` operation of IEEE 754 (2019 revision).  If
+            /// exactly one input is NaN, the other input is returned; if both are NaN, the result
+            /// is a quiet NaN.
+            pub const fn maximum_magnitude_number(&self, rhs: Self) -> Self {
+                if self.is_nan() || rhs.is_nan() {
+                    self.maximum_number(rhs)
+                } else {
+                    self.maximum_magnitude(rhs)
+                }
+            }
         }
 
         impl Default for $ty {
@@ -382,6 +1454,418 @@ macro_rules! define_tail {
                 self.total_cmp(*rhs)
             }
         }
+
+        impl core::ops::Neg for $ty {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self::neg(&self)
+            }
+        }
+
+        impl core::fmt::Debug for $ty {
+            /// Shows the raw bits, or with `{:#?}`, the fields of the representation as well.
+            ///
+            /// The alternate form lists the sign bit, the biased exponent in hex and decimal, the
+            /// stored mantissa bits, the classification, and the value as printed by `Display`.
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                if !f.alternate() {
+                    return f.debug_struct(stringify!($ty)).field("bits", &self.bits).finish();
+                }
+                let exp = (self.bits & Self::EXP_MASK) >> Self::MANTISSA_BITS;
+                let mant = self.bits & Self::MANT_MASK;
+                f.debug_struct(stringify!($ty))
+                    .field("bits", &format_args!("{:#x}", self.bits))
+                    .field("sign", &(self.is_sign_negative() as u8))
+                    .field("exponent", &format_args!("{exp:#x} ({exp})"))
+                    .field("mantissa", &format_args!("{mant:#x}"))
+                    .field("class", &self.classify())
+                    .field("value", &format_args!("{self}"))
+                    .finish()
+            }
+        }
+
+        define_bits_fmt!($ty; Binary, Octal, LowerHex, UpperHex);
+    };
+}
+
+// Formats the raw bits, so that `{:#034b}` shows the bit pattern without a call to `to_bits`.
+macro_rules! define_bits_fmt {
+    ($ty:ident; $( $trait:ident ),*) => {
+        $(
+            impl core::fmt::$trait for $ty {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    core::fmt::$trait::fmt(&self.bits, f)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! define_soft {
+    ($ty:ident; $u_ty:ty) => {
+        impl $ty {
+            const FORMAT: crate::soft::Format =
+                crate::soft::Format::new(Self::BITS, Self::EXP_BITS);
+
+            /// Raises `self` to an integer power.
+            ///
+            /// The computation is carried out in software on the raw bits, so it is available for
+            /// every format and gives the same result on every platform.  Intermediate products
+            /// are kept to 128 significant bits and the result is rounded once, to nearest with
+            /// ties to even.
+            ///
+            /// As in IEEE 754 `pown`, `x.powi(0)` is `+1.0` for every `x`, including NaN.
+            pub const fn powi(&self, n: i32) -> Self {
+                self.powi_rounded(n, crate::RoundingMode::TiesToEven)
+            }
+
+            /// Raises `self` to an integer power, rounding the result according to `mode`.
+            ///
+            /// See [`Self::powi`] for details.
+            pub const fn powi_rounded(&self, n: i32, mode: crate::RoundingMode) -> Self {
+                let bits = crate::soft::powi(Self::FORMAT, self.bits as u128, n, mode);
+                Self::from_bits(bits as $u_ty)
+            }
+
+            /// Returns `self + rhs` rounded according to `mode`, together with the IEEE 754
+            /// exception flags that the addition raised.
+            ///
+            /// The sum is computed in software on the raw bits, and rounded once.  An exact zero
+            /// sum of operands of opposite signs is `+0.0`, or `-0.0` when rounding toward
+            /// negative.  A NaN result is quiet, with the payload of the first NaN operand.
+            pub const fn add_with_flags(
+                &self,
+                rhs: Self,
+                mode: crate::RoundingMode,
+            ) -> (Self, crate::StatusFlags) {
+                let (bits, flags) =
+                    crate::soft::add(Self::FORMAT, self.bits as u128, rhs.bits as u128, mode);
+                (Self::from_bits(bits as $u_ty), flags)
+            }
+
+            /// Returns `self - rhs` rounded according to `mode`, together with the exception
+            /// flags raised.
+            ///
+            /// See [`Self::add_with_flags`] for details.
+            pub const fn sub_with_flags(
+                &self,
+                rhs: Self,
+                mode: crate::RoundingMode,
+            ) -> (Self, crate::StatusFlags) {
+                let (bits, flags) =
+                    crate::soft::sub(Self::FORMAT, self.bits as u128, rhs.bits as u128, mode);
+                (Self::from_bits(bits as $u_ty), flags)
+            }
+
+            /// Returns `self × rhs` rounded according to `mode`, together with the exception
+            /// flags raised.
+            ///
+            /// See [`Self::add_with_flags`] for details.
+            pub const fn mul_with_flags(
+                &self,
+                rhs: Self,
+                mode: crate::RoundingMode,
+            ) -> (Self, crate::StatusFlags) {
+                let (bits, flags) =
+                    crate::soft::mul(Self::FORMAT, self.bits as u128, rhs.bits as u128, mode);
+                (Self::from_bits(bits as $u_ty), flags)
+            }
+
+            /// Returns `self / rhs` rounded according to `mode`, together with the exception
+            /// flags raised.
+            ///
+            /// See [`Self::add_with_flags`] for details.
+            pub const fn div_with_flags(
+                &self,
+                rhs: Self,
+                mode: crate::RoundingMode,
+            ) -> (Self, crate::StatusFlags) {
+                let (bits, flags) =
+                    crate::soft::div(Self::FORMAT, self.bits as u128, rhs.bits as u128, mode);
+                (Self::from_bits(bits as $u_ty), flags)
+            }
+
+            /// Returns the square root of `self` rounded according to `mode`, together with the
+            /// exception flags raised.
+            ///
+            /// The square root of `-0.0` is `-0.0`, and that of any other negative number is
+            /// NaN, raising the invalid flag.
+            pub const fn sqrt_with_flags(
+                &self,
+                mode: crate::RoundingMode,
+            ) -> (Self, crate::StatusFlags) {
+                let (bits, flags) =
+                    crate::soft::sqrt_with_flags(Self::FORMAT, self.bits as u128, mode);
+                (Self::from_bits(bits as $u_ty), flags)
+            }
+
+            /// Raises `self` to an integer power, rounded according to `mode`, together with the
+            /// exception flags raised.
+            ///
+            /// The result is that of [`Self::powi_rounded`].  Zero raised to a negative power is
+            /// an infinity, which raises the division by zero flag.
+            pub const fn powi_with_flags(
+                &self,
+                n: i32,
+                mode: crate::RoundingMode,
+            ) -> (Self, crate::StatusFlags) {
+                let (bits, flags) =
+                    crate::soft::powi_with_flags(Self::FORMAT, self.bits as u128, n, mode);
+                (Self::from_bits(bits as $u_ty), flags)
+            }
+
+            /// Decomposes `self` into `(mantissa, exponent)` such that the value is exactly
+            /// `mantissa × 2^exponent`, or returns `None` for infinities and NaN.
+            ///
+            /// The mantissa is odd, so the decomposition is unique, except that both zeros give
+            /// `(0, 0)`.
+            pub const fn to_ratio(&self) -> Option<(i128, i32)> {
+                let x = crate::soft::unpack(Self::FORMAT, self.bits as u128);
+                match x.kind {
+                    crate::soft::Kind::Zero => Some((0, 0)),
+                    crate::soft::Kind::Finite => {
+                        let shift = x.sig.trailing_zeros();
+                        let mantissa = (x.sig >> shift) as i128;
+                        let mantissa = if x.sign { -mantissa } else { mantissa };
+                        Some((mantissa, x.exp - 127 + shift as i32))
+                    },
+                    _ => None,
+                }
+            }
+
+            /// Returns `mantissa × 2^exponent`, rounded to nearest with ties to even.
+            ///
+            /// The value is rounded exactly once, so this is the inverse of [`Self::to_ratio`]
+            /// and also correctly rounds mantissas too wide for the format.  A zero mantissa gives
+            /// `+0.0`.
+            pub const fn from_ratio(mantissa: i128, exponent: i32) -> Self {
+                Self::from_ratio_rounded(mantissa, exponent, crate::RoundingMode::TiesToEven)
+            }
+
+            /// Returns `mantissa × 2^exponent`, rounded according to `mode`.
+            ///
+            /// See [`Self::from_ratio`] for details.
+            pub const fn from_ratio_rounded(
+                mantissa: i128,
+                exponent: i32,
+                mode: crate::RoundingMode,
+            ) -> Self {
+                let (sign, mag) = (mantissa < 0, mantissa.unsigned_abs());
+                let bits = crate::soft::from_int(Self::FORMAT, sign, mag, exponent as i64, mode);
+                Self::from_bits(bits as $u_ty)
+            }
+
+            /// Returns `true` if `n` converts to the format without rounding, so that it survives
+            /// a round trip.
+            ///
+            /// Every integer of magnitude up to [`MAX_SAFE_INTEGER`](Self::MAX_SAFE_INTEGER) is
+            /// exact, and so are larger integers whose significant bits, from the highest set bit
+            /// to the lowest, fit in [`MANTISSA_DIGITS`](Self::MANTISSA_DIGITS), as long as the
+            /// value is finite.
+            pub const fn represents_integer_exactly(n: i128) -> bool {
+                let mag = n.unsigned_abs();
+                let width = 128 - mag.leading_zeros();
+                mag == 0
+                    || ((width - mag.trailing_zeros()) as usize <= Self::MANTISSA_DIGITS
+                        && width as i32 <= Self::MAX_EXP)
+            }
+
+            /// Converts the raw value of a signed fixed-point number with `FRAC_BITS` fraction bits
+            /// to a float, rounding according to `mode`.
+            ///
+            /// The fixed-point number is `raw × 2^-FRAC_BITS`, so Q15 and Q31 samples convert with
+            /// `FRAC_BITS` of 15 and 31, after widening the raw `i16` or `i32` to `i128`.
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            /// # use float_bits::{F16, RoundingMode};
+            ///
+            /// let x = F16::from_fixed::<15>(-0x4000, RoundingMode::TiesToEven);
+            /// assert_eq!(F16::from_bits(0xb800), x); // -0.5
+            /// ```
+            pub const fn from_fixed<const FRAC_BITS: u32>(
+                raw: i128,
+                mode: crate::RoundingMode,
+            ) -> Self {
+                let (sign, mag) = (raw < 0, raw.unsigned_abs());
+                let exp = -(FRAC_BITS as i64);
+                Self::from_bits(crate::soft::from_int(Self::FORMAT, sign, mag, exp, mode) as $u_ty)
+            }
+
+            /// Converts `self` to the raw value of a signed fixed-point number with `FRAC_BITS`
+            /// fraction bits, rounding according to `mode`.
+            ///
+            /// This is `self × 2^FRAC_BITS` rounded to an integer.  Returns `None` for infinities,
+            /// NaNs, and results that do not fit in `i128`; narrowing to the raw type of a Q15 or
+            /// Q31 number, with saturation or not, is left to the caller.
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            /// # use float_bits::{F32, RoundingMode};
+            ///
+            /// let x = F32::from_float(0.3);
+            /// assert_eq!(Some(9830), x.to_fixed::<15>(RoundingMode::TowardZero));
+            /// assert_eq!(Some(9831), x.to_fixed::<15>(RoundingMode::TowardPositive));
+            /// assert_eq!(None, F32::ONE.to_fixed::<127>(RoundingMode::TiesToEven));
+            /// ```
+            pub const fn to_fixed<const FRAC_BITS: u32>(
+                &self,
+                mode: crate::RoundingMode,
+            ) -> Option<i128> {
+                let scale = FRAC_BITS as i64;
+                match crate::soft::to_int(Self::FORMAT, self.bits as u128, scale, mode) {
+                    Some((false, mag)) if mag <= i128::MAX as u128 => Some(mag as i128),
+                    Some((true, mag)) if mag <= i128::MIN.unsigned_abs() => {
+                        Some((mag as i128).wrapping_neg())
+                    },
+                    _ => None,
+                }
+            }
+
+            /// Compares `self` exactly with a value of any format.
+            ///
+            /// Neither value is rounded, so the result is the order of the real numbers that they
+            /// represent, and a [`BF16`](crate::BF16) element can be checked against an
+            /// [`F64`](crate::F64) reference without converting either one.  Unlike the
+            /// [`total_cmp`](Self::total_cmp) order used by [`Ord`], the two zeros are equal and
+            /// NaN is unordered, so this returns `None` if either value is NaN.
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            /// # use float_bits::{F32, F64};
+            /// # use core::cmp::Ordering;
+            ///
+            /// // 0.1 rounds up in F32, but down in F64.
+            /// let x = F32::from_float(0.1);
+            /// assert_eq!(Some(Ordering::Greater), x.cmp_exact(F64::from_float(0.1)));
+            /// ```
+            pub fn cmp_exact<Other: crate::FloatBits>(
+                &self,
+                other: Other,
+            ) -> Option<core::cmp::Ordering> {
+                let lhs = crate::soft::unpack(Self::FORMAT, self.bits as u128);
+                crate::soft::compare(lhs, crate::exact::unpack_any(other))
+            }
+
+            /// Converts `self` to the format `U`, rounding according to `mode`, and returns the
+            /// result together with the IEEE 754 exception flags raised.
+            ///
+            /// This is the `convertFormat` operation of IEEE 754.  A narrowing conversion may
+            /// overflow, underflow, or be inexact, and a NaN becomes a quiet NaN that keeps the
+            /// sign and the high bits of the payload, raising the invalid flag if it was
+            /// signaling.
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            /// # use float_bits::{F16, F64, RoundingMode, StatusFlags};
+            ///
+            /// let mode = RoundingMode::TiesToEven;
+            /// let (x, flags) = F64::from_float(65536.0).convert_with_flags::<F16>(mode);
+            /// assert_eq!((F16::INFINITY, StatusFlags::OVERFLOW | StatusFlags::INEXACT), (x, flags));
+            /// ```
+            pub fn convert_with_flags<U: crate::FloatBits>(
+                &self,
+                mode: crate::RoundingMode,
+            ) -> (U, crate::StatusFlags) {
+                crate::exact::convert_any_with_flags(*self, mode)
+            }
+
+            /// Parses a decimal string at compile time, rounding to nearest with ties to even.
+            ///
+            /// This accepts the same syntax as [`FromStr`](core::str::FromStr) and gives the same
+            /// result, but as a `const fn` it lets exact constants be written in decimal for
+            /// every format, without needing Rust support for the format.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `s` is not a valid float, with a message describing the
+            /// [kind](crate::ParseErrorKind) of failure.  In a const context, this is a
+            /// compile-time error.
+            pub const fn parse_const(s: &str) -> Self {
+                let mode = crate::RoundingMode::TiesToEven;
+                match crate::decimal::parse_bytes(Self::FORMAT, s.as_bytes(), mode) {
+                    Ok(bits) => Self::from_bits(bits as $u_ty),
+                    Err(err) => err.panic(),
+                }
+            }
+
+            /// Writes `self` in decimal into `buf`, exactly as `Display` does without any flags,
+            /// and returns the written part of `buf`.
+            ///
+            /// This needs neither an allocator nor a [`Formatter`](core::fmt::Formatter), which
+            /// suits `no_std` targets.  The shortest round-tripping digits are written in
+            /// positional notation, so values of extreme magnitude need long buffers: up to 5000
+            /// bytes for `F128`, but fewer than 64 for formats no wider than `F32`.
+            ///
+            /// The digits are computed exactly with big integers on the stack, sized for the
+            /// format.  In optimized builds, this takes about 1 KiB of stack for formats no wider
+            /// than `F32`, 2 KiB for `F64`, and 16 KiB for `F128`; unoptimized builds take about
+            /// three times as much.
+            pub fn format_into<'a>(
+                &self,
+                buf: &'a mut [u8],
+            ) -> Result<&'a str, crate::BufferTooSmall> {
+                crate::decimal::format_into(Self::FORMAT, self.bits as u128, buf)
+            }
+
+            /// Returns the exact value of `self` in decimal, with every significant digit.
+            ///
+            /// Every finite float is a dyadic rational, so its decimal expansion terminates:
+            /// `F64` 0.1 is `0.1000000000000000055511151231257827021181583404541015625`.  The
+            /// expansion can be very long, up to 16496 characters for the least `F128`
+            /// subnormal.  Infinities and NaN are written as by `Display`.
+            ///
+            /// # Features
+            ///
+            /// Requires crate feature `alloc`.
+            #[cfg(feature = "alloc")]
+            pub fn to_exact_decimal(&self) -> alloc::string::String {
+                let mut out = alloc::string::String::new();
+                crate::decimal::write_exact(&mut out, Self::FORMAT, self.bits as u128)
+                    .expect("writing to a String cannot fail");
+                out
+            }
+
+            /// Compares the exact value of `self` with the exact value of the decimal string
+            /// `s`, without rounding `s` to the format first.
+            ///
+            /// For example, `F64` 0.1 is slightly greater than the decimal `0.1`, so comparing
+            /// the two gives `Ordering::Greater`.  `s` may be anything accepted by `FromStr`.
+            /// Values are ordered as by [`Self::total_cmp`], so `-0.0` is less than `"0"`, and the
+            /// spellings of infinity and NaN compare as the corresponding values of `Self` would.
+            pub fn cmp_decimal_str(
+                &self,
+                s: &str,
+            ) -> Result<core::cmp::Ordering, crate::ParseError> {
+                crate::decimal::cmp_decimal(Self::FORMAT, self.bits as u128, s)
+            }
+
+            /// Returns an adapter that formats `self` exactly in hexadecimal, like C's `%a`.
+            ///
+            /// See [`LowerHexFloat`](crate::LowerHexFloat) for details.
+            pub const fn fmt_hex(&self) -> crate::LowerHexFloat {
+                crate::LowerHexFloat::new(Self::FORMAT, self.bits as u128)
+            }
+
+            /// Parses a hexadecimal float, such as `0x1.8p3`, rounding it to nearest with ties
+            /// to even.
+            ///
+            /// The syntax is that of C99 hexadecimal floats, with an optional sign, a `0x` or
+            /// `0X` prefix, and an optional binary exponent.  The spellings of infinity and NaN
+            /// accepted by `FromStr`, such as `inf`, `snan`, and `nan:0x…`, are also accepted, so
+            /// the output of [`Self::fmt_hex`] always parses back to the same value, apart from
+            /// NaN payloads.  Any number of digits may be given: the value is
+            /// rounded exactly once.
+            pub fn from_hex_str(s: &str) -> Result<Self, crate::ParseError> {
+                let bits = crate::hex::parse(Self::FORMAT, s, crate::RoundingMode::TiesToEven)?;
+                Ok(Self::from_bits(bits as $u_ty))
+            }
+        }
     };
 }
 
@@ -396,5 +1880,162 @@ macro_rules! define {
         define_head!($( #[$meta] )* $vis struct $ty; $u_ty);
         define_mid!($ty; $( $rest )*);
         define_tail!($ty; $u_ty; $s_ty; $size_bits; $exp_bits);
+        define_soft!($ty; $u_ty);
+        define_defmt!($ty);
+        define_ufmt!($ty);
+        define_arbitrary!($ty; $u_ty);
+        define_num_primitive!($ty);
+    };
+}
+
+macro_rules! define_num_primitive {
+    ($ty:ident) => {
+        /// Conversions to integers truncate toward zero, and give `None` for NaNs, infinities, and
+        /// values out of range.  Conversions to floats round to nearest with ties to even.
+        #[cfg(feature = "num-traits")]
+        impl num_traits::ToPrimitive for $ty {
+            define_num_primitive!(
+                @to signed;
+                to_i8 -> i8, to_i16 -> i16, to_i32 -> i32, to_i64 -> i64, to_i128 -> i128,
+                to_isize -> isize
+            );
+            define_num_primitive!(
+                @to unsigned;
+                to_u8 -> u8, to_u16 -> u16, to_u32 -> u32, to_u64 -> u64, to_u128 -> u128,
+                to_usize -> usize
+            );
+
+            fn to_f32(&self) -> Option<f32> {
+                let bits = crate::soft::convert(Self::FORMAT, crate::F32::FORMAT, self.bits as u128);
+                Some(f32::from_bits(bits as u32))
+            }
+
+            fn to_f64(&self) -> Option<f64> {
+                let bits = crate::soft::convert(Self::FORMAT, crate::F64::FORMAT, self.bits as u128);
+                Some(f64::from_bits(bits as u64))
+            }
+        }
+
+        /// Every conversion succeeds, rounding to nearest with ties to even.
+        #[cfg(feature = "num-traits")]
+        impl num_traits::FromPrimitive for $ty {
+            fn from_i64(n: i64) -> Option<Self> {
+                Some(Self::from_ratio(n as i128, 0))
+            }
+
+            fn from_u64(n: u64) -> Option<Self> {
+                Some(Self::from_ratio(n as i128, 0))
+            }
+
+            fn from_i128(n: i128) -> Option<Self> {
+                Some(Self::from_ratio(n, 0))
+            }
+
+            fn from_u128(n: u128) -> Option<Self> {
+                if n <= i128::MAX as u128 {
+                    return Some(Self::from_ratio(n as i128, 0));
+                }
+                // Above `i128::MAX`, the low bit stands in for the bit shifted out, which is far
+                // below the rounding point of every format since the top bit is set.
+                Some(Self::from_ratio(((n >> 1) | (n & 1)) as i128, 1))
+            }
+
+            fn from_f32(n: f32) -> Option<Self> {
+                let bits = crate::soft::convert(crate::F32::FORMAT, Self::FORMAT, n.to_bits() as u128);
+                Some(Self::from_bits(bits as _))
+            }
+
+            fn from_f64(n: f64) -> Option<Self> {
+                let bits = crate::soft::convert(crate::F64::FORMAT, Self::FORMAT, n.to_bits() as u128);
+                Some(Self::from_bits(bits as _))
+            }
+        }
+    };
+    (@to signed; $( $m:ident -> $i_ty:ident ),*) => {
+        $(
+            fn $m(&self) -> Option<$i_ty> {
+                let (neg, mag) = crate::soft::to_int(Self::FORMAT, self.bits as u128, 0, crate::RoundingMode::TowardZero)?;
+                if !neg {
+                    $i_ty::try_from(mag).ok()
+                } else if mag <= $i_ty::MIN.unsigned_abs() as u128 {
+                    Some((0 as $i_ty).wrapping_sub(mag as $i_ty))
+                } else {
+                    None
+                }
+            }
+        )*
+    };
+    (@to unsigned; $( $m:ident -> $i_ty:ident ),*) => {
+        $(
+            fn $m(&self) -> Option<$i_ty> {
+                match crate::soft::to_int(Self::FORMAT, self.bits as u128, 0, crate::RoundingMode::TowardZero)? {
+                    (true, 1..) => None,
+                    (_, mag) => $i_ty::try_from(mag).ok(),
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! define_arbitrary {
+    ($ty:ident; $u_ty:ty) => {
+        #[cfg(feature = "arbitrary")]
+        impl<'a> arbitrary::Arbitrary<'a> for $ty {
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                let bits = crate::fuzz::arbitrary_bits(Self::FORMAT, u)?;
+                Ok(Self::from_bits(bits as $u_ty))
+            }
+
+            fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+                // The sign, the class, and at most the whole bits again.
+                (2, Some(2 + size_of::<$u_ty>()))
+            }
+        }
+    };
+}
+
+// Display matches `format_into`, and Debug matches `{:?}`, as `F16 { bits: 15360 }`.
+macro_rules! define_ufmt {
+    ($ty:ident) => {
+        #[cfg(feature = "ufmt")]
+        impl ufmt::uDisplay for $ty {
+            fn fmt<W: ufmt::uWrite + ?Sized>(
+                &self,
+                f: &mut ufmt::Formatter<'_, W>,
+            ) -> Result<(), W::Error> {
+                crate::decimal::write_ufmt(f, Self::FORMAT, self.bits as u128)
+            }
+        }
+
+        #[cfg(feature = "ufmt")]
+        impl ufmt::uDebug for $ty {
+            fn fmt<W: ufmt::uWrite + ?Sized>(
+                &self,
+                f: &mut ufmt::Formatter<'_, W>,
+            ) -> Result<(), W::Error> {
+                f.debug_struct(stringify!($ty))?.field("bits", &self.bits)?.finish()
+            }
+        }
+    };
+}
+
+// The value is converted to the narrowest of `f32` and `f64` that holds it exactly, or to `f64`
+// for F128, since defmt has no other float types.
+macro_rules! define_defmt {
+    ($ty:ident) => {
+        #[cfg(feature = "defmt")]
+        impl defmt::Format for $ty {
+            fn format(&self, f: defmt::Formatter<'_>) {
+                use crate::soft::convert;
+                let (name, bits) = (stringify!($ty), self.bits as u128);
+                if Self::BITS <= 32 {
+                    let x = f32::from_bits(convert(Self::FORMAT, crate::F32::FORMAT, bits) as u32);
+                    defmt::write!(f, "{=str}({:#x} ≈ {=f32})", name, self.bits, x);
+                } else {
+                    let x = f64::from_bits(convert(Self::FORMAT, crate::F64::FORMAT, bits) as u64);
+                    defmt::write!(f, "{=str}({:#x} ≈ {=f64})", name, self.bits, x);
+                }
+            }
+        }
     };
 }
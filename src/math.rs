@@ -0,0 +1,206 @@
+//! Backends for the transcendental functions on the types backed by a Rust float.
+//!
+//! The `std` backend takes precedence over the `libm` backend when both features are enabled.
+
+/// The math functions which the wrapper types forward to, implemented for the Rust floats.
+pub(crate) trait Math: Copy {
+    fn sqrt(self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn log2(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn mul_add(self, a: Self, b: Self) -> Self;
+}
+
+#[cfg(all(feature = "libm", not(feature = "std")))]
+impl Math for f32 {
+    fn sqrt(self) -> Self {
+        libm::sqrtf(self)
+    }
+
+    fn exp(self) -> Self {
+        libm::expf(self)
+    }
+
+    fn ln(self) -> Self {
+        libm::logf(self)
+    }
+
+    fn log2(self) -> Self {
+        libm::log2f(self)
+    }
+
+    fn sin(self) -> Self {
+        libm::sinf(self)
+    }
+
+    fn cos(self) -> Self {
+        libm::cosf(self)
+    }
+
+    fn tan(self) -> Self {
+        libm::tanf(self)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        libm::atan2f(self, other)
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        libm::hypotf(self, other)
+    }
+
+    fn powf(self, n: Self) -> Self {
+        libm::powf(self, n)
+    }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        libm::fmaf(self, a, b)
+    }
+}
+
+#[cfg(all(feature = "libm", not(feature = "std")))]
+impl Math for f64 {
+    fn sqrt(self) -> Self {
+        libm::sqrt(self)
+    }
+
+    fn exp(self) -> Self {
+        libm::exp(self)
+    }
+
+    fn ln(self) -> Self {
+        libm::log(self)
+    }
+
+    fn log2(self) -> Self {
+        libm::log2(self)
+    }
+
+    fn sin(self) -> Self {
+        libm::sin(self)
+    }
+
+    fn cos(self) -> Self {
+        libm::cos(self)
+    }
+
+    fn tan(self) -> Self {
+        libm::tan(self)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        libm::atan2(self, other)
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        libm::hypot(self, other)
+    }
+
+    fn powf(self, n: Self) -> Self {
+        libm::pow(self, n)
+    }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        libm::fma(self, a, b)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Math for f32 {
+    fn sqrt(self) -> Self {
+        f32::sqrt(self)
+    }
+
+    fn exp(self) -> Self {
+        f32::exp(self)
+    }
+
+    fn ln(self) -> Self {
+        f32::ln(self)
+    }
+
+    fn log2(self) -> Self {
+        f32::log2(self)
+    }
+
+    fn sin(self) -> Self {
+        f32::sin(self)
+    }
+
+    fn cos(self) -> Self {
+        f32::cos(self)
+    }
+
+    fn tan(self) -> Self {
+        f32::tan(self)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        f32::atan2(self, other)
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        f32::hypot(self, other)
+    }
+
+    fn powf(self, n: Self) -> Self {
+        f32::powf(self, n)
+    }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        f32::mul_add(self, a, b)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Math for f64 {
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    fn exp(self) -> Self {
+        f64::exp(self)
+    }
+
+    fn ln(self) -> Self {
+        f64::ln(self)
+    }
+
+    fn log2(self) -> Self {
+        f64::log2(self)
+    }
+
+    fn sin(self) -> Self {
+        f64::sin(self)
+    }
+
+    fn cos(self) -> Self {
+        f64::cos(self)
+    }
+
+    fn tan(self) -> Self {
+        f64::tan(self)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        f64::atan2(self, other)
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        f64::hypot(self, other)
+    }
+
+    fn powf(self, n: Self) -> Self {
+        f64::powf(self, n)
+    }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        f64::mul_add(self, a, b)
+    }
+}
@@ -0,0 +1,139 @@
+//! Strategies that generate floats for property tests.
+//!
+//! For each float type there are four strategies, named after the type:
+//!
+//! * `any_f64_bits()` generates every bit pattern with equal probability
+//! * `finite_f64_bits()` generates zeros, subnormal numbers, and normal numbers of either sign
+//! * `nan_f64_bits()` generates every NaN, of either sign and with every payload
+//! * `f64_bits_in(lo, hi)` generates the values from `lo` to `hi` inclusive, in the order given by
+//!   `total_cmp`, so that a range can tell `-0.0` from `+0.0` and can include NaNs of either sign
+//!
+//! Each value has equal probability within its strategy, so that no part of the bit space is
+//! favored.  Values shrink toward `+0.0`, except for [`f64_bits_in`] and the like, which shrink
+//! toward `lo`.
+//!
+//! # Features
+//!
+//! Requires crate feature `proptest`.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use float_bits::{F64, proptest::f64_bits_in};
+//! use proptest::prelude::*;
+//! use proptest::test_runner::TestRunner;
+//!
+//! let mut runner = TestRunner::default();
+//! let result = runner.run(&f64_bits_in(F64::NEG_INFINITY, F64::INFINITY), |x| {
+//!     prop_assert!(!x.is_nan() && x.abs().is_sign_positive());
+//!     Ok(())
+//! });
+//! assert!(result.is_ok());
+//! ```
+
+use ::proptest::prelude::*;
+
+use crate::{BF16, F16, F32, F64, F128};
+
+macro_rules! strategies {
+    ($( $ty:ident: $u_ty:ident / $s_ty:ident => $any:ident, $finite:ident, $nan:ident, $range:ident; )*) => {
+        $(
+            #[doc = concat!("Generates every [`", stringify!($ty), "`] bit pattern.")]
+            pub fn $any() -> impl Strategy<Value = $ty> {
+                any::<$u_ty>().prop_map($ty::from_bits)
+            }
+
+            #[doc = concat!("Generates every finite [`", stringify!($ty), "`] value.")]
+            pub fn $finite() -> impl Strategy<Value = $ty> {
+                (0..=$ty::MAX.bits, any::<bool>()).prop_map(|(abs, neg)| {
+                    $ty::from_bits(abs | if neg { $ty::SIGN_MASK } else { 0 })
+                })
+            }
+
+            #[doc = concat!("Generates every [`", stringify!($ty), "`] NaN.")]
+            pub fn $nan() -> impl Strategy<Value = $ty> {
+                (1..=$ty::MANT_MASK, any::<bool>()).prop_map(|(payload, neg)| {
+                    let sign_bit = if neg { $ty::SIGN_MASK } else { 0 };
+                    $ty::from_bits(sign_bit | $ty::EXP_MASK | payload)
+                })
+            }
+
+            #[doc = concat!("Generates the [`", stringify!($ty), "`] values from `lo` to `hi`")]
+            #[doc = "inclusive, in the order given by `total_cmp`."]
+            #[doc = ""]
+            #[doc = "# Panics"]
+            #[doc = ""]
+            #[doc = "Panics if `hi` is less than `lo`."]
+            pub fn $range(lo: $ty, hi: $ty) -> impl Strategy<Value = $ty> {
+                assert!(lo.total_cmp(hi).is_le(), "empty float range");
+                (lo.sort_bits()..=hi.sort_bits()).prop_map(|key: $s_ty| {
+                    let mask = if key < 0 { $ty::ABS_MASK } else { 0 };
+                    $ty::from_bits(key as $u_ty ^ mask)
+                })
+            }
+        )*
+    };
+}
+
+strategies! {
+    BF16: u16 / i16 => any_bf16_bits, finite_bf16_bits, nan_bf16_bits, bf16_bits_in;
+    F16: u16 / i16 => any_f16_bits, finite_f16_bits, nan_f16_bits, f16_bits_in;
+    F32: u32 / i32 => any_f32_bits, finite_f32_bits, nan_f32_bits, f32_bits_in;
+    F64: u64 / i64 => any_f64_bits, finite_f64_bits, nan_f64_bits, f64_bits_in;
+    F128: u128 / i128 => any_f128_bits, finite_f128_bits, nan_f128_bits, f128_bits_in;
+}
+
+#[cfg(test)]
+mod tests {
+    use ::proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn finite_test(x in finite_f16_bits(), y in finite_f128_bits()) {
+            prop_assert!(x.is_finite() && y.is_finite());
+        }
+
+        #[test]
+        fn nan_test(x in nan_bf16_bits(), y in nan_f64_bits()) {
+            prop_assert!(x.is_nan() && y.is_nan());
+        }
+
+        #[test]
+        fn range_test(x in f32_bits_in(F32::NEG_ONE, F32::NEG_ZERO)) {
+            prop_assert!(x.is_sign_negative() && x.abs().bits <= F32::ONE.bits, "{x:?}");
+        }
+
+        #[test]
+        fn nan_range_test(x in f64_bits_in(F64::INFINITY, F64::from_bits(u64::MAX >> 1))) {
+            prop_assert!(x == F64::INFINITY || (x.is_nan() && x.is_sign_positive()), "{x:?}");
+        }
+
+        #[test]
+        fn any_test(x in any_f128_bits()) {
+            prop_assert_eq!(x, F128::from_bits(x.to_bits()));
+        }
+    }
+
+    #[test]
+    fn range_bounds_test() {
+        let strategy = f16_bits_in(F16::NEG_ZERO, F16::ZERO);
+        use ::proptest::strategy::ValueTree;
+
+        let mut runner = ::proptest::test_runner::TestRunner::deterministic();
+        let mut seen = [false; 2];
+        for _ in 0..64 {
+            let x = strategy.new_tree(&mut runner).unwrap().current();
+            seen[x.is_sign_negative() as usize] = true;
+            assert_eq!(0, x.bits & 0x7fff);
+        }
+        assert_eq!([true, true], seen);
+    }
+
+    #[test]
+    #[should_panic = "empty float range"]
+    fn empty_range_test() {
+        let _ = f64_bits_in(F64::ONE, F64::ZERO);
+    }
+}
@@ -0,0 +1,236 @@
+//! Affine quantization of [`F32`] values to `i8`.
+//!
+//! A value `x` is represented by the integer `q = round(x / scale) + zero_point`, saturated to the
+//! range of `i8`, and `q` stands for `(q − zero_point) × scale`.  The zero point makes the real
+//! zero exactly representable, which matters for padding and for sparse data.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use float_bits::{F32, RoundingMode};
+//! # use float_bits::quant::affine;
+//!
+//! let values = [-1.0, 0.0, 0.5, 3.0].map(F32::from_float);
+//! let (scale, zero_point) = affine::params_i8(values[0], values[3], RoundingMode::TiesToEven);
+//! let mut q = [0; 4];
+//! affine::quantize_i8_into(&values, scale, zero_point, RoundingMode::TiesToEven, &mut q);
+//! assert_eq!([-128, -64, -32, 127], q);
+//! let mut x = [F32::ZERO; 4];
+//! affine::dequantize_i8_into(&q, scale, zero_point, &mut x);
+//! assert_eq!(F32::ZERO, x[1]);
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{F32, RoundingMode};
+
+/// The greatest magnitude that [`round_to_i32`] returns, which saturates any `i8` sum.
+const LIMIT: u128 = 1 << 16;
+
+/// Rounds `x` to an integer according to `mode`, saturating magnitudes above [`LIMIT`].
+///
+/// NaN rounds to zero.
+const fn round_to_i32(x: F32, mode: RoundingMode) -> i32 {
+    match crate::soft::to_int(F32::FORMAT, x.to_bits() as u128, 0, mode) {
+        Some((sign, mag)) => {
+            let mag = if mag > LIMIT { LIMIT } else { mag } as i32;
+            if sign { -mag } else { mag }
+        },
+        None if x.is_nan() => 0,
+        None if x.is_sign_negative() => -(LIMIT as i32),
+        None => LIMIT as i32,
+    }
+}
+
+const fn saturate_i8(n: i32) -> i8 {
+    if n < i8::MIN as i32 {
+        i8::MIN
+    } else if n > i8::MAX as i32 {
+        i8::MAX
+    } else {
+        n as i8
+    }
+}
+
+/// Returns the scale and zero point that map the range from `min` to `max` onto the whole range
+/// of `i8`.
+///
+/// The range is first widened to include zero, then `scale = max / 255 − min / 255`, and
+/// `zero_point = −128 − min / scale` rounded according to `mode`.  Each division and subtraction
+/// is a single `f32` operation, rounded to nearest with ties to even.  If the range is so narrow
+/// that the scale is zero, the scale is 1.
+///
+/// # Panics
+///
+/// Panics if `min` or `max` is not finite, or if `min > max`.
+pub fn params_i8(min: F32, max: F32, mode: RoundingMode) -> (F32, i8) {
+    assert!(min.is_finite() && max.is_finite(), "min and max must be finite");
+    let (min, max) = (min.to_float(), max.to_float());
+    assert!(min <= max, "min must not be greater than max");
+    let (min, max) = (min.min(0.0), max.max(0.0));
+    // Dividing before subtracting cannot overflow.
+    let scale = match max / 255.0 - min / 255.0 {
+        0.0 => 1.0,
+        scale => scale,
+    };
+    let zero_point = round_to_i32(F32::from_float(-128.0 - min / scale), mode);
+    (F32::from_float(scale), saturate_i8(zero_point))
+}
+
+/// Quantizes `values` into `out`, rounding `x / scale` to an integer according to `mode`.
+///
+/// Values outside of the range saturate to `i8::MIN` or `i8::MAX`, and NaN quantizes to
+/// `zero_point`, like zero.
+///
+/// # Panics
+///
+/// Panics if `scale` is not positive and finite, or if `values` and `out` have different lengths.
+pub fn quantize_i8_into(
+    values: &[F32],
+    scale: F32,
+    zero_point: i8,
+    mode: RoundingMode,
+    out: &mut [i8],
+) {
+    assert!(scale.to_float() > 0.0 && scale.is_finite(), "scale must be positive and finite");
+    assert_eq!(values.len(), out.len(), "slices must have the same length");
+    let scale = scale.to_float();
+    for (q, x) in out.iter_mut().zip(values) {
+        let n = round_to_i32(F32::from_float(x.to_float() / scale), mode);
+        *q = saturate_i8(n + zero_point as i32);
+    }
+}
+
+/// Quantizes `values`, rounding `x / scale` to nearest with ties to even.
+///
+/// This is [`quantize_i8_into`] with [`RoundingMode::TiesToEven`], the rounding of
+/// `round_ties_even` and of most inference runtimes.
+///
+/// # Features
+///
+/// Available only with feature: `alloc`.
+///
+/// # Panics
+///
+/// Panics if `scale` is not positive and finite.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::F32;
+/// # use float_bits::quant::affine;
+///
+/// let values = [-0.25, 0.0, 0.125, 0.375, 100.0].map(F32::from_float);
+/// let q = affine::quantize_i8(&values, F32::from_float(0.25), 10);
+/// assert_eq!(vec![9, 10, 10, 12, 127], q);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn quantize_i8(values: &[F32], scale: F32, zero_point: i8) -> Vec<i8> {
+    let mut out = vec![0; values.len()];
+    quantize_i8_into(values, scale, zero_point, RoundingMode::TiesToEven, &mut out);
+    out
+}
+
+/// Dequantizes `values` into `out`, as `(q − zero_point) × scale`.
+///
+/// The difference is exact, so the only rounding is that of the `f32` multiplication.
+///
+/// # Panics
+///
+/// Panics if `values` and `out` have different lengths.
+pub fn dequantize_i8_into(values: &[i8], scale: F32, zero_point: i8, out: &mut [F32]) {
+    assert_eq!(values.len(), out.len(), "slices must have the same length");
+    let scale = scale.to_float();
+    for (x, q) in out.iter_mut().zip(values) {
+        *x = F32::from_float((*q as i32 - zero_point as i32) as f32 * scale);
+    }
+}
+
+/// Dequantizes `values`, as `(q − zero_point) × scale`.
+///
+/// # Features
+///
+/// Available only with feature: `alloc`.
+#[cfg(feature = "alloc")]
+pub fn dequantize_i8(values: &[i8], scale: F32, zero_point: i8) -> Vec<F32> {
+    let mut out = vec![F32::ZERO; values.len()];
+    dequantize_i8_into(values, scale, zero_point, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_to_i32_test() {
+        let mode = RoundingMode::TiesToEven;
+        assert_eq!(2, round_to_i32(F32::from_float(2.5), mode));
+        assert_eq!(-2, round_to_i32(F32::from_float(-2.5), mode));
+        assert_eq!(-3, round_to_i32(F32::from_float(-2.5), RoundingMode::TiesToAway));
+        assert_eq!(-3, round_to_i32(F32::from_float(-2.5), RoundingMode::TowardNegative));
+        assert_eq!(65536, round_to_i32(F32::from_float(1e30), mode));
+        assert_eq!(-65536, round_to_i32(F32::NEG_INFINITY, mode));
+        assert_eq!(0, round_to_i32(F32::NAN, mode));
+    }
+
+    #[test]
+    fn params_i8_test() {
+        let f = F32::from_float;
+        let (scale, zero_point) = params_i8(f(-1.0), f(3.0), RoundingMode::TiesToEven);
+        assert_eq!(f(4.0 / 255.0), scale);
+        assert_eq!(-64, zero_point);
+        assert_eq!(-65, params_i8(f(-1.0), f(3.0), RoundingMode::TowardNegative).1);
+
+        // Ranges are widened to include zero.
+        assert_eq!((f(1.0 / 255.0), -128), params_i8(f(0.5), f(1.0), RoundingMode::TiesToEven));
+        assert_eq!((f(1.0 / 255.0), 127), params_i8(f(-1.0), f(-0.5), RoundingMode::TiesToEven));
+        assert_eq!((F32::ONE, -128), params_i8(F32::ZERO, F32::ZERO, RoundingMode::TiesToEven));
+
+        let (scale, _) = params_i8(F32::MIN, F32::MAX, RoundingMode::TiesToEven);
+        assert!(scale.is_finite());
+    }
+
+    #[test]
+    #[should_panic = "min must not be greater than max"]
+    fn params_i8_order_test() {
+        params_i8(F32::ONE, F32::ZERO, RoundingMode::TiesToEven);
+    }
+
+    #[test]
+    fn quantize_i8_into_test() {
+        let values = [-1.0, -0.75, 0.0, 0.25, 0.5, 0.75, 1e9, f32::NAN].map(F32::from_float);
+        let scale = F32::from_float(0.5);
+        let mut q = [0; 8];
+        quantize_i8_into(&values, scale, -3, RoundingMode::TiesToEven, &mut q);
+        assert_eq!([-5, -5, -3, -3, -2, -1, 127, -3], q);
+        quantize_i8_into(&values, scale, -3, RoundingMode::TowardZero, &mut q);
+        assert_eq!([-5, -4, -3, -3, -2, -2, 127, -3], q);
+
+        let mut x = [F32::ZERO; 8];
+        dequantize_i8_into(&q, scale, -3, &mut x);
+        assert_eq!([-1.0, -0.5, 0.0, 0.0, 0.5, 0.5, 65.0, 0.0].map(F32::from_float), x);
+    }
+
+    #[test]
+    #[should_panic = "scale must be positive and finite"]
+    fn quantize_i8_into_scale_test() {
+        quantize_i8_into(&[F32::ONE], F32::NEG_ONE, 0, RoundingMode::TiesToEven, &mut [0]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn round_trip_test() {
+        let values: Vec<F32> = (-50..50).map(|i| F32::from_float(i as f32 / 16.0)).collect();
+        let (scale, zero_point) = params_i8(values[0], values[99], RoundingMode::TiesToEven);
+        let q = quantize_i8(&values, scale, zero_point);
+        let x = dequantize_i8(&q, scale, zero_point);
+        for (a, b) in values.iter().zip(&x) {
+            assert!((a.to_float() - b.to_float()).abs() <= scale.to_float() / 2.0, "{a} {b}");
+        }
+        assert_eq!(F32::ZERO, x[50]);
+    }
+}
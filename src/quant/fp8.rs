@@ -0,0 +1,117 @@
+//! Scaled quantization of [`F32`] values to [`F8E4M3`].
+//!
+//! A tensor is stored as E4M3 values `q` with a single `f32` scale, and `q` stands for
+//! `q × scale`.  [`absmax_scale`] chooses the scale that maps the greatest magnitude onto
+//! [`F8E4M3::MAX`], so that the whole range of the format is used.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use float_bits::{F8E4M3, F32};
+//! # use float_bits::quant::fp8;
+//!
+//! let values = [-0.5, 0.0, 0.1, 2.0].map(F32::from_float);
+//! let scale = fp8::absmax_scale(&values);
+//! assert_eq!(F32::from_float(2.0 / 448.0), scale);
+//! let mut q = [F8E4M3::ZERO; 4];
+//! fp8::quantize_fp8(&values, &mut q, scale);
+//! assert_eq!([0xee, 0x00, 0x5b, 0x7e], q.map(|x| x.to_bits()));
+//! let mut x = [F32::ZERO; 4];
+//! fp8::dequantize_fp8(&q, &mut x, scale);
+//! assert_eq!([-0.5, 0.0, 0.09821429, 2.0], x.map(|x| x.to_float()));
+//! ```
+
+use crate::{F8E4M3, F32};
+
+/// Returns the scale that maps the greatest finite magnitude in `values` onto [`F8E4M3::MAX`].
+///
+/// The scale is `absmax / 448`, a single `f32` division rounded to nearest with ties to even, so
+/// the greatest magnitude may quantize to a value just above `448`, which saturates.  NaNs and
+/// infinities are ignored.  If the scale is zero, as for an empty slice or one of zeros, the scale
+/// is 1.
+pub fn absmax_scale(values: &[F32]) -> F32 {
+    let absmax =
+        values.iter().map(|x| x.abs().to_float()).filter(|x| x.is_finite()).fold(0.0f32, f32::max);
+    match absmax / F8E4M3::MAX.to_f32() {
+        0.0 => F32::ONE,
+        scale => F32::from_float(scale),
+    }
+}
+
+/// Quantizes `src` into `dst`, converting `x / scale` with [`F8E4M3::from_f32_saturating`].
+///
+/// The division is a single `f32` operation.  Since `f32` has more than twice the precision of
+/// E4M3, rounding the quotient twice gives the same result as rounding it once, so each element is
+/// the correctly rounded quotient, saturated to the finite range.  NaN quantizes to NaN.
+///
+/// The loop is free of branches on the values, so the compiler can vectorize it.
+///
+/// # Panics
+///
+/// Panics if `scale` is not positive and finite, or if `src` and `dst` have different lengths.
+pub fn quantize_fp8(src: &[F32], dst: &mut [F8E4M3], scale: F32) {
+    assert!(scale.to_float() > 0.0 && scale.is_finite(), "scale must be positive and finite");
+    assert_eq!(src.len(), dst.len(), "slices must have the same length");
+    let scale = scale.to_float();
+    for (q, x) in dst.iter_mut().zip(src) {
+        let bits = (x.to_float() / scale).to_bits();
+        *q = F8E4M3::from_bits(F8E4M3::round_f32_bits(bits, true));
+    }
+}
+
+/// Dequantizes `src` into `dst`, as `q × scale`.
+///
+/// The conversion of `q` to `f32` is exact, so the only rounding is that of the `f32`
+/// multiplication.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+pub fn dequantize_fp8(src: &[F8E4M3], dst: &mut [F32], scale: F32) {
+    assert_eq!(src.len(), dst.len(), "slices must have the same length");
+    let scale = scale.to_float();
+    for (x, q) in dst.iter_mut().zip(src) {
+        *x = F32::from_float(q.to_f32() * scale);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn absmax_scale_test() {
+        let f = F32::from_float;
+        assert_eq!(f(1.0), absmax_scale(&[f(-448.0), f(1.0)]));
+        assert_eq!(f(0.5), absmax_scale(&[f(3.0), F32::NAN, f(-224.0), F32::NEG_INFINITY]));
+        assert_eq!(F32::ONE, absmax_scale(&[]));
+        assert_eq!(F32::ONE, absmax_scale(&[F32::NEG_ZERO, F32::INFINITY]));
+    }
+
+    #[test]
+    fn quantize_fp8_test() {
+        let f = F32::from_float;
+        let src = [f(1.0), f(-1000.0), f(0.0009765625), f(0.0009765626), F32::NAN, f(2.25)];
+        let mut dst = [F8E4M3::ZERO; 6];
+        quantize_fp8(&src, &mut dst, f(1.0));
+        assert_eq!([0x38, 0xfe, 0x00, 0x01, 0x7f, 0x41], dst.map(|x| x.to_bits()));
+
+        // 0.53125 lies halfway between 0.5 and 0.5625, and rounds to the even mantissa.
+        quantize_fp8(&[f(1.0625); 6], &mut dst, f(2.0));
+        assert_eq!(0x30, dst[0].to_bits());
+
+        // Every value survives the round trip with a power-of-two scale.
+        let all: [F8E4M3; 256] = core::array::from_fn(|i| F8E4M3::from_bits(i as u8));
+        let mut x = [F32::ZERO; 256];
+        let mut q = [F8E4M3::ZERO; 256];
+        dequantize_fp8(&all, &mut x, f(0.25));
+        quantize_fp8(&x, &mut q, f(0.25));
+        assert_eq!(all, q);
+    }
+
+    #[test]
+    #[should_panic = "scale must be positive and finite"]
+    fn quantize_fp8_scale_test() {
+        quantize_fp8(&[], &mut [], F32::ZERO);
+    }
+}
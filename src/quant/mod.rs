@@ -0,0 +1,10 @@
+//! Quantization of floats to small integers and to FP8, for storing and exchanging model weights
+//! and activations.
+//!
+//! Every rounding step is spelled out, either as an explicit [`RoundingMode`](crate::RoundingMode)
+//! or as a single correctly rounded `f32` operation, and rounding to an integer is done in
+//! software.  The results are therefore the same bits on every platform, unlike code that relies
+//! on the rounding of a float-to-int cast or of a platform's SIMD instructions.
+
+pub mod affine;
+pub mod fp8;
@@ -0,0 +1,281 @@
+//! Random floats with the `rand` crate.
+//!
+//! [`StandardUniform`], [`Open01`], and [`OpenClosed01`] give every type the same distributions as
+//! `rand` gives `f32` and `f64`: the multiples of `2^-p` in the unit interval, where `p` is the
+//! precision of the format, with equal probability.  [`F32`] and [`F64`] use the native floats, so
+//! they match `rand` exactly for the same RNG; the other formats build the value from random bits.
+//!
+//! Ranges sample a uniform `f64` between the bounds and round it to nearest with ties to even, so
+//! they are limited to formats that `f64` holds exactly.
+
+use core::marker::PhantomData;
+
+use rand::Rng;
+use rand::distr::uniform::{Error, SampleBorrow, SampleUniform, UniformFloat, UniformSampler};
+use rand::distr::{Distribution, Open01, OpenClosed01, StandardUniform};
+
+use crate::soft::convert;
+use crate::{BF16, F16, F32, F64, F128};
+
+/// Returns `count` random bits, in the low bits of the result.
+fn random_bits<R: Rng + ?Sized>(rng: &mut R, count: u32) -> u128 {
+    let (bits, width) = match count {
+        0..=32 => (rng.next_u32() as u128, 32),
+        33..=64 => (rng.next_u64() as u128, 64),
+        _ => (rng.random::<u128>(), 128),
+    };
+    bits >> (width - count)
+}
+
+macro_rules! native_distributions {
+    ($( $ty:ident / $f_ty:ident ),*) => {
+        $(
+            impl Distribution<$ty> for StandardUniform {
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                    $ty::from_float(Distribution::<$f_ty>::sample(self, rng))
+                }
+            }
+
+            impl Distribution<$ty> for Open01 {
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                    $ty::from_float(Distribution::<$f_ty>::sample(self, rng))
+                }
+            }
+
+            impl Distribution<$ty> for OpenClosed01 {
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                    $ty::from_float(Distribution::<$f_ty>::sample(self, rng))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! bit_distributions {
+    ($( $ty:ident ),*) => {
+        $(
+            // Each distribution gives `n × 2^-p` for a random `n`, which is always exact.
+            impl Distribution<$ty> for StandardUniform {
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                    let p = $ty::FORMAT.precision();
+                    $ty::from_ratio(random_bits(rng, p) as i128, -(p as i32))
+                }
+            }
+
+            impl Distribution<$ty> for Open01 {
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                    let p = $ty::FORMAT.precision();
+                    $ty::from_ratio((2 * random_bits(rng, p - 1) + 1) as i128, -(p as i32))
+                }
+            }
+
+            impl Distribution<$ty> for OpenClosed01 {
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                    let p = $ty::FORMAT.precision();
+                    $ty::from_ratio((random_bits(rng, p) + 1) as i128, -(p as i32))
+                }
+            }
+        )*
+    };
+}
+
+native_distributions!(F32 / f32, F64 / f64);
+bit_distributions!(BF16, F16, F128);
+
+/// A distribution over bit patterns: every value of the type, including each NaN and subnormal,
+/// has equal probability.
+///
+/// This differs from the value-uniform distributions, which never give NaN, infinity, or a
+/// negative number.  It suits differential testing of math routines, where every encoding should
+/// be reached.  Half of the samples are negative, and about one in 2^`k` of them is a NaN or
+/// infinity, where `k` is the number of exponent bits.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::{BitUniform, F16};
+/// use rand::Rng;
+///
+/// fn check_sqrt(rng: &mut impl Rng) {
+///     let x: F16 = rng.sample(BitUniform);
+///     // ... compare a square root routine against a reference on `x` ...
+/// }
+/// ```
+///
+/// # Features
+///
+/// Requires crate feature `rand`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct BitUniform;
+
+macro_rules! bit_uniform {
+    ($( $ty:ident / $u_ty:ident ),*) => {
+        $(
+            impl Distribution<$ty> for BitUniform {
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                    $ty::from_bits(rng.random::<$u_ty>())
+                }
+            }
+        )*
+    };
+}
+
+bit_uniform!(BF16 / u16, F16 / u16, F32 / u32, F64 / u64, F128 / u128);
+
+/// The [`UniformSampler`] for the float types, which makes [`Rng::random_range`] work for them.
+///
+/// This samples `f64` uniformly between the bounds, as [`UniformFloat`] does, and rounds the
+/// result to nearest with ties to even.  A half-open range never gives its upper bound: a sample
+/// that rounds to it is drawn again.
+///
+/// # Features
+///
+/// Requires crate feature `rand`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UniformFloatBits<T> {
+    inner: UniformFloat<f64>,
+    high: f64,
+    inclusive: bool,
+    marker: PhantomData<T>,
+}
+
+macro_rules! uniform {
+    ($( $ty:ident ),*) => {
+        $(
+            impl SampleUniform for $ty {
+                type Sampler = UniformFloatBits<$ty>;
+            }
+
+            impl UniformSampler for UniformFloatBits<$ty> {
+                type X = $ty;
+
+                fn new<B1, B2>(low: B1, high: B2) -> Result<Self, Error>
+                where
+                    B1: SampleBorrow<$ty> + Sized,
+                    B2: SampleBorrow<$ty> + Sized,
+                {
+                    let (low, high) = (to_f64(*low.borrow()), to_f64(*high.borrow()));
+                    let inner = UniformFloat::<f64>::new(low, high)?;
+                    Ok(Self { inner, high, inclusive: false, marker: PhantomData })
+                }
+
+                fn new_inclusive<B1, B2>(low: B1, high: B2) -> Result<Self, Error>
+                where
+                    B1: SampleBorrow<$ty> + Sized,
+                    B2: SampleBorrow<$ty> + Sized,
+                {
+                    let (low, high) = (to_f64(*low.borrow()), to_f64(*high.borrow()));
+                    let inner = UniformFloat::<f64>::new_inclusive(low, high)?;
+                    Ok(Self { inner, high, inclusive: true, marker: PhantomData })
+                }
+
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                    loop {
+                        let x = self.inner.sample(rng);
+                        let bits = convert(F64::FORMAT, $ty::FORMAT, x.to_bits() as u128);
+                        let y = $ty::from_bits(bits as _);
+                        if self.inclusive || to_f64(y) != self.high {
+                            return y;
+                        }
+                    }
+                }
+            }
+
+            impl ToF64 for $ty {
+                fn to_f64(self) -> f64 {
+                    f64::from_bits(convert($ty::FORMAT, F64::FORMAT, self.bits as u128) as u64)
+                }
+            }
+        )*
+    };
+}
+
+trait ToF64 {
+    fn to_f64(self) -> f64;
+}
+
+fn to_f64<T: ToF64>(x: T) -> f64 {
+    x.to_f64()
+}
+
+uniform!(BF16, F16, F32, F64);
+
+#[cfg(test)]
+mod tests {
+    use rand::distr::{Distribution, Open01, OpenClosed01, StandardUniform, Uniform};
+    use rand::{Rng, RngCore};
+
+    use super::BitUniform;
+    use crate::{BF16, F16, F32, F64, F128};
+
+    /// A linear congruential generator; with a multiplier of one and no increment, it returns
+    /// the same word forever.
+    #[derive(Clone)]
+    struct Lcg(u64, u64, u64);
+
+    impl RngCore for Lcg {
+        fn next_u32(&mut self) -> u32 {
+            (self.next_u64() >> 32) as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0 = self.0.wrapping_mul(self.1).wrapping_add(self.2);
+            self.0
+        }
+
+        fn fill_bytes(&mut self, dst: &mut [u8]) {
+            rand::rand_core::impls::fill_bytes_via_next(self, dst)
+        }
+    }
+
+    #[test]
+    fn distributions_test() {
+        // With an RNG that returns all zeros or all ones, the bounds of each interval come out.
+        let (mut zeros, mut ones) = (Lcg(0, 1, 0), Lcg(u64::MAX, 1, 0));
+        assert_eq!(F16::ZERO, StandardUniform.sample(&mut zeros));
+        assert_eq!(F16::from_bits(0x3bff), StandardUniform.sample(&mut ones));
+        assert_eq!(F16::from_bits(0x1000), Open01.sample(&mut zeros));
+        assert_eq!(F16::from_bits(0x3bff), Open01.sample(&mut ones));
+        assert_eq!(BF16::from_bits(0x3b80), OpenClosed01.sample(&mut zeros));
+        assert_eq!(BF16::ONE, OpenClosed01.sample(&mut ones));
+        assert_eq!(F128::from_bits(0x3f8e << 112), OpenClosed01.sample(&mut zeros));
+        let x: F128 = StandardUniform.sample(&mut ones);
+        assert_eq!(F128::ONE.to_bits() - 1, x.to_bits());
+
+        let mut rng = Lcg(1, 6364136223846793005, 1442695040888963407);
+        let mut copy = rng.clone();
+        assert_eq!(F64::from_float(copy.random()), rng.random());
+        assert_eq!(F32::from_float(copy.sample(Open01)), rng.sample(Open01));
+    }
+
+    #[test]
+    fn uniform_test() {
+        let mut rng = Lcg(2, 6364136223846793005, 1442695040888963407);
+        for _ in 0..1000 {
+            let x = rng.random_range(F64::ZERO..F64::ONE);
+            assert!(F64::ZERO <= x && x < F64::ONE, "{x}");
+            let y = rng.random_range(F16::NEG_ONE..=F16::from_bits(0xbbff));
+            assert!(y == F16::NEG_ONE || y == F16::from_bits(0xbbff), "{y}");
+            assert_eq!(BF16::ONE, rng.random_range(BF16::ONE..BF16::from_bits(0x3f81)));
+        }
+        assert!(Uniform::new(F32::ONE, F32::ONE).is_err());
+        assert!(Uniform::new(F32::ZERO, F32::INFINITY).is_err());
+    }
+
+    #[test]
+    fn bit_uniform_test() {
+        assert_eq!(F16::from_bits(0xffff), Lcg(u64::MAX, 1, 0).sample(BitUniform));
+        assert_eq!(F128::from_bits(u128::MAX), Lcg(u64::MAX, 1, 0).sample(BitUniform));
+
+        // Every exponent field comes up, NaNs and subnormals included.
+        let mut rng = Lcg(3, 6364136223846793005, 1442695040888963407);
+        let (mut nan, mut subnormal, mut negative) = (0, 0, 0);
+        for _ in 0..4096 {
+            let x: BF16 = rng.sample(BitUniform);
+            nan += x.is_nan() as u32;
+            subnormal += x.is_subnormal() as u32;
+            negative += x.is_sign_negative() as u32;
+        }
+        assert!(nan > 0 && subnormal > 0 && (1800..2300).contains(&negative));
+    }
+}
@@ -0,0 +1,86 @@
+use core::fmt;
+
+use crate::{BF16, F16, F32, F64, F128, FloatBits};
+
+/// A float canonicalized for use as a hash or map key.
+///
+/// Construction maps `-0.0` to `+0.0` and every NaN to the positive quiet NaN with no payload,
+/// [`T::QNAN`](FloatBits::QNAN), so that values equal under IEEE 754 semantics become the same
+/// key, and every NaN becomes one key that is equal to itself.  Any other value is kept as is.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::collections::HashSet;
+/// # use float_bits::F64;
+/// # use float_bits::refined::CanonicalKey;
+///
+/// let values = [F64::ZERO, F64::NEG_ZERO, F64::QNAN, F64::NEG_SNAN, F64::ONE];
+/// let keys: HashSet<_> = values.into_iter().map(CanonicalKey::new).collect();
+/// assert_eq!(3, keys.len());
+/// assert!(keys.contains(&CanonicalKey::new(F64::NAN)));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct CanonicalKey<T>(T);
+
+/// A [`BF16`] canonicalized for use as a key.
+pub type CanonicalKeyBF16 = CanonicalKey<BF16>;
+
+/// An [`F16`] canonicalized for use as a key.
+pub type CanonicalKeyF16 = CanonicalKey<F16>;
+
+/// An [`F32`] canonicalized for use as a key.
+pub type CanonicalKeyF32 = CanonicalKey<F32>;
+
+/// An [`F64`] canonicalized for use as a key.
+pub type CanonicalKeyF64 = CanonicalKey<F64>;
+
+/// An [`F128`] canonicalized for use as a key.
+pub type CanonicalKeyF128 = CanonicalKey<F128>;
+
+impl<T: FloatBits> CanonicalKey<T> {
+    /// Wraps the canonical form of `value`.
+    pub fn new(value: T) -> Self {
+        if value.is_nan() {
+            Self(T::QNAN)
+        } else if value.is_zero() {
+            Self(T::ZERO)
+        } else {
+            Self(value)
+        }
+    }
+
+    /// Returns the canonicalized value.
+    pub const fn get(self) -> T {
+        self.0
+    }
+}
+
+impl<T: FloatBits> From<T> for CanonicalKey<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: FloatBits> fmt::Display for CanonicalKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_key_test() {
+        assert_eq!(CanonicalKeyF64::new(F64::ZERO), CanonicalKeyF64::new(F64::NEG_ZERO));
+        assert_eq!(F32::ZERO.to_bits(), CanonicalKeyF32::new(F32::NEG_ZERO).get().to_bits());
+        assert_eq!(F16::QNAN.to_bits(), CanonicalKeyF16::from(F16::NEG_SNAN).get().to_bits());
+        let payload = BF16::from_bits(0xffc1);
+        assert_eq!(CanonicalKey(BF16::QNAN), CanonicalKeyBF16::new(payload));
+        assert_eq!(CanonicalKey(F128::NEG_ONE), CanonicalKeyF128::new(F128::NEG_ONE));
+        assert_ne!(CanonicalKeyF64::new(F64::MIN_POSITIVE), CanonicalKeyF64::new(F64::ZERO));
+    }
+}
@@ -0,0 +1,184 @@
+use core::fmt;
+use core::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use super::InvariantError;
+use crate::{BF16, F16, F32, F64, F128, FloatBits};
+
+/// A float that is neither NaN nor infinite.
+///
+/// Arithmetic on finite values can still overflow to infinity, or give NaN for `0 / 0` and
+/// `x % 0`, so the operations return a `Result` rather than implementing the operator traits.
+/// Only negation and [`abs`](Self::abs), which cannot leave the finite values, are infallible.
+///
+/// With crate feature `serde`, the wrapper serializes as the wrapped value, and deserializing
+/// fails for a value that is not finite.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::F64;
+/// # use float_bits::refined::{Finite, InvariantError};
+///
+/// let x = Finite::try_from(F64::MAX).unwrap();
+/// assert_eq!(Err(InvariantError::Infinite), x.try_add(x));
+/// assert_eq!(Ok(F64::ZERO), x.try_sub(x).map(Finite::get));
+/// assert_eq!(Err(InvariantError::Nan), Finite::try_from(f64::NAN));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Finite<T>(T);
+
+/// A [`BF16`] that is neither NaN nor infinite.
+pub type FiniteBF16 = Finite<BF16>;
+
+/// An [`F16`] that is neither NaN nor infinite.
+pub type FiniteF16 = Finite<F16>;
+
+/// An [`F32`] that is neither NaN nor infinite.
+pub type FiniteF32 = Finite<F32>;
+
+/// An [`F64`] that is neither NaN nor infinite.
+pub type FiniteF64 = Finite<F64>;
+
+/// An [`F128`] that is neither NaN nor infinite.
+pub type FiniteF128 = Finite<F128>;
+
+impl<T: FloatBits> Finite<T> {
+    /// Wraps `value`, or returns an error if it is NaN or infinite.
+    pub fn new(value: T) -> Result<Self, InvariantError> {
+        if value.is_nan() {
+            Err(InvariantError::Nan)
+        } else if value.is_infinite() {
+            Err(InvariantError::Infinite)
+        } else {
+            Ok(Self(value))
+        }
+    }
+
+    /// Returns the wrapped value.
+    pub const fn get(self) -> T {
+        self.0
+    }
+
+    /// Returns the absolute value.
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// Returns `self + rhs`, or an error if the sum overflows to infinity.
+    pub fn try_add(self, rhs: Self) -> Result<Self, InvariantError>
+    where
+        T: Add<Output = T>,
+    {
+        Self::new(self.0 + rhs.0)
+    }
+
+    /// Returns `self - rhs`, or an error if the difference overflows to infinity.
+    pub fn try_sub(self, rhs: Self) -> Result<Self, InvariantError>
+    where
+        T: Sub<Output = T>,
+    {
+        Self::new(self.0 - rhs.0)
+    }
+
+    /// Returns `self * rhs`, or an error if the product overflows to infinity.
+    pub fn try_mul(self, rhs: Self) -> Result<Self, InvariantError>
+    where
+        T: Mul<Output = T>,
+    {
+        Self::new(self.0 * rhs.0)
+    }
+
+    /// Returns `self / rhs`, or an error if the quotient overflows to infinity, as it does for a
+    /// nonzero value divided by zero, or is NaN, as it is for zero divided by zero.
+    pub fn try_div(self, rhs: Self) -> Result<Self, InvariantError>
+    where
+        T: Div<Output = T>,
+    {
+        Self::new(self.0 / rhs.0)
+    }
+
+    /// Returns `self % rhs`, or an error if `rhs` is zero, which gives NaN.
+    pub fn try_rem(self, rhs: Self) -> Result<Self, InvariantError>
+    where
+        T: Rem<Output = T>,
+    {
+        Self::new(self.0 % rhs.0)
+    }
+}
+
+impl<T: FloatBits> Neg for Finite<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(self.0.neg())
+    }
+}
+
+impl<T: FloatBits> fmt::Display for Finite<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: FloatBits + ::serde::Serialize> ::serde::Serialize for Finite<T> {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: FloatBits + ::serde::Deserialize<'de>> ::serde::Deserialize<'de> for Finite<T> {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::new(T::deserialize(deserializer)?).map_err(::serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finite_test() {
+        assert_eq!(Err(InvariantError::Nan), FiniteBF16::new(BF16::QNAN));
+        assert_eq!(Err(InvariantError::Infinite), FiniteF16::new(F16::NEG_INFINITY));
+        assert_eq!(Ok(F128::MAX), FiniteF128::new(F128::MAX).map(Finite::get));
+        assert_eq!(Err(InvariantError::Infinite), FiniteF32::try_from(f32::INFINITY));
+
+        let max = FiniteF64::new(F64::MAX).unwrap();
+        assert_eq!(F64::MIN, (-max).get());
+        assert_eq!(max, (-max).abs());
+        assert_eq!(F64::NEG_ZERO.to_bits(), (-Finite(F64::ZERO)).get().to_bits());
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        let f = |x| FiniteF32::try_from(x).unwrap();
+        assert_eq!(Ok(f(3.5)), f(1.5).try_add(f(2.0)));
+        assert_eq!(Ok(f(-0.5)), f(1.5).try_sub(f(2.0)));
+        assert_eq!(Ok(f(3.0)), f(1.5).try_mul(f(2.0)));
+        assert_eq!(Ok(f(0.75)), f(1.5).try_div(f(2.0)));
+        assert_eq!(Ok(f(1.5)), f(1.5).try_rem(f(2.0)));
+        assert_eq!(Err(InvariantError::Infinite), f(f32::MAX).try_mul(f(2.0)));
+        assert_eq!(Err(InvariantError::Infinite), f(-1.0).try_div(f(0.0)));
+        assert_eq!(Err(InvariantError::Nan), f(0.0).try_div(f(0.0)));
+        assert_eq!(Err(InvariantError::Nan), f(1.0).try_rem(f(0.0)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        use ::serde::Deserialize;
+        use ::serde::de::IntoDeserializer;
+        use ::serde::de::value::{Error, MapDeserializer};
+
+        let from_map = |bits: u64| {
+            let map = MapDeserializer::<_, Error>::new([("bits", bits)].into_iter());
+            FiniteF64::deserialize(map.into_deserializer())
+        };
+        assert_eq!(Ok(Finite(F64::ONE)), from_map(F64::ONE.to_bits()));
+        assert!(from_map(F64::INFINITY.to_bits()).is_err());
+        assert!(from_map(F64::NAN.to_bits()).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+//! Wrappers that guarantee an invariant of the value they hold, such as not being NaN.
+//!
+//! Each wrapper checks its invariant when it is constructed, so that code receiving one can rely
+//! on it without checking again.  The wrappers are generic over the format, with an alias for
+//! each format, and hold the value unchanged, bits included.
+//!
+//! Every wrapper that can reject a value is constructed the same way: `new`, or `TryFrom` from
+//! the format or, for [`F32`] and [`F64`], from the native float, and every failure is an
+//! [`InvariantError`].  [`CanonicalKey`] accepts every value, and converts it instead.
+
+use core::fmt;
+
+use crate::{BF16, F16, F32, F64, F128};
+
+mod canonical;
+mod finite;
+mod non_nan;
+mod sign;
+mod unit_interval;
+
+pub use canonical::{
+    CanonicalKey,
+    CanonicalKeyBF16,
+    CanonicalKeyF16,
+    CanonicalKeyF32,
+    CanonicalKeyF64,
+    CanonicalKeyF128,
+};
+pub use finite::{Finite, FiniteBF16, FiniteF16, FiniteF32, FiniteF64, FiniteF128};
+pub use non_nan::{NonNan, NonNanBF16, NonNanF16, NonNanF32, NonNanF64, NonNanF128};
+pub use sign::{
+    NonNegative,
+    NonNegativeBF16,
+    NonNegativeF16,
+    NonNegativeF32,
+    NonNegativeF64,
+    NonNegativeF128,
+    Positive,
+    PositiveBF16,
+    PositiveF16,
+    PositiveF32,
+    PositiveF64,
+    PositiveF128,
+};
+pub use unit_interval::{
+    UnitInterval,
+    UnitIntervalBF16,
+    UnitIntervalF16,
+    UnitIntervalF32,
+    UnitIntervalF64,
+    UnitIntervalF128,
+};
+
+/// The invariant that a value failed to satisfy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum InvariantError {
+    /// The value was NaN.
+    Nan,
+
+    /// The value was infinite.
+    Infinite,
+
+    /// The value was outside of the range of the wrapper.
+    OutOfRange {
+        /// A description of the range, such as `"greater than zero"`.
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Nan => f.write_str("value is NaN"),
+            Self::Infinite => f.write_str("value is infinite"),
+            Self::OutOfRange { expected } => {
+                write!(f, "value is out of range: expected {expected}")
+            },
+        }
+    }
+}
+
+impl core::error::Error for InvariantError {}
+
+// A blanket `impl<T> TryFrom<T>` would overlap the one in `core` for `Into`, so each format gets
+// its own.
+macro_rules! define_try_from {
+    ($($name:ident),*) => {
+        $(
+            define_try_from!(@format $name; BF16, F16, F32, F64, F128);
+
+            impl TryFrom<f32> for $name<F32> {
+                type Error = InvariantError;
+
+                fn try_from(value: f32) -> Result<Self, InvariantError> {
+                    Self::new(F32::from_float(value))
+                }
+            }
+
+            impl TryFrom<f64> for $name<F64> {
+                type Error = InvariantError;
+
+                fn try_from(value: f64) -> Result<Self, InvariantError> {
+                    Self::new(F64::from_float(value))
+                }
+            }
+        )*
+    };
+    (@format $name:ident; $($ty:ident),*) => {
+        $(
+            impl TryFrom<$ty> for $name<$ty> {
+                type Error = InvariantError;
+
+                fn try_from(value: $ty) -> Result<Self, InvariantError> {
+                    Self::new(value)
+                }
+            }
+        )*
+    };
+}
+
+define_try_from!(NonNan, Finite, Positive, NonNegative, UnitInterval);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_from_test() {
+        assert_eq!(Err(InvariantError::Nan), NonNan::<F32>::try_from(f32::NAN));
+        assert_eq!(Err(InvariantError::Infinite), Finite::<F64>::try_from(f64::INFINITY));
+        let err = Positive::<F16>::try_from(F16::ZERO).unwrap_err();
+        assert_eq!(InvariantError::OutOfRange { expected: "greater than zero" }, err);
+        assert!(NonNegative::<BF16>::try_from(BF16::ZERO).is_ok());
+        assert!(UnitInterval::<F128>::try_from(F128::ONE).is_ok());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn display_test() {
+        use std::string::ToString;
+
+        assert_eq!("value is NaN", InvariantError::Nan.to_string());
+        let err = UnitInterval::<F64>::try_from(2.0).unwrap_err();
+        assert_eq!("value is out of range: expected from zero to one", err.to_string());
+    }
+}
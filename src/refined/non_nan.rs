@@ -0,0 +1,78 @@
+use core::fmt;
+
+use super::InvariantError;
+use crate::{BF16, F16, F32, F64, F128, FloatBits};
+
+/// A float that is not NaN.
+///
+/// Without NaN, the total order of the float types has no surprises left but one: as in IEEE 754
+/// `totalOrder`, which [`Ord`] follows here as it does for the wrapped types, `-0.0` sorts before
+/// `+0.0` and the two are not equal.  Any value is therefore a valid key for sorting, a
+/// [`BTreeMap`](https://doc.rust-lang.org/std/collections/struct.BTreeMap.html), or a hash map.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::F64;
+/// # use float_bits::refined::{InvariantError, NonNan};
+///
+/// let mut xs: Vec<NonNan<F64>> =
+///     [2.5, -1.0, 0.0].into_iter().filter_map(|x| NonNan::try_from(x).ok()).collect();
+/// xs.sort();
+/// assert_eq!(F64::from_float(-1.0), xs[0].get());
+/// assert_eq!(Err(InvariantError::Nan), NonNan::new(F64::NAN));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct NonNan<T>(T);
+
+/// A [`BF16`] that is not NaN.
+pub type NonNanBF16 = NonNan<BF16>;
+
+/// An [`F16`] that is not NaN.
+pub type NonNanF16 = NonNan<F16>;
+
+/// An [`F32`] that is not NaN.
+pub type NonNanF32 = NonNan<F32>;
+
+/// An [`F64`] that is not NaN.
+pub type NonNanF64 = NonNan<F64>;
+
+/// An [`F128`] that is not NaN.
+pub type NonNanF128 = NonNan<F128>;
+
+impl<T: FloatBits> NonNan<T> {
+    /// Wraps `value`, or returns an error if it is NaN.
+    pub fn new(value: T) -> Result<Self, InvariantError> {
+        if value.is_nan() { Err(InvariantError::Nan) } else { Ok(Self(value)) }
+    }
+
+    /// Returns the wrapped value.
+    pub const fn get(self) -> T {
+        self.0
+    }
+}
+
+impl<T: FloatBits> fmt::Display for NonNan<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_nan_test() {
+        assert_eq!(Err(InvariantError::Nan), NonNanF32::new(F32::NAN));
+        assert_eq!(Err(InvariantError::Nan), NonNanBF16::new(BF16::NEG_SNAN));
+        let zero = NonNanF64::new(F64::ZERO).unwrap();
+        let neg_zero = NonNanF64::new(F64::NEG_ZERO).unwrap();
+        let inf = NonNanF64::new(F64::INFINITY).unwrap();
+        assert!(neg_zero < zero && zero < inf);
+        assert_eq!(F64::NEG_ZERO.to_bits(), neg_zero.get().to_bits());
+        assert_eq!(NonNan(F128::ZERO), NonNanF128::default());
+        assert!(NonNanF16::new(F16::MIN).unwrap() < NonNanF16::new(F16::MAX).unwrap());
+    }
+}
@@ -0,0 +1,199 @@
+use core::fmt;
+use core::ops::{Add, Div, Mul};
+
+use super::InvariantError;
+use crate::{BF16, F16, F32, F64, F128, FloatBits};
+
+/// A float greater than zero: neither NaN, nor a zero, nor negative.
+///
+/// Positive infinity is allowed.  The sum of positive values is positive, so [`Add`] is
+/// implemented, but a product or quotient can underflow to zero, and `∞ / ∞` is NaN, so
+/// [`try_mul`](Self::try_mul) and [`try_div`](Self::try_div) return an error when the result
+/// would break the invariant.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::F64;
+/// # use float_bits::refined::Positive;
+///
+/// let half = Positive::new(F64::from_float(0.5)).unwrap();
+/// assert_eq!(F64::ONE, (half + half).get());
+/// let tiny = Positive::new(F64::from_bits(1)).unwrap();
+/// assert!(tiny.try_mul(half).is_err());
+/// assert!(Positive::new(F64::ZERO).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Positive<T>(T);
+
+/// A float that is zero or greater: neither NaN nor negative.
+///
+/// Both zeros are allowed, since `-0.0` is numerically equal to `+0.0`, and so is positive
+/// infinity.  The value is kept bit for bit, so as for [`NonNan`](super::NonNan), the wrappers of
+/// the two zeros are not equal and `-0.0` sorts before `+0.0`.  The sum of non-negative values is
+/// non-negative, so [`Add`] is implemented, but `0 × ∞`, `0 / 0`, and `∞ / ∞` are NaN, so
+/// [`try_mul`](Self::try_mul) and [`try_div`](Self::try_div) return an error when the result would
+/// break the invariant.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::F32;
+/// # use float_bits::refined::{InvariantError, NonNegative};
+///
+/// let variance = NonNegative::new(F32::from_float(2.25)).unwrap();
+/// let zero = NonNegative::new(F32::ZERO).unwrap();
+/// assert_eq!(variance, variance + zero);
+/// assert_eq!(Err(InvariantError::Nan), zero.try_div(zero));
+/// assert!(NonNegative::new(F32::NEG_ONE).is_err());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct NonNegative<T>(T);
+
+/// A [`BF16`] greater than zero.
+pub type PositiveBF16 = Positive<BF16>;
+
+/// An [`F16`] greater than zero.
+pub type PositiveF16 = Positive<F16>;
+
+/// An [`F32`] greater than zero.
+pub type PositiveF32 = Positive<F32>;
+
+/// An [`F64`] greater than zero.
+pub type PositiveF64 = Positive<F64>;
+
+/// An [`F128`] greater than zero.
+pub type PositiveF128 = Positive<F128>;
+
+/// A [`BF16`] that is zero or greater.
+pub type NonNegativeBF16 = NonNegative<BF16>;
+
+/// An [`F16`] that is zero or greater.
+pub type NonNegativeF16 = NonNegative<F16>;
+
+/// An [`F32`] that is zero or greater.
+pub type NonNegativeF32 = NonNegative<F32>;
+
+/// An [`F64`] that is zero or greater.
+pub type NonNegativeF64 = NonNegative<F64>;
+
+/// An [`F128`] that is zero or greater.
+pub type NonNegativeF128 = NonNegative<F128>;
+
+impl<T: FloatBits> Positive<T> {
+    /// Wraps `value`, or returns an error if it is NaN or not greater than zero.
+    pub fn new(value: T) -> Result<Self, InvariantError> {
+        if value.is_nan() {
+            Err(InvariantError::Nan)
+        } else if value.is_zero() || value.is_sign_negative() {
+            Err(InvariantError::OutOfRange { expected: "greater than zero" })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl<T: FloatBits> NonNegative<T> {
+    /// Wraps `value`, or returns an error if it is NaN or less than zero.
+    pub fn new(value: T) -> Result<Self, InvariantError> {
+        if value.is_nan() {
+            Err(InvariantError::Nan)
+        } else if !value.is_zero() && value.is_sign_negative() {
+            Err(InvariantError::OutOfRange { expected: "zero or greater" })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl<T: FloatBits> From<Positive<T>> for NonNegative<T> {
+    fn from(value: Positive<T>) -> Self {
+        Self(value.0)
+    }
+}
+
+macro_rules! define_sign_ops {
+    ($name:ident) => {
+        impl<T: FloatBits> $name<T> {
+            /// Returns the wrapped value.
+            pub const fn get(self) -> T {
+                self.0
+            }
+
+            /// Returns `self * rhs`, or an error if the product breaks the invariant.
+            pub fn try_mul(self, rhs: Self) -> Result<Self, InvariantError>
+            where
+                T: Mul<Output = T>,
+            {
+                Self::new(self.0 * rhs.0)
+            }
+
+            /// Returns `self / rhs`, or an error if the quotient breaks the invariant.
+            pub fn try_div(self, rhs: Self) -> Result<Self, InvariantError>
+            where
+                T: Div<Output = T>,
+            {
+                Self::new(self.0 / rhs.0)
+            }
+        }
+
+        impl<T: FloatBits + Add<Output = T>> Add for $name<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl<T: FloatBits> fmt::Display for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+    };
+}
+
+define_sign_ops!(Positive);
+define_sign_ops!(NonNegative);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positive_test() {
+        assert!(PositiveF32::new(F32::ZERO).is_err());
+        assert!(PositiveF32::new(F32::NEG_ZERO).is_err());
+        assert!(PositiveBF16::new(BF16::NAN).is_err());
+        assert!(PositiveF128::new(F128::from_bits(1 << 127 | 1)).is_err());
+        assert_eq!(Ok(F16::INFINITY), PositiveF16::new(F16::INFINITY).map(Positive::get));
+
+        let p = |x| PositiveF64::new(F64::from_float(x)).unwrap();
+        assert_eq!(p(f64::INFINITY), p(f64::MAX) + p(f64::MAX));
+        assert_eq!(Ok(p(6.0)), p(2.0).try_mul(p(3.0)));
+        assert!(p(1e-300).try_mul(p(1e-300)).is_err());
+        assert_eq!(Ok(p(f64::INFINITY)), p(f64::MAX).try_mul(p(2.0)));
+        assert_eq!(Ok(p(0.5)), p(1.0).try_div(p(2.0)));
+        assert!(p(1e-300).try_div(p(1e300)).is_err());
+        assert!(p(f64::INFINITY).try_div(p(f64::INFINITY)).is_err());
+    }
+
+    #[test]
+    fn non_negative_test() {
+        assert!(NonNegativeF32::new(F32::NEG_ONE).is_err());
+        assert!(NonNegativeF32::new(F32::NAN).is_err());
+        assert_eq!(Ok(F32::NEG_ZERO), NonNegativeF32::new(F32::NEG_ZERO).map(NonNegative::get));
+        assert_eq!(NonNegativeF16::new(F16::ZERO), Ok(NonNegative::default()));
+
+        let n = |x| NonNegativeF64::new(F64::from_float(x)).unwrap();
+        assert_eq!(n(1.5), n(0.0) + n(1.5));
+        assert_eq!(Ok(n(0.0)), n(0.0).try_mul(n(2.0)));
+        assert!(n(0.0).try_mul(n(f64::INFINITY)).is_err());
+        assert_eq!(Ok(n(f64::INFINITY)), n(1.0).try_div(n(0.0)));
+        assert!(n(0.0).try_div(n(0.0)).is_err());
+        let positive = PositiveF64::new(F64::ONE).unwrap();
+        assert_eq!(n(1.0), NonNegative::from(positive));
+    }
+}
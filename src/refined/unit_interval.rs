@@ -0,0 +1,168 @@
+use core::fmt;
+use core::ops::{Add, Div, Mul, Sub};
+
+use super::InvariantError;
+use crate::{BF16, F16, F32, F64, F128, FloatBits};
+
+/// A float from zero to one inclusive, such as a probability or an interpolation parameter.
+///
+/// Both zeros are allowed.  The product of two values in the interval stays in it, so [`Mul`] is
+/// implemented, and so does the [`complement`](Self::complement).
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::F64;
+/// # use float_bits::refined::UnitInterval;
+///
+/// let p = UnitInterval::new(F64::from_float(0.25)).unwrap();
+/// assert_eq!(F64::from_float(0.75), p.complement().get());
+/// assert_eq!(F64::from_float(15.0), p.lerp(F64::from_float(10.0), F64::from_float(30.0)));
+/// assert!(UnitInterval::new(F64::from_float(1.5)).is_err());
+/// assert_eq!(UnitInterval::ONE, UnitInterval::new_clamped(F64::from_float(1.5)));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct UnitInterval<T>(T);
+
+/// A [`BF16`] from zero to one inclusive.
+pub type UnitIntervalBF16 = UnitInterval<BF16>;
+
+/// An [`F16`] from zero to one inclusive.
+pub type UnitIntervalF16 = UnitInterval<F16>;
+
+/// An [`F32`] from zero to one inclusive.
+pub type UnitIntervalF32 = UnitInterval<F32>;
+
+/// An [`F64`] from zero to one inclusive.
+pub type UnitIntervalF64 = UnitInterval<F64>;
+
+/// An [`F128`] from zero to one inclusive.
+pub type UnitIntervalF128 = UnitInterval<F128>;
+
+impl<T: FloatBits> UnitInterval<T> {
+    /// Zero.
+    pub const ZERO: Self = Self(T::ZERO);
+
+    /// One.
+    pub const ONE: Self = Self(T::ONE);
+
+    /// Wraps `value`, or returns an error if it is NaN or outside of the interval.
+    pub fn new(value: T) -> Result<Self, InvariantError> {
+        // Past NaN and the zeros, the total order matches the numeric order.
+        if value.is_nan() {
+            Err(InvariantError::Nan)
+        } else if value.is_zero() || (value.is_sign_positive() && value <= T::ONE) {
+            Ok(Self(value))
+        } else {
+            Err(InvariantError::OutOfRange { expected: "from zero to one" })
+        }
+    }
+
+    /// Wraps `value`, clamped to the interval.
+    ///
+    /// NaN becomes zero.
+    pub fn new_clamped(value: T) -> Self {
+        Self::new(value).unwrap_or(if value.is_nan() || value.is_sign_negative() {
+            Self::ZERO
+        } else {
+            Self::ONE
+        })
+    }
+
+    /// Returns the wrapped value.
+    pub const fn get(self) -> T {
+        self.0
+    }
+
+    /// Returns `1 - self`.
+    pub fn complement(self) -> Self
+    where
+        T: Sub<Output = T>,
+    {
+        Self(T::ONE - self.0)
+    }
+
+    /// Interpolates linearly from `a` at zero to `b` at one.
+    ///
+    /// The result is exactly `a` at zero and exactly `b` at one, even if `b - a` overflows.  In
+    /// between, the difference is scaled from whichever end is nearer.
+    pub fn lerp(self, a: T, b: T) -> T
+    where
+        T: Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        if self.0.is_zero() {
+            a
+        } else if self.0 == T::ONE {
+            b
+        } else if self.0 + self.0 <= T::ONE {
+            a + (b - a) * self.0
+        } else {
+            b - (b - a) * (T::ONE - self.0)
+        }
+    }
+
+    /// Returns where `value` lies from `a` at zero to `b` at one, clamped to the interval.
+    ///
+    /// The inverse of [`lerp`](Self::lerp).  If `a` equals `b`, the position is undefined, and the
+    /// result is zero.
+    pub fn inverse_lerp(a: T, b: T, value: T) -> Self
+    where
+        T: Sub<Output = T> + Div<Output = T>,
+    {
+        Self::new_clamped((value - a) / (b - a))
+    }
+}
+
+impl<T: FloatBits + Mul<Output = T>> Mul for UnitInterval<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(self.0 * rhs.0)
+    }
+}
+
+impl<T: FloatBits> fmt::Display for UnitInterval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_interval_test() {
+        assert_eq!(Ok(F32::NEG_ZERO), UnitIntervalF32::new(F32::NEG_ZERO).map(UnitInterval::get));
+        assert_eq!(Ok(UnitInterval::ONE), UnitIntervalF32::new(F32::ONE));
+        assert!(UnitIntervalF32::new(F32::ONE.next_up()).is_err());
+        assert!(UnitIntervalF32::new(F32::NEG_ONE).is_err());
+        assert!(UnitIntervalBF16::new(BF16::NAN).is_err());
+        assert!(UnitIntervalF128::new(F128::INFINITY).is_err());
+
+        assert_eq!(UnitInterval::ZERO, UnitIntervalF16::new_clamped(F16::NAN));
+        assert_eq!(UnitInterval::ZERO, UnitIntervalF16::new_clamped(F16::NEG_INFINITY));
+        assert_eq!(UnitInterval::ONE, UnitIntervalF16::new_clamped(F16::MAX));
+        let half = F16::from_bits(0x3800);
+        assert_eq!(half, UnitIntervalF16::new_clamped(half).get());
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        let f = F64::from_float;
+        let u = |x| UnitIntervalF64::new(f(x)).unwrap();
+        assert_eq!(u(0.125), u(0.25) * u(0.5));
+        assert_eq!(u(0.0), u(1.0).complement());
+        assert_eq!(f(0.1), u(0.0).lerp(f(0.1), f(0.7)));
+        assert_eq!(f(0.7), u(1.0).lerp(f(0.1), f(0.7)));
+        assert_eq!(f(-1.0), u(0.75).lerp(f(2.0), f(-2.0)));
+        assert_eq!(f(f64::MAX), u(1.0).lerp(f(0.0), f(f64::MAX)));
+        assert_eq!(F64::MIN, UnitInterval::ZERO.lerp(F64::MIN, F64::MAX));
+        assert_eq!(F64::MAX, UnitInterval::ONE.lerp(F64::MIN, F64::MAX));
+
+        assert_eq!(u(0.25), UnitInterval::inverse_lerp(f(10.0), f(30.0), f(15.0)));
+        assert_eq!(u(1.0), UnitInterval::inverse_lerp(f(10.0), f(30.0), f(45.0)));
+        assert_eq!(u(0.0), UnitInterval::inverse_lerp(f(10.0), f(10.0), f(10.0)));
+    }
+}
@@ -0,0 +1,24 @@
+/// Rounding direction for operations whose exact result is not representable.
+///
+/// The names follow the rounding-direction attributes of IEEE 754 (2019 revision).
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum RoundingMode {
+    /// Round to the nearest representable value; on a tie, pick the one with an even mantissa.
+    ///
+    /// This is the default rounding mode of IEEE 754 and of Rust's native float operations.
+    #[default]
+    TiesToEven,
+
+    /// Round to the nearest representable value; on a tie, pick the one with greater magnitude.
+    TiesToAway,
+
+    /// Round to the representable value closest to zero, i.e. truncate.
+    TowardZero,
+
+    /// Round to the representable value closest to positive infinity, i.e. take the ceiling.
+    TowardPositive,
+
+    /// Round to the representable value closest to negative infinity, i.e. take the floor.
+    TowardNegative,
+}
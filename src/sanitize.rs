@@ -0,0 +1,112 @@
+//! In-place cleanup of slices of floats.
+//!
+//! The loops work on the raw bits and select results without branching on the values, so that
+//! the compiler can vectorize them.
+
+use crate::{BF16, F16, F32, F64, F128};
+
+macro_rules! define_sanitize {
+    ($ty:ident) => {
+        impl $ty {
+            /// Replaces every NaN in `values` with `with`.
+            pub fn replace_nan(values: &mut [Self], with: Self) {
+                for value in values {
+                    let is_nan = value.bits & Self::ABS_MASK > Self::EXP_MASK;
+                    *value = if is_nan { with } else { *value };
+                }
+            }
+
+            /// Applies [`Self::clamp`] to every element of `values`.
+            ///
+            /// NaNs are left unchanged, and as for [`Self::clamp`], `-0.0` is less than `+0.0`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `min` > `max`, `min` is NaN, or `max` is NaN, even if `values` is empty.
+            pub fn clamp_slice(values: &mut [Self], min: Self, max: Self) {
+                if min.is_nan() {
+                    panic!("min is NaN")
+                } else if max.is_nan() {
+                    panic!("max is NaN")
+                } else if min.total_cmp(max).is_gt() {
+                    panic!("min > max")
+                }
+                let (lo, hi) = (min.sort_bits(), max.sort_bits());
+                for value in values {
+                    let key = value.sort_bits();
+                    let is_nan = value.bits & Self::ABS_MASK > Self::EXP_MASK;
+                    let clamped = if key < lo {
+                        min
+                    } else if key > hi {
+                        max
+                    } else {
+                        *value
+                    };
+                    *value = if is_nan { *value } else { clamped };
+                }
+            }
+
+            /// Applies [`Self::flush_subnormals_to_zero`] to every element of `values`.
+            pub fn flush_subnormals(values: &mut [Self]) {
+                for value in values {
+                    let is_tiny = value.bits & Self::EXP_MASK == 0;
+                    value.bits &= if is_tiny { Self::SIGN_MASK } else { !0 };
+                }
+            }
+        }
+    };
+}
+
+define_sanitize!(BF16);
+define_sanitize!(F16);
+define_sanitize!(F32);
+define_sanitize!(F64);
+define_sanitize!(F128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace_nan_test() {
+        let mut values = [F32::NEG_QNAN, F32::INFINITY, F32::SNAN, F32::NEG_ZERO];
+        F32::replace_nan(&mut values, F32::ONE);
+        assert_eq!([F32::ONE, F32::INFINITY, F32::ONE, F32::NEG_ZERO], values);
+    }
+
+    #[test]
+    fn clamp_slice_test() {
+        let mut values =
+            [-3.0, -0.0, 0.0, 0.5, 7.0, f64::NAN, f64::NEG_INFINITY].map(F64::from_float);
+        let expected = values.map(|x| F64::clamp(&x, F64::ZERO, F64::ONE));
+        F64::clamp_slice(&mut values, F64::ZERO, F64::ONE);
+        assert_eq!(expected, values);
+        assert_eq!(F64::ZERO, values[1]);
+        assert!(values[5].is_nan());
+
+        let mut values = [F128::MAX, F128::MIN];
+        F128::clamp_slice(&mut values, F128::NEG_ONE, F128::ONE);
+        assert_eq!([F128::ONE, F128::NEG_ONE], values);
+    }
+
+    #[test]
+    #[should_panic = "min > max"]
+    fn clamp_slice_panic_test() {
+        F32::clamp_slice(&mut [], F32::ONE, F32::ZERO);
+    }
+
+    #[test]
+    fn flush_subnormals_test() {
+        let mut values = [
+            BF16::from_bits(0x0001),
+            BF16::from_bits(0x807f),
+            BF16::from_bits(0x0080),
+            BF16::NEG_ZERO,
+            BF16::NAN,
+        ];
+        let expected = values.map(|x| x.flush_subnormals_to_zero());
+        BF16::flush_subnormals(&mut values);
+        assert_eq!(expected, values);
+        assert_eq!([BF16::ZERO, BF16::NEG_ZERO], [values[0], values[1]]);
+    }
+}
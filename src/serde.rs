@@ -0,0 +1,730 @@
+//! Adapters that choose how a single float field is serialized.
+//!
+//! Each module here can be named in `#[serde(with = "…")]` on a field holding any float type of
+//! this crate, or a plain `f32` or `f64`:
+//!
+//! * [`as_bits`] writes the raw bits as an unsigned integer of the same width
+//! * [`as_float`] writes a native float number, as `f32` or `f64`
+//! * [`as_hex_string`] writes a C99 hexadecimal float string, like `"0x1.8p+1"`
+//! * [`as_decimal_string`] writes the shortest decimal string that parses back to the same bits,
+//!   like `"0.1"`
+//! * [`as_json_float`] writes a native float number, or a string like `"NaN"` for values that
+//!   JSON cannot hold as numbers
+//! * [`as_hex_bits`] writes the raw bits as a fixed-width hex string, like `"0x3c00"`, or as bytes
+//!   in binary formats
+//! * [`as_human_readable`] writes the shortest decimal string in human-readable formats, and the
+//!   default struct with a single `bits` field in binary formats
+//! * [`lenient`] writes the default struct, but also reads decimal strings and plain numbers in
+//!   human-readable formats
+//!
+//! Except for the last four, the chosen representation is used by every format, human-readable or
+//! not.  Except for [`as_float`] and [`as_json_float`], which round
+//! [`F128`] to `f64`, each one round-trips every value exactly, NaN payloads included.
+//!
+//! # Features
+//!
+//! Requires crate feature `serde`.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use float_bits::F64;
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Reading {
+//!     #[serde(with = "float_bits::serde::as_decimal_string")]
+//!     value: F64,
+//!     #[serde(with = "float_bits::serde::as_bits")]
+//!     raw: f32,
+//! }
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::num::FpCategory;
+use core::str::FromStr;
+
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::soft::convert;
+use crate::{BF16, F16, F32, F64, F128, LowerHexFloat, ParseError, ParseErrorKind};
+
+/// A float that the adapters in this module can serialize.
+///
+/// This trait is sealed.  It is implemented for every float type in this crate, and for `f32` and
+/// `f64`.
+pub trait Float: sealed::Float {}
+
+mod sealed {
+    use super::*;
+
+    pub trait Float: Copy {
+        type Bits: Serialize + for<'de> Deserialize<'de>;
+
+        /// The float type of this crate with the same format.
+        type Wrapper: Copy
+            + fmt::Display
+            + FromStr<Err: fmt::Display>
+            + Serialize
+            + for<'de> Deserialize<'de>;
+
+        /// The width of the raw bits, in bytes.
+        const BYTES: usize;
+
+        fn to_bits(self) -> Self::Bits;
+        fn from_bits(bits: Self::Bits) -> Self;
+        fn to_u128(self) -> u128;
+        fn from_u128(bits: u128) -> Self;
+        fn wrap(self) -> Self::Wrapper;
+        fn unwrap(x: Self::Wrapper) -> Self;
+        fn fmt_hex(self) -> LowerHexFloat;
+        fn from_hex_str(s: &str) -> Result<Self, ParseError>;
+        fn classify(self) -> FpCategory;
+
+        /// Returns true for the positive quiet NaN with an empty payload, as given by `f64::NAN`.
+        fn is_canonical_nan(self) -> bool;
+
+        fn from_f64(x: f64) -> Self;
+        fn from_integer(n: i128) -> Self;
+        fn serialize_float<S: Serializer>(self, serializer: S) -> Result<S::Ok, S::Error>;
+        fn deserialize_float<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+    }
+}
+
+// Each format is written as the narrowest native float that holds every value of the format, or as
+// `f64` if there is none.  F128 is rounded to `f64` on the way out.
+macro_rules! impl_wrapper {
+    ($( $ty:ident: $u_ty:ty => $wide:ident / $f_ty:ident ),*) => {
+        $(
+            impl Float for $ty {}
+
+            impl sealed::Float for $ty {
+                type Bits = $u_ty;
+                type Wrapper = Self;
+
+                const BYTES: usize = size_of::<$u_ty>();
+
+                fn to_bits(self) -> $u_ty {
+                    self.bits
+                }
+
+                fn from_bits(bits: $u_ty) -> Self {
+                    Self { bits }
+                }
+
+                fn to_u128(self) -> u128 {
+                    self.bits as u128
+                }
+
+                fn from_u128(bits: u128) -> Self {
+                    Self { bits: bits as $u_ty }
+                }
+
+                fn wrap(self) -> Self {
+                    self
+                }
+
+                fn unwrap(x: Self) -> Self {
+                    x
+                }
+
+                fn fmt_hex(self) -> LowerHexFloat {
+                    $ty::fmt_hex(&self)
+                }
+
+                fn from_hex_str(s: &str) -> Result<Self, ParseError> {
+                    $ty::from_hex_str(s)
+                }
+
+                fn classify(self) -> FpCategory {
+                    $ty::classify(&self)
+                }
+
+                fn is_canonical_nan(self) -> bool {
+                    self.bits == Self::EXP_MASK | Self::QUIET_MASK
+                }
+
+                fn from_f64(x: f64) -> Self {
+                    Self::from_bits(convert(F64::FORMAT, Self::FORMAT, x.to_bits() as u128) as _)
+                }
+
+                fn from_integer(n: i128) -> Self {
+                    Self::from_ratio(n, 0)
+                }
+
+                fn serialize_float<S: Serializer>(self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let bits = convert(Self::FORMAT, $wide::FORMAT, self.bits as u128);
+                    $wide::from_bits(bits as _).to_float().serialize(serializer)
+                }
+
+                fn deserialize_float<'de, D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Self, D::Error> {
+                    let x = $wide::from_float($f_ty::deserialize(deserializer)?);
+                    Ok(Self::from_bits(convert($wide::FORMAT, Self::FORMAT, x.bits as u128) as _))
+                }
+            }
+        )*
+    };
+}
+
+impl_wrapper!(
+    BF16: u16 => F32 / f32,
+    F16: u16 => F32 / f32,
+    F32: u32 => F32 / f32,
+    F64: u64 => F64 / f64,
+    F128: u128 => F64 / f64
+);
+
+macro_rules! impl_native {
+    ($( $f_ty:ident: $u_ty:ty => $ty:ident ),*) => {
+        $(
+            impl Float for $f_ty {}
+
+            impl sealed::Float for $f_ty {
+                type Bits = $u_ty;
+                type Wrapper = $ty;
+
+                const BYTES: usize = size_of::<$u_ty>();
+
+                fn to_bits(self) -> $u_ty {
+                    $f_ty::to_bits(self)
+                }
+
+                fn from_bits(bits: $u_ty) -> Self {
+                    $f_ty::from_bits(bits)
+                }
+
+                fn to_u128(self) -> u128 {
+                    $f_ty::to_bits(self) as u128
+                }
+
+                fn from_u128(bits: u128) -> Self {
+                    $f_ty::from_bits(bits as $u_ty)
+                }
+
+                fn wrap(self) -> $ty {
+                    $ty::from_float(self)
+                }
+
+                fn unwrap(x: $ty) -> Self {
+                    x.to_float()
+                }
+
+                fn fmt_hex(self) -> LowerHexFloat {
+                    $ty::from_float(self).fmt_hex()
+                }
+
+                fn from_hex_str(s: &str) -> Result<Self, ParseError> {
+                    $ty::from_hex_str(s).map(|x| x.to_float())
+                }
+
+                fn classify(self) -> FpCategory {
+                    $f_ty::classify(self)
+                }
+
+                fn is_canonical_nan(self) -> bool {
+                    sealed::Float::is_canonical_nan($ty::from_float(self))
+                }
+
+                fn from_f64(x: f64) -> Self {
+                    x as $f_ty
+                }
+
+                fn from_integer(n: i128) -> Self {
+                    $ty::from_ratio(n, 0).to_float()
+                }
+
+                fn serialize_float<S: Serializer>(self, serializer: S) -> Result<S::Ok, S::Error> {
+                    Serialize::serialize(&self, serializer)
+                }
+
+                fn deserialize_float<'de, D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Self, D::Error> {
+                    <$f_ty as Deserialize>::deserialize(deserializer)
+                }
+            }
+        )*
+    };
+}
+
+impl_native!(f32: u32 => F32, f64: u64 => F64);
+
+/// Serializes a float as its raw bits, an unsigned integer of the same width.
+pub mod as_bits {
+    use super::*;
+
+    /// Serializes `value` as its raw bits.
+    pub fn serialize<T: Float, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        value.to_bits().serialize(serializer)
+    }
+
+    /// Deserializes a float from its raw bits.
+    pub fn deserialize<'de, T: Float, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::Bits::deserialize(deserializer).map(T::from_bits)
+    }
+}
+
+/// Serializes a float as a native float number.
+///
+/// [`BF16`], [`F16`], and [`F32`] are written as `f32`, which holds all of their values exactly.
+/// [`F64`] and [`F128`] are written as `f64`, so [`F128`] is rounded to nearest, ties to even.
+/// Reading rounds the native number to the format the same way.
+pub mod as_float {
+    use super::*;
+
+    /// Serializes `value` as a native float number.
+    pub fn serialize<T: Float, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        value.serialize_float(serializer)
+    }
+
+    /// Deserializes a float from a native float number.
+    pub fn deserialize<'de, T: Float, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::deserialize_float(deserializer)
+    }
+}
+
+/// Serializes a float as a C99 hexadecimal float string, like `"0x1.8p+1"`.
+///
+/// The string is written by [`LowerHexFloat`] with the `#` flag, so that NaN keeps its payload as
+/// `nan:0x…`.
+pub mod as_hex_string {
+    use super::*;
+
+    /// Serializes `value` as a hexadecimal float string.
+    pub fn serialize<T: Float, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{:#}", value.fmt_hex()))
+    }
+
+    /// Deserializes a float from a hexadecimal float string, rounding to nearest with ties to even.
+    pub fn deserialize<'de, T: Float, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        deserializer.deserialize_str(StrVisitor::new("a hexadecimal float string", T::from_hex_str))
+    }
+}
+
+/// Serializes a float as the shortest decimal string that parses back to the same bits.
+///
+/// The string is written by [`Display`](fmt::Display) with the `#` flag, like `"0.1"`, `"1.0"`,
+/// `"-inf"`, or `"nan:0x8000000000000"`.
+pub mod as_decimal_string {
+    use super::*;
+
+    /// Serializes `value` as a decimal string.
+    pub fn serialize<T: Float, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{:#}", value.wrap()))
+    }
+
+    /// Deserializes a float from a decimal string, rounding to nearest with ties to even.
+    pub fn deserialize<'de, T: Float, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let parse = |s: &str| s.parse().map(T::unwrap);
+        deserializer.deserialize_str(StrVisitor::new("a decimal float string", parse))
+    }
+}
+
+/// Serializes a float as a native float number, or as a string if it is not finite.
+///
+/// JSON has no numbers for infinity and NaN, so human-readable formats get finite values as
+/// [`as_float`] writes them, but infinity as `"Infinity"` or `"-Infinity"`, the NaN given by
+/// `f64::NAN` as `"NaN"`, and any other NaN as `"nan:0x…"` with its sign and mantissa field.
+/// Reading accepts any number, rounding it to nearest with ties to even, and any string accepted
+/// by [`FromStr`], so that every value survives a round trip except as rounded by [`as_float`].
+///
+/// Binary formats can hold every native float, so they are handled exactly as by [`as_float`].
+pub mod as_json_float {
+    use super::*;
+
+    /// Serializes `value` as a number, or as a string if it is not finite.
+    pub fn serialize<T: Float, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        if !serializer.is_human_readable() {
+            return value.serialize_float(serializer);
+        }
+        let negative = (value.to_u128() >> (8 * T::BYTES - 1)) != 0;
+        match value.classify() {
+            FpCategory::Infinite if negative => serializer.serialize_str("-Infinity"),
+            FpCategory::Infinite => serializer.serialize_str("Infinity"),
+            FpCategory::Nan if value.is_canonical_nan() => serializer.serialize_str("NaN"),
+            FpCategory::Nan => serializer.collect_str(&format_args!("{:#}", value.wrap())),
+            _ => value.serialize_float(serializer),
+        }
+    }
+
+    /// Deserializes a float from a number or a string.
+    pub fn deserialize<'de, T: Float, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(JsonFloatVisitor(PhantomData))
+        } else {
+            T::deserialize_float(deserializer)
+        }
+    }
+}
+
+struct JsonFloatVisitor<T>(PhantomData<T>);
+
+impl<T: Float> ::serde::de::Visitor<'_> for JsonFloatVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a float as a number or a string")
+    }
+
+    fn visit_i64<E: ::serde::de::Error>(self, n: i64) -> Result<T, E> {
+        Ok(T::from_integer(n as i128))
+    }
+
+    fn visit_u64<E: ::serde::de::Error>(self, n: u64) -> Result<T, E> {
+        Ok(T::from_integer(n as i128))
+    }
+
+    fn visit_f64<E: ::serde::de::Error>(self, x: f64) -> Result<T, E> {
+        Ok(T::from_f64(x))
+    }
+
+    fn visit_str<E: ::serde::de::Error>(self, s: &str) -> Result<T, E> {
+        s.parse().map(T::unwrap).map_err(E::custom)
+    }
+}
+
+/// Serializes a float as the shortest decimal string in human-readable formats, and as the struct
+/// with a single `bits` field that the float types of this crate use by default in binary formats.
+///
+/// Human-readable formats such as JSON get the string that [`as_decimal_string`] writes, like
+/// `"0.1"` or `"nan:0x1"`.  Reading accepts whatever [`lenient`] reads, so that data written
+/// without the adapter still loads.  Binary formats keep the raw bits, exactly as without the
+/// adapter.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::F64;
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Reading {
+///     #[serde(with = "float_bits::serde::as_human_readable")]
+///     value: F64,
+/// }
+/// ```
+pub mod as_human_readable {
+    use super::*;
+
+    /// Serializes `value` as a decimal string, or as its bits in binary formats.
+    pub fn serialize<T: Float, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            as_decimal_string::serialize(value, serializer)
+        } else {
+            value.wrap().serialize(serializer)
+        }
+    }
+
+    /// Deserializes a float from a decimal string, a number, or the struct with its bits.
+    pub fn deserialize<'de, T: Float, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        lenient::deserialize(deserializer)
+    }
+}
+
+/// Serializes a float as the struct with a single `bits` field that the float types of this crate
+/// use by default, but reads other forms too.
+///
+/// Human-readable formats accept that struct, a decimal string as [`FromStr`] parses it, or a
+/// plain number, such as `0.1` or `3` in JSON, rounded to nearest with ties to even.  A number
+/// with a fraction or exponent is read as an `f64` by most formats before it is rounded, so prefer
+/// strings when more precision matters.  Binary formats accept only the struct.
+pub mod lenient {
+    use super::*;
+
+    /// Serializes `value` as the struct with its bits.
+    pub fn serialize<T: Float, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        value.wrap().serialize(serializer)
+    }
+
+    /// Deserializes a float from the struct with its bits, or in human-readable formats, from a
+    /// decimal string or a number.
+    pub fn deserialize<'de, T: Float, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(LenientVisitor(PhantomData))
+        } else {
+            T::Wrapper::deserialize(deserializer).map(T::unwrap)
+        }
+    }
+}
+
+struct LenientVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Float> ::serde::de::Visitor<'de> for LenientVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a float as a struct with its bits, a decimal string, or a number")
+    }
+
+    fn visit_i64<E: ::serde::de::Error>(self, n: i64) -> Result<T, E> {
+        Ok(T::from_integer(n as i128))
+    }
+
+    fn visit_u64<E: ::serde::de::Error>(self, n: u64) -> Result<T, E> {
+        Ok(T::from_integer(n as i128))
+    }
+
+    fn visit_f64<E: ::serde::de::Error>(self, x: f64) -> Result<T, E> {
+        Ok(T::from_f64(x))
+    }
+
+    fn visit_str<E: ::serde::de::Error>(self, s: &str) -> Result<T, E> {
+        s.parse().map(T::unwrap).map_err(E::custom)
+    }
+
+    fn visit_map<A: ::serde::de::MapAccess<'de>>(self, map: A) -> Result<T, A::Error> {
+        let deserializer = ::serde::de::value::MapAccessDeserializer::new(map);
+        T::Wrapper::deserialize(deserializer).map(T::unwrap)
+    }
+}
+
+/// Serializes a float as its raw bits, in a fixed-width hex string or as bytes.
+///
+/// Human-readable formats get `0x` followed by two lowercase hex digits per byte, like `"0x3c00"`
+/// for [`F16::ONE`].  Reading requires the same width, but accepts `0X` and digits of either case.
+/// Binary formats get the bytes of the bits in big-endian order, the same order as the digits.
+///
+/// This suits [`BF16`], [`F16`], and [`F128`], which have no stable Rust primitive, since their
+/// values stay portable and easy to diff, but it works for every float.
+pub mod as_hex_bits {
+    use super::*;
+
+    /// Serializes `value` as a hex string or as bytes.
+    pub fn serialize<T: Float, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        let bits = value.to_u128();
+        if serializer.is_human_readable() {
+            let width = 2 + 2 * T::BYTES;
+            serializer.collect_str(&format_args!("{bits:#0width$x}"))
+        } else {
+            serializer.serialize_bytes(&bits.to_be_bytes()[16 - T::BYTES..])
+        }
+    }
+
+    /// Deserializes a float from a hex string or from bytes.
+    pub fn deserialize<'de, T: Float, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(HexBitsVisitor(PhantomData))
+        } else {
+            deserializer.deserialize_bytes(HexBitsVisitor(PhantomData))
+        }
+    }
+}
+
+struct HexBitsVisitor<T>(PhantomData<T>);
+
+impl<T: Float> ::serde::de::Visitor<'_> for HexBitsVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} bytes of raw float bits, as a hex string or as bytes", T::BYTES)
+    }
+
+    fn visit_str<E: ::serde::de::Error>(self, s: &str) -> Result<T, E> {
+        parse_hex_bits(s.as_bytes(), T::BYTES).map(T::from_u128).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: ::serde::de::Error>(self, b: &[u8]) -> Result<T, E> {
+        if b.len() != T::BYTES {
+            return Err(E::invalid_length(b.len(), &self));
+        }
+        Ok(T::from_u128(b.iter().fold(0, |bits, &byte| (bits << 8) | byte as u128)))
+    }
+}
+
+/// Parses `0x` followed by exactly `2 × bytes` hex digits.
+fn parse_hex_bits(s: &[u8], bytes: usize) -> Result<u128, ParseError> {
+    if s.is_empty() {
+        return Err(ParseError::empty());
+    }
+    for (i, &expected) in b"0x".iter().enumerate() {
+        if i == s.len() || s[i].to_ascii_lowercase() != expected {
+            return Err(ParseError::invalid(s, i));
+        }
+    }
+    let mut bits = 0u128;
+    for i in 2..s.len() {
+        let Some(digit) = (s[i] as char).to_digit(16) else {
+            return Err(ParseError::invalid(s, i));
+        };
+        if i == 2 + 2 * bytes {
+            return Err(ParseError::new(ParseErrorKind::FieldTooLarge, s, i));
+        }
+        bits = (bits << 4) | digit as u128;
+    }
+    if s.len() != 2 + 2 * bytes {
+        return Err(ParseError::new(ParseErrorKind::FieldTooSmall, s, s.len()));
+    }
+    Ok(bits)
+}
+
+struct StrVisitor<F> {
+    expecting: &'static str,
+    parse: F,
+}
+
+impl<F> StrVisitor<F> {
+    fn new(expecting: &'static str, parse: F) -> Self {
+        Self { expecting, parse }
+    }
+}
+
+impl<T, Err, F> ::serde::de::Visitor<'_> for StrVisitor<F>
+where
+    Err: fmt::Display,
+    F: FnOnce(&str) -> Result<T, Err>,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: ::serde::de::Error>(self, s: &str) -> Result<T, E> {
+        (self.parse)(s).map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use ::serde::de::value::{
+        BytesDeserializer,
+        Error,
+        F32Deserializer,
+        F64Deserializer,
+        StrDeserializer,
+        U16Deserializer,
+        U32Deserializer,
+        U64Deserializer,
+    };
+
+    use super::sealed::Float as _;
+    use super::*;
+
+    #[test]
+    fn adapters_test() {
+        let from_str = |s| StrDeserializer::<Error>::new(s);
+        assert_eq!(Ok(F64::from_float(0.1)), as_decimal_string::deserialize(from_str("0.1")));
+        assert_eq!(Ok(0.1f32), as_decimal_string::deserialize(from_str("0.1")));
+        assert_eq!(Ok(F16::NEG_INFINITY), as_decimal_string::deserialize(from_str("-inf")));
+        assert!(as_decimal_string::deserialize::<F64, _>(from_str("0x1p0")).is_err());
+        assert_eq!(Ok(F64::ONE), as_hex_string::deserialize(from_str("0x1p0")));
+        assert_eq!(Ok(-12.0f64), as_hex_string::deserialize(from_str("-0x1.8p+3")));
+        let snan = as_hex_string::deserialize::<F32, _>(from_str("nan:0x1")).unwrap();
+        assert_eq!(F32::from_bits(0x7f800001), snan);
+
+        assert_eq!(Ok(F16::ONE), as_bits::deserialize(U16Deserializer::<Error>::new(0x3c00)));
+        assert_eq!(
+            Ok(1.0f64),
+            as_bits::deserialize(U64Deserializer::<Error>::new(0x3ff0000000000000))
+        );
+        let wrong_width = as_bits::deserialize::<F128, _>(U32Deserializer::<Error>::new(1));
+        assert_eq!(Ok(F128::from_bits(1)), wrong_width);
+
+        let from_f32 = |x| F32Deserializer::<Error>::new(x);
+        assert_eq!(Ok(BF16::from_bits(0x3dcd)), as_float::deserialize(from_f32(0.1)));
+        assert_eq!(Ok(F16::MAX), as_float::deserialize(from_f32(65519.0)));
+        assert_eq!(Ok(F16::INFINITY), as_float::deserialize(from_f32(65520.0)));
+        let from_f64 = |x| F64Deserializer::<Error>::new(x);
+        let exact = F128::from_hex_str("0x1.999999999999ap-4").unwrap();
+        assert_eq!(Ok(exact), as_float::deserialize(from_f64(0.1)));
+    }
+
+    #[test]
+    fn as_json_float_test() {
+        fn from_str<T: Float>(s: &str) -> Result<T, Error> {
+            as_json_float::deserialize(StrDeserializer::new(s))
+        }
+
+        assert_eq!(Ok(F64::NEG_INFINITY), from_str("-Infinity"));
+        assert_eq!(Ok(f32::INFINITY), from_str("Infinity"));
+        assert!(from_str::<f64>("NaN").unwrap().is_nan());
+        assert_eq!(Ok(F16::from_bits(0xfc01)), from_str("-nan:0x1"));
+        assert_eq!(Ok(F64::from_float(0.1)), from_str("0.1"));
+        assert!(from_str::<F64>("Infinityx").is_err());
+
+        let from_f64 = |x| F64Deserializer::<Error>::new(x);
+        assert_eq!(Ok(F32::from_float(0.1)), as_json_float::deserialize(from_f64(0.1)));
+        assert_eq!(Ok(BF16::from_bits(0x3dcd)), as_json_float::deserialize(from_f64(0.1)));
+        let max = U64Deserializer::<Error>::new(u64::MAX);
+        assert_eq!(Ok(F64::from_bits(0x43f0000000000000)), as_json_float::deserialize(max));
+
+        assert!(F64::from_float(f64::NAN).is_canonical_nan());
+        assert!(f32::NAN.is_canonical_nan());
+        assert!(!F16::QNAN.is_canonical_nan());
+        assert!(!F128::NEG_QNAN.is_canonical_nan());
+    }
+
+    #[test]
+    fn lenient_test() {
+        use ::serde::de::IntoDeserializer;
+        use ::serde::de::value::{I64Deserializer, MapDeserializer};
+
+        let from_str = |s| as_human_readable::deserialize(StrDeserializer::<Error>::new(s));
+        assert_eq!(Ok(F64::from_float(0.1)), from_str("0.1"));
+        assert_eq!(Ok(F64::NEG_INFINITY), from_str("-inf"));
+        assert_eq!(Ok(F64::from_bits(0x7ff0000000000001)), from_str("nan:0x1"));
+        assert!(from_str("0.1x").is_err());
+
+        let from_map = |k, v: u16| {
+            let map = MapDeserializer::<_, Error>::new([(k, v)].into_iter());
+            lenient::deserialize::<F16, _>(map.into_deserializer())
+        };
+        assert_eq!(Ok(F16::ONE), from_map("bits", 0x3c00));
+        assert!(from_map("bytes", 0x3c00).is_err());
+
+        let from_f64 = |x| lenient::deserialize(F64Deserializer::<Error>::new(x));
+        assert_eq!(Ok(F32::from_float(0.1)), from_f64(0.1));
+        assert_eq!(Ok(F32::INFINITY), from_f64(1e300));
+        let exact = F128::from_bits(0x3ffb999999999999a000000000000000);
+        assert_eq!(Ok(exact), lenient::deserialize(F64Deserializer::<Error>::new(0.1)));
+        let minus_three = I64Deserializer::<Error>::new(-3);
+        assert_eq!(Ok(F16::from_bits(0xc200)), lenient::deserialize(minus_three));
+        let max = U64Deserializer::<Error>::new(u64::MAX);
+        assert_eq!(Ok(BF16::from_bits(0x5f80)), lenient::deserialize(max));
+    }
+
+    #[test]
+    fn as_hex_bits_test() {
+        fn from_str<T: Float>(s: &str) -> Result<T, Error> {
+            as_hex_bits::deserialize(StrDeserializer::new(s))
+        }
+        fn from_bytes<T: Float>(b: &[u8]) -> Result<T, Error> {
+            as_hex_bits::deserialize(BytesDeserializer::new(b))
+        }
+
+        assert_eq!(Ok(F16::ONE), from_str("0x3c00"));
+        assert_eq!(Ok(BF16::from_bits(0xabcd)), from_str("0XABcd"));
+        assert_eq!(Ok(F128::ONE), from_str("0x3fff0000000000000000000000000000"));
+        assert!(from_str::<F16>("0x3c0").is_err());
+        let err = |s: &str| parse_hex_bits(s.as_bytes(), 2).unwrap_err().to_string();
+        assert_eq!("field too small for the format: unexpected end at byte 5", err("0x3c0"));
+        assert_eq!("field too large for the format: found '0' at byte 6", err("0x3c000"));
+        assert_eq!("invalid float literal: found 'g' at byte 3", err("0x3g00"));
+        assert_eq!("invalid float literal: found '3' at byte 0", err("3c00"));
+
+        assert_eq!(Ok(F16::ONE), from_bytes(&[0x3c, 0x00]));
+        assert_eq!(Ok(F128::ONE), from_bytes(&F128::ONE.bits.to_be_bytes()));
+        assert!(from_bytes::<F16>(&[0x3c]).is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use core::ops::Neg;
+
+use crate::{BF16, F16, F32, F64, F128};
+
+/// The sign of a float, as stored in its sign bit.
+///
+/// Every value has a sign, zeros, infinities, and NaNs included.  A [`bool`] converts to the
+/// sign whose sign bit it is, so `true` is [`Negative`](Self::Negative), and every float, the
+/// Rust floats included, converts to its sign.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::{F32, Sign};
+///
+/// assert_eq!(Sign::Negative, F32::NEG_ZERO.sign());
+/// assert_eq!(F32::NEG_INFINITY, F32::INFINITY.with_sign(Sign::Negative));
+/// assert_eq!(Sign::Positive, -Sign::Negative);
+/// assert!(bool::from(Sign::Negative));
+/// ```
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Sign {
+    /// A clear sign bit, as in `+0.0` and `+∞`.
+    #[default]
+    Positive,
+
+    /// A set sign bit, as in `-0.0` and `-∞`.
+    Negative,
+}
+
+impl Sign {
+    /// Returns `true` if `self` is [`Positive`](Self::Positive).
+    pub const fn is_positive(self) -> bool {
+        matches!(self, Self::Positive)
+    }
+
+    /// Returns `true` if `self` is [`Negative`](Self::Negative).
+    pub const fn is_negative(self) -> bool {
+        matches!(self, Self::Negative)
+    }
+
+    /// Returns the sign whose sign bit is `negative`.
+    pub const fn from_bit(negative: bool) -> Self {
+        if negative { Self::Negative } else { Self::Positive }
+    }
+}
+
+impl From<bool> for Sign {
+    fn from(negative: bool) -> Self {
+        Self::from_bit(negative)
+    }
+}
+
+impl From<Sign> for bool {
+    fn from(sign: Sign) -> Self {
+        sign.is_negative()
+    }
+}
+
+impl Neg for Sign {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::from_bit(self.is_positive())
+    }
+}
+
+macro_rules! define_from_float {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Sign {
+                fn from(value: $ty) -> Self {
+                    Self::from_bit(value.is_sign_negative())
+                }
+            }
+        )*
+    };
+}
+
+define_from_float!(BF16, F16, F32, F64, F128, f32, f64);
@@ -0,0 +1,159 @@
+//! Vectorized kernels for the slice operations, using `core::simd`.
+//!
+//! Each kernel handles the longest prefix of its input that fills whole vectors and returns its
+//! length, leaving the rest to the scalar loop of the caller.  The kernels compute exactly what
+//! the scalar code does, lane by lane.
+
+use core::num::FpCategory;
+use core::simd::prelude::*;
+
+use crate::{BF16, F16, F32};
+
+const LANES: usize = 16;
+
+type Bits = Simd<u32, LANES>;
+
+fn load(src: &[F32]) -> Bits {
+    Bits::from_array(core::array::from_fn(|i| src[i].bits))
+}
+
+/// Converts `src` to BF16 as [`BF16::from_f32_round`] does.
+pub(crate) fn f32_to_bf16(src: &[F32], dst: &mut [BF16]) -> usize {
+    let len = src.len() - src.len() % LANES;
+    for (s, d) in src[..len].chunks_exact(LANES).zip(dst[..len].chunks_exact_mut(LANES)) {
+        let bits = load(s);
+        let is_nan = (bits & Bits::splat(0x7fff_ffff)).simd_gt(Bits::splat(0x7f80_0000));
+        let quieted = (bits >> 16) | Bits::splat(BF16::QUIET_MASK as u32);
+        let round = Bits::splat(0x7fff) + ((bits >> 16) & Bits::splat(1));
+        let rounded = (bits + round) >> 16;
+        let out = is_nan.select(quieted, rounded).cast::<u16>().to_array();
+        for (d, bits) in d.iter_mut().zip(out) {
+            *d = BF16::from_bits(bits);
+        }
+    }
+    len
+}
+
+/// Converts `src` to F16 as [`F16::from_f32_round`] does.
+pub(crate) fn f32_to_f16(src: &[F32], dst: &mut [F16]) -> usize {
+    let len = src.len() - src.len() % LANES;
+    let splat = Bits::splat;
+    for (s, d) in src[..len].chunks_exact(LANES).zip(dst[..len].chunks_exact_mut(LANES)) {
+        let bits = load(s);
+        let sign = (bits >> 16) & splat(0x8000);
+        let abs = bits & splat(0x7fff_ffff);
+
+        let quieted = ((abs >> 13) & splat(0x03ff)) | splat(0x7e00);
+
+        let normal = abs - splat(0x3800_0000);
+        let normal = (normal + splat(0x0fff) + ((normal >> 13) & splat(1))) >> 13;
+
+        let mant = (abs & splat(0x007f_ffff)) | splat(0x0080_0000);
+        let shift = (splat(126) - (abs >> 23).simd_min(splat(112))).simd_min(splat(31));
+        let half = splat(1) << (shift - splat(1));
+        let subnormal = (mant + half - splat(1) + ((mant >> shift) & splat(1))) >> shift;
+
+        let out = abs.simd_lt(splat(0x3880_0000)).select(subnormal, normal);
+        let out = abs.simd_ge(splat(0x477f_f000)).select(splat(0x7c00), out);
+        let out = abs.simd_gt(splat(0x7f80_0000)).select(quieted, out);
+        let out = (out | sign).cast::<u16>().to_array();
+        for (d, bits) in d.iter_mut().zip(out) {
+            *d = F16::from_bits(bits);
+        }
+    }
+    len
+}
+
+/// Computes the keys of [`crate::total_key_f32`].
+pub(crate) fn total_keys_f32(src: &[f32], dst: &mut [i32]) -> usize {
+    let len = src.len() - src.len() % LANES;
+    for (s, d) in src[..len].chunks_exact(LANES).zip(dst[..len].chunks_exact_mut(LANES)) {
+        let bits = Simd::<f32, LANES>::from_slice(s).to_bits().cast::<i32>();
+        let mask = ((bits >> 31).cast::<u32>() >> 1).cast::<i32>();
+        (bits ^ mask).copy_to_slice(d);
+    }
+    len
+}
+
+/// Computes the keys of [`crate::total_key_f64`].
+pub(crate) fn total_keys_f64(src: &[f64], dst: &mut [i64]) -> usize {
+    const LANES: usize = 8;
+    let len = src.len() - src.len() % LANES;
+    for (s, d) in src[..len].chunks_exact(LANES).zip(dst[..len].chunks_exact_mut(LANES)) {
+        let bits = Simd::<f64, LANES>::from_slice(s).to_bits().cast::<i64>();
+        let mask = ((bits >> 63).cast::<u64>() >> 1).cast::<i64>();
+        (bits ^ mask).copy_to_slice(d);
+    }
+    len
+}
+
+/// Classifies `src` as [`F32::classify`] does.
+pub(crate) fn classify_f32(src: &[F32], dst: &mut [FpCategory]) -> usize {
+    const CATEGORIES: [FpCategory; 5] = [
+        FpCategory::Zero,
+        FpCategory::Subnormal,
+        FpCategory::Normal,
+        FpCategory::Infinite,
+        FpCategory::Nan,
+    ];
+    let len = src.len() - src.len() % LANES;
+    let splat = Bits::splat;
+    for (s, d) in src[..len].chunks_exact(LANES).zip(dst[..len].chunks_exact_mut(LANES)) {
+        let abs = load(s) & splat(0x7fff_ffff);
+        let one = |mask: Mask<i32, LANES>| mask.select(splat(1), splat(0));
+        let code = one(abs.simd_ne(splat(0)))
+            + one(abs.simd_ge(splat(0x0080_0000)))
+            + one(abs.simd_ge(splat(0x7f80_0000)))
+            + one(abs.simd_gt(splat(0x7f80_0000)));
+        for (d, code) in d.iter_mut().zip(code.to_array()) {
+            *d = CATEGORIES[code as usize];
+        }
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(seed: u32) -> [F32; 4 * LANES] {
+        core::array::from_fn(|i| {
+            let x = (i as u32 ^ seed).wrapping_mul(0x9e37_79b9);
+            F32::from_bits(x >> (i % 9) | (x & 0x8000_0000))
+        })
+    }
+
+    #[test]
+    fn conversion_test() {
+        for seed in 0..0x400 {
+            let src = inputs(seed);
+            let mut bf16 = [BF16::ZERO; 4 * LANES];
+            let mut f16 = [F16::ZERO; 4 * LANES];
+            assert_eq!(src.len(), f32_to_bf16(&src, &mut bf16));
+            assert_eq!(src.len(), f32_to_f16(&src, &mut f16));
+            for (i, s) in src.iter().enumerate() {
+                assert_eq!(BF16::from_f32_round(s.to_float()), bf16[i], "{:#x}", s.bits);
+                assert_eq!(F16::from_f32_round(s.to_float()), f16[i], "{:#x}", s.bits);
+            }
+        }
+    }
+
+    #[test]
+    fn classify_test() {
+        for seed in 0..0x40 {
+            let src = inputs(seed);
+            let mut out = [FpCategory::Zero; 4 * LANES];
+            assert_eq!(src.len(), classify_f32(&src, &mut out));
+            assert_eq!(src.map(|x| x.classify()), out);
+        }
+    }
+
+    #[test]
+    fn partial_test() {
+        let src = inputs(0);
+        let mut out = [BF16::ZERO; LANES + 3];
+        assert_eq!(LANES, f32_to_bf16(&src[..LANES + 3], &mut out));
+        let mut keys = [0; LANES - 1];
+        assert_eq!(0, total_keys_f32(&[0.0; LANES - 1], &mut keys));
+    }
+}
@@ -0,0 +1,658 @@
+//! Software floating point operations on the raw bit representation.
+//!
+//! Every format is handled by widening its bits to `u128` and unpacking them into a sign, an
+//! unbiased exponent, and a 128-bit significand with the leading 1 in the most significant bit.
+//! Intermediate results carry those 128 bits plus a sticky bit, and are rounded to the target
+//! format exactly once by [`round`].
+
+use core::cmp::Ordering;
+
+use crate::{RoundingMode, StatusFlags};
+
+/// The shape of a binary interchange format: total width and exponent width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Format {
+    pub(crate) bits: u32,
+    pub(crate) exp_bits: u32,
+}
+
+impl Format {
+    pub(crate) const fn new(bits: usize, exp_bits: usize) -> Self {
+        Self { bits: bits as u32, exp_bits: exp_bits as u32 }
+    }
+
+    /// Number of explicitly stored mantissa bits.
+    pub(crate) const fn mant_bits(self) -> u32 {
+        self.bits - self.exp_bits - 1
+    }
+
+    /// Number of significant bits, including the implicit leading 1.
+    pub(crate) const fn precision(self) -> u32 {
+        self.mant_bits() + 1
+    }
+
+    pub(crate) const fn bias(self) -> i32 {
+        (1 << (self.exp_bits - 1)) - 1
+    }
+
+    /// Unbiased exponent of the least positive normal value.
+    pub(crate) const fn emin(self) -> i32 {
+        1 - self.bias()
+    }
+
+    /// Unbiased exponent of the greatest finite value.
+    pub(crate) const fn emax(self) -> i32 {
+        self.bias()
+    }
+
+    pub(crate) const fn sign_mask(self) -> u128 {
+        1 << (self.bits - 1)
+    }
+
+    pub(crate) const fn mant_mask(self) -> u128 {
+        (1 << self.mant_bits()) - 1
+    }
+
+    pub(crate) const fn exp_mask(self) -> u128 {
+        (self.sign_mask() - 1) & !self.mant_mask()
+    }
+
+    pub(crate) const fn quiet_mask(self) -> u128 {
+        1 << (self.mant_bits() - 1)
+    }
+
+    pub(crate) const fn one(self) -> u128 {
+        (self.bias() as u128) << self.mant_bits()
+    }
+
+    pub(crate) const fn max(self) -> u128 {
+        self.exp_mask() - 1
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    Zero,
+    Finite,
+    Infinite,
+    Nan,
+}
+
+/// An unpacked float.
+///
+/// For [`Kind::Finite`], the value is `sig × 2^(exp − 127)` and bit 127 of `sig` is set.  For
+/// [`Kind::Nan`], `sig` holds the payload left-aligned, so that bit 127 is the `is_quiet` bit.
+/// Otherwise `exp` and `sig` are zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Unpacked {
+    pub(crate) sign: bool,
+    pub(crate) kind: Kind,
+    pub(crate) exp: i32,
+    pub(crate) sig: u128,
+}
+
+impl Unpacked {
+    pub(crate) const fn special(sign: bool, kind: Kind) -> Self {
+        Self { sign, kind, exp: 0, sig: 0 }
+    }
+
+    pub(crate) const fn finite(sign: bool, exp: i32, sig: u128) -> Self {
+        Self { sign, kind: Kind::Finite, exp, sig }
+    }
+}
+
+pub(crate) const fn unpack(fmt: Format, bits: u128) -> Unpacked {
+    let sign = (bits & fmt.sign_mask()) != 0;
+    let mant_bits = fmt.mant_bits();
+    let exp_field = (bits & fmt.exp_mask()) >> mant_bits;
+    let max_field = fmt.exp_mask() >> mant_bits;
+    let mant = bits & fmt.mant_mask();
+    if exp_field == max_field {
+        if mant == 0 {
+            Unpacked::special(sign, Kind::Infinite)
+        } else {
+            Unpacked { sign, kind: Kind::Nan, exp: 0, sig: mant << (128 - mant_bits) }
+        }
+    } else if exp_field == 0 {
+        if mant == 0 {
+            Unpacked::special(sign, Kind::Zero)
+        } else {
+            let lz = mant.leading_zeros();
+            let exp = 127 - lz as i32 + fmt.emin() - mant_bits as i32;
+            Unpacked::finite(sign, exp, mant << lz)
+        }
+    } else {
+        let exp = exp_field as i32 - fmt.bias();
+        let sig = (mant | (1 << mant_bits)) << (127 - mant_bits);
+        Unpacked::finite(sign, exp, sig)
+    }
+}
+
+/// Packs `value` into `fmt`, rounding finite values according to `mode`.
+///
+/// `sticky` records that the exact value is strictly greater in magnitude than `value`, by an
+/// amount smaller than one unit in the last place of the 128-bit significand.
+pub(crate) const fn round(fmt: Format, value: Unpacked, sticky: bool, mode: RoundingMode) -> u128 {
+    round_with_flags(fmt, value, sticky, mode).0
+}
+
+/// Packs `value` into `fmt` like [`round`], also returning the flags that rounding raised.
+pub(crate) const fn round_with_flags(
+    fmt: Format,
+    value: Unpacked,
+    sticky: bool,
+    mode: RoundingMode,
+) -> (u128, StatusFlags) {
+    let sign_bit = if value.sign { fmt.sign_mask() } else { 0 };
+    match value.kind {
+        Kind::Zero => (sign_bit, StatusFlags::NONE),
+        Kind::Infinite => (sign_bit | fmt.exp_mask(), StatusFlags::NONE),
+        Kind::Nan => {
+            let mut payload = value.sig >> (128 - fmt.mant_bits());
+            if payload == 0 {
+                payload = fmt.quiet_mask();
+            }
+            (sign_bit | fmt.exp_mask() | payload, StatusFlags::NONE)
+        },
+        Kind::Finite => {
+            let (bits, flags) = round_finite(fmt, value.sign, value.exp, value.sig, sticky, mode);
+            (sign_bit | bits, flags)
+        },
+    }
+}
+
+const fn round_finite(
+    fmt: Format,
+    sign: bool,
+    exp: i32,
+    sig: u128,
+    sticky: bool,
+    mode: RoundingMode,
+) -> (u128, StatusFlags) {
+    const OVERFLOW: StatusFlags = StatusFlags::OVERFLOW.union(StatusFlags::INEXACT);
+    let emin = fmt.emin();
+    if exp > fmt.emax() {
+        return (overflow(fmt, sign, mode), OVERFLOW);
+    }
+    let (base, drop) = if exp >= emin {
+        ((exp - emin) as u128, 128 - fmt.precision())
+    } else {
+        let extra = emin as i64 - exp as i64;
+        let drop = 128 - fmt.precision() as i64 + extra;
+        (0, if drop > 129 { 129 } else { drop as u32 })
+    };
+    let (kept, guard, rest) = if drop > 128 {
+        (0, false, true)
+    } else if drop == 128 {
+        (0, true, (sig << 1) != 0 || sticky)
+    } else {
+        let kept = sig >> drop;
+        let guard = ((sig >> (drop - 1)) & 1) != 0;
+        let rest = (sig & ((1 << (drop - 1)) - 1)) != 0 || sticky;
+        (kept, guard, rest)
+    };
+    let up = round_up(mode, sign, (kept & 1) != 0, guard, rest);
+    let bits = (base << fmt.mant_bits()) + kept + up as u128;
+    if bits >= fmt.exp_mask() {
+        return (overflow(fmt, sign, mode), OVERFLOW);
+    }
+    // Tininess is detected before rounding.
+    let flags = match (guard || rest, exp < emin) {
+        (false, _) => StatusFlags::NONE,
+        (true, false) => StatusFlags::INEXACT,
+        (true, true) => StatusFlags::UNDERFLOW.union(StatusFlags::INEXACT),
+    };
+    (bits, flags)
+}
+
+/// Decides whether a truncated magnitude must be incremented, given whether it is `odd`, whether
+/// the first dropped bit (`guard`) is set, and whether any later dropped bit (`rest`) is set.
+const fn round_up(mode: RoundingMode, sign: bool, odd: bool, guard: bool, rest: bool) -> bool {
+    match mode {
+        RoundingMode::TiesToEven => guard && (rest || odd),
+        RoundingMode::TiesToAway => guard,
+        RoundingMode::TowardZero => false,
+        RoundingMode::TowardPositive => !sign && (guard || rest),
+        RoundingMode::TowardNegative => sign && (guard || rest),
+    }
+}
+
+const fn overflow(fmt: Format, sign: bool, mode: RoundingMode) -> u128 {
+    match mode {
+        RoundingMode::TiesToEven | RoundingMode::TiesToAway => fmt.exp_mask(),
+        RoundingMode::TowardZero => fmt.max(),
+        RoundingMode::TowardPositive if sign => fmt.max(),
+        RoundingMode::TowardNegative if !sign => fmt.max(),
+        RoundingMode::TowardPositive | RoundingMode::TowardNegative => fmt.exp_mask(),
+    }
+}
+
+/// Converts `bits` from format `from` to format `to`, rounding to nearest with ties to even.
+///
+/// NaN payloads keep their most significant bits, as in hardware conversions.
+pub(crate) const fn convert(from: Format, to: Format, bits: u128) -> u128 {
+    round(to, unpack(from, bits), false, RoundingMode::TiesToEven)
+}
+
+/// Converts `bits` from format `from` to format `to`, rounding according to `mode`, and returns
+/// the result with the flags raised.
+///
+/// Unlike [`convert`], this follows the `convertFormat` operation of IEEE 754 for NaNs as well:
+/// a NaN result is quiet, and a signaling NaN operand raises the invalid flag.
+pub(crate) const fn convert_with_flags(
+    from: Format,
+    to: Format,
+    bits: u128,
+    mode: RoundingMode,
+) -> (u128, StatusFlags) {
+    let x = unpack(from, bits);
+    match x.kind {
+        Kind::Nan => {
+            let flags = if is_signaling(x) { StatusFlags::INVALID } else { StatusFlags::NONE };
+            (quiet(to, round(to, x, false, mode)), flags)
+        },
+        _ => round_with_flags(to, x, false, mode),
+    }
+}
+
+/// Rounds `mag × 2^exp`, negated if `sign` is set, to `fmt` according to `mode`.
+pub(crate) const fn from_int(
+    fmt: Format,
+    sign: bool,
+    mag: u128,
+    exp: i64,
+    mode: RoundingMode,
+) -> u128 {
+    if mag == 0 {
+        return round(fmt, Unpacked::special(sign, Kind::Zero), false, mode);
+    }
+    let shift = mag.leading_zeros();
+    // Exponents this far out of range overflow or underflow in every format.
+    let exp = exp + 127 - shift as i64;
+    let exp = if exp > 1 << 20 {
+        1 << 20
+    } else if exp < -(1 << 20) {
+        -(1 << 20)
+    } else {
+        exp
+    };
+    round(fmt, Unpacked::finite(sign, exp as i32, mag << shift), false, mode)
+}
+
+/// Rounds `bits × 2^scale` to an integer according to `mode`, returning its sign and magnitude.
+///
+/// Returns `None` for infinities, NaNs, and magnitudes of `2^128` or more.
+pub(crate) const fn to_int(
+    fmt: Format,
+    bits: u128,
+    scale: i64,
+    mode: RoundingMode,
+) -> Option<(bool, u128)> {
+    let x = unpack(fmt, bits);
+    let exp = match x.kind {
+        Kind::Zero => return Some((x.sign, 0)),
+        Kind::Finite => x.exp as i64 + scale,
+        _ => return None,
+    };
+    // The value is `sig × 2^(exp − 127)`; split it at the binary point.
+    let (int, guard, rest) = if exp >= 128 {
+        return None;
+    } else if exp == 127 {
+        (x.sig, false, false)
+    } else if exp == -1 {
+        (0, true, (x.sig << 1) != 0)
+    } else if exp >= 0 {
+        let drop = (127 - exp) as u32;
+        let guard = ((x.sig >> (drop - 1)) & 1) != 0;
+        (x.sig >> drop, guard, (x.sig & ((1 << (drop - 1)) - 1)) != 0)
+    } else {
+        (0, false, true)
+    };
+    match int.checked_add(round_up(mode, x.sign, (int & 1) != 0, guard, rest) as u128) {
+        Some(int) => Some((x.sign, int)),
+        None => None,
+    }
+}
+
+/// Compares the real numbers that `a` and `b` represent, or returns `None` if either is NaN.
+///
+/// The two zeros are equal.
+pub(crate) fn compare(a: Unpacked, b: Unpacked) -> Option<Ordering> {
+    if a.kind == Kind::Nan || b.kind == Kind::Nan {
+        return None;
+    }
+    let a_neg = a.sign && a.kind != Kind::Zero;
+    let b_neg = b.sign && b.kind != Kind::Zero;
+    if a_neg != b_neg {
+        return Some(if a_neg { Ordering::Less } else { Ordering::Greater });
+    }
+    let order = compare_magnitude(a, b);
+    Some(if a_neg { order.reverse() } else { order })
+}
+
+/// Compares `a` and `b` in the IEEE 754 `totalOrder` sense, which is meaningful across formats.
+///
+/// Negative zero is less than positive zero, negative NaNs come before everything else and
+/// positive NaNs after, and NaNs of the same sign order by their left-aligned payloads, so that
+/// signaling NaNs are nearer to the infinities than quiet ones.
+pub(crate) fn total_compare(a: Unpacked, b: Unpacked) -> Ordering {
+    if a.sign != b.sign {
+        return if a.sign { Ordering::Less } else { Ordering::Greater };
+    }
+    let order = compare_magnitude(a, b);
+    if a.sign { order.reverse() } else { order }
+}
+
+/// Compares the magnitudes of `a` and `b`, with NaNs above infinity ordered by payload.
+fn compare_magnitude(a: Unpacked, b: Unpacked) -> Ordering {
+    const fn rank(x: Unpacked) -> u8 {
+        match x.kind {
+            Kind::Zero => 0,
+            Kind::Finite => 1,
+            Kind::Infinite => 2,
+            Kind::Nan => 3,
+        }
+    }
+    (rank(a), a.exp, a.sig).cmp(&(rank(b), b.exp, b.sig))
+}
+
+/// Returns the quiet NaN corresponding to the NaN `bits`.
+pub(crate) const fn quiet(fmt: Format, bits: u128) -> u128 {
+    bits | fmt.quiet_mask()
+}
+
+/// Returns the default quiet NaN, raising the invalid flag.
+const fn invalid(fmt: Format) -> (u128, StatusFlags) {
+    (fmt.exp_mask() | fmt.quiet_mask(), StatusFlags::INVALID)
+}
+
+/// Returns the quiet NaN that an operation on `a` and `b` gives when either of them is NaN.
+///
+/// The payload of `a` is preferred, and a signaling NaN operand raises the invalid flag.
+const fn propagate_nan(fmt: Format, a: u128, b: u128) -> (u128, StatusFlags) {
+    let (ua, ub) = (unpack(fmt, a), unpack(fmt, b));
+    let flags =
+        if is_signaling(ua) || is_signaling(ub) { StatusFlags::INVALID } else { StatusFlags::NONE };
+    let nan = if matches!(ua.kind, Kind::Nan) { a } else { b };
+    (quiet(fmt, nan), flags)
+}
+
+const fn is_signaling(x: Unpacked) -> bool {
+    matches!(x.kind, Kind::Nan) && (x.sig >> 127) == 0
+}
+
+/// Adds `a` and `b`, rounding once, and returns the result with the flags raised.
+pub(crate) const fn add(fmt: Format, a: u128, b: u128, mode: RoundingMode) -> (u128, StatusFlags) {
+    let (x, y) = (unpack(fmt, a), unpack(fmt, b));
+    // The sign of an exact zero sum of operands of opposite signs.
+    let zero_sign = matches!(mode, RoundingMode::TowardNegative);
+    match (x.kind, y.kind) {
+        (Kind::Nan, _) | (_, Kind::Nan) => return propagate_nan(fmt, a, b),
+        (Kind::Infinite, Kind::Infinite) if x.sign != y.sign => return invalid(fmt),
+        (Kind::Infinite, _) => return (a, StatusFlags::NONE),
+        (_, Kind::Infinite) => return (b, StatusFlags::NONE),
+        (Kind::Zero, Kind::Zero) => {
+            let sign = if x.sign == y.sign { x.sign } else { zero_sign };
+            return (if sign { fmt.sign_mask() } else { 0 }, StatusFlags::NONE);
+        },
+        (Kind::Zero, _) => return (b, StatusFlags::NONE),
+        (_, Kind::Zero) => return (a, StatusFlags::NONE),
+        (Kind::Finite, Kind::Finite) => {},
+    }
+
+    // Order the operands by magnitude, and halve both significands to make room for a carry.  No
+    // bit is lost, since no format has more than 113 significant bits.
+    let (big, small) =
+        if x.exp > y.exp || (x.exp == y.exp && x.sig >= y.sig) { (x, y) } else { (y, x) };
+    let shift = (big.exp - small.exp) as u32;
+    let (small_sig, sticky) = if shift >= 127 {
+        (0, true)
+    } else {
+        let sig = small.sig >> 1;
+        (sig >> shift, shift != 0 && (sig << (128 - shift)) != 0)
+    };
+    let sum = if big.sign == small.sign {
+        (big.sig >> 1) + small_sig
+    } else {
+        // The exact difference is below the truncated one by less than one unit, so take one
+        // unit off and let the sticky bit stand for the fraction.
+        (big.sig >> 1) - small_sig - sticky as u128
+    };
+    if sum == 0 {
+        return (if zero_sign { fmt.sign_mask() } else { 0 }, StatusFlags::NONE);
+    }
+    let lz = sum.leading_zeros();
+    round_with_flags(
+        fmt,
+        Unpacked::finite(big.sign, big.exp + 1 - lz as i32, sum << lz),
+        sticky,
+        mode,
+    )
+}
+
+/// Subtracts `b` from `a`, rounding once, and returns the result with the flags raised.
+pub(crate) const fn sub(fmt: Format, a: u128, b: u128, mode: RoundingMode) -> (u128, StatusFlags) {
+    match unpack(fmt, b).kind {
+        Kind::Nan => add(fmt, a, b, mode),
+        _ => add(fmt, a, b ^ fmt.sign_mask(), mode),
+    }
+}
+
+/// Multiplies `a` by `b`, rounding once, and returns the result with the flags raised.
+pub(crate) const fn mul(fmt: Format, a: u128, b: u128, mode: RoundingMode) -> (u128, StatusFlags) {
+    let (x, y) = (unpack(fmt, a), unpack(fmt, b));
+    let sign = x.sign != y.sign;
+    let kind = match (x.kind, y.kind) {
+        (Kind::Nan, _) | (_, Kind::Nan) => return propagate_nan(fmt, a, b),
+        (Kind::Infinite, Kind::Zero) | (Kind::Zero, Kind::Infinite) => return invalid(fmt),
+        (Kind::Infinite, _) | (_, Kind::Infinite) => Kind::Infinite,
+        (Kind::Zero, _) | (_, Kind::Zero) => Kind::Zero,
+        (Kind::Finite, Kind::Finite) => Kind::Finite,
+    };
+    if !matches!(kind, Kind::Finite) {
+        return round_with_flags(fmt, Unpacked::special(sign, kind), false, mode);
+    }
+    let (exp, sig, sticky) = mul_sig(x.exp as i64, x.sig, y.exp as i64, y.sig);
+    round_with_flags(fmt, Unpacked::finite(sign, clamp_exp(exp), sig), sticky, mode)
+}
+
+/// Divides `a` by `b`, rounding once, and returns the result with the flags raised.
+pub(crate) const fn div(fmt: Format, a: u128, b: u128, mode: RoundingMode) -> (u128, StatusFlags) {
+    let (x, y) = (unpack(fmt, a), unpack(fmt, b));
+    let sign = x.sign != y.sign;
+    let kind = match (x.kind, y.kind) {
+        (Kind::Nan, _) | (_, Kind::Nan) => return propagate_nan(fmt, a, b),
+        (Kind::Infinite, Kind::Infinite) | (Kind::Zero, Kind::Zero) => return invalid(fmt),
+        (Kind::Finite, Kind::Zero) => {
+            let bits = round(fmt, Unpacked::special(sign, Kind::Infinite), false, mode);
+            return (bits, StatusFlags::DIV_BY_ZERO);
+        },
+        (Kind::Infinite, _) => Kind::Infinite,
+        (Kind::Zero, _) | (_, Kind::Infinite) => Kind::Zero,
+        (Kind::Finite, Kind::Finite) => Kind::Finite,
+    };
+    if !matches!(kind, Kind::Finite) {
+        return round_with_flags(fmt, Unpacked::special(sign, kind), false, mode);
+    }
+    let (exp, sig, sticky) = div_sig(x.exp as i64, x.sig, y.exp as i64, y.sig);
+    round_with_flags(fmt, Unpacked::finite(sign, clamp_exp(exp), sig), sticky, mode)
+}
+
+/// Computes the full 256-bit product of `a` and `b`, as `(high, low)`.
+pub(crate) const fn widening_mul(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a1, a0) = (a >> 64, a & MASK);
+    let (b1, b0) = (b >> 64, b & MASK);
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+    let mid = (p00 >> 64) + (p01 & MASK) + (p10 & MASK);
+    let lo = (p00 & MASK) | (mid << 64);
+    let hi = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
+/// Multiplies two normalized significands, returning `(exp, sig, sticky)`.
+const fn mul_sig(ea: i64, sa: u128, eb: i64, sb: u128) -> (i64, u128, bool) {
+    let (hi, lo) = widening_mul(sa, sb);
+    if (hi >> 127) != 0 {
+        (ea + eb + 1, hi, lo != 0)
+    } else {
+        (ea + eb, (hi << 1) | (lo >> 127), (lo << 1) != 0)
+    }
+}
+
+/// Divides two normalized significands, returning `(exp, sig, sticky)`.
+const fn div_sig(ea: i64, sa: u128, eb: i64, sb: u128) -> (i64, u128, bool) {
+    let (exp, steps) = if sa >= sb { (ea - eb, 128) } else { (ea - eb - 1, 129) };
+    let mut rem = sa;
+    let mut carry = false;
+    let mut q: u128 = 0;
+    let mut i = 0;
+    while i < steps {
+        q <<= 1;
+        if carry || rem >= sb {
+            rem = rem.wrapping_sub(sb);
+            q |= 1;
+        }
+        carry = (rem >> 127) != 0;
+        rem <<= 1;
+        i += 1;
+    }
+    (exp, q, carry || rem != 0)
+}
+
+const fn clamp_exp(exp: i64) -> i32 {
+    const LIMIT: i64 = 1 << 30;
+    if exp > LIMIT {
+        LIMIT as i32
+    } else if exp < -LIMIT {
+        -LIMIT as i32
+    } else {
+        exp as i32
+    }
+}
+
+/// Raises `bits` to the `n`th power, rounding once at the end.
+pub(crate) const fn powi(fmt: Format, bits: u128, n: i32, mode: RoundingMode) -> u128 {
+    powi_with_flags(fmt, bits, n, mode).0
+}
+
+/// Raises `bits` to the `n`th power like [`powi`], also returning the flags raised.
+///
+/// A zero raised to a negative power is an exact infinity, which raises the division by zero
+/// flag, as in the `pown` operation of IEEE 754.
+pub(crate) const fn powi_with_flags(
+    fmt: Format,
+    bits: u128,
+    n: i32,
+    mode: RoundingMode,
+) -> (u128, StatusFlags) {
+    if n == 0 {
+        return (fmt.one(), StatusFlags::NONE);
+    }
+    let x = unpack(fmt, bits);
+    let sign = x.sign && (n & 1) != 0;
+    let kind = match x.kind {
+        Kind::Nan => return propagate_nan(fmt, bits, bits),
+        Kind::Infinite if n > 0 => Kind::Infinite,
+        Kind::Infinite => Kind::Zero,
+        Kind::Zero if n > 0 => Kind::Zero,
+        Kind::Zero => {
+            let bits = round(fmt, Unpacked::special(sign, Kind::Infinite), false, mode);
+            return (bits, StatusFlags::DIV_BY_ZERO);
+        },
+        Kind::Finite => Kind::Finite,
+    };
+    if !matches!(kind, Kind::Finite) {
+        return round_with_flags(fmt, Unpacked::special(sign, kind), false, mode);
+    }
+
+    let mut m = n.unsigned_abs();
+    let (mut base_exp, mut base_sig, mut base_sticky) = (x.exp as i64, x.sig, false);
+    let (mut exp, mut sig, mut sticky) = (0i64, 1u128 << 127, false);
+    loop {
+        if (m & 1) != 0 {
+            let (e, s, st) = mul_sig(exp, sig, base_exp, base_sig);
+            (exp, sig, sticky) = (e, s, sticky || st || base_sticky);
+        }
+        m >>= 1;
+        if m == 0 {
+            break;
+        }
+        let (e, s, st) = mul_sig(base_exp, base_sig, base_exp, base_sig);
+        (base_exp, base_sig, base_sticky) = (e, s, base_sticky || st);
+    }
+    if n < 0 {
+        let (e, s, st) = div_sig(0, 1 << 127, exp, sig);
+        (exp, sig, sticky) = (e, s, sticky || st);
+    }
+    round_with_flags(fmt, Unpacked::finite(sign, clamp_exp(exp), sig), sticky, mode)
+}
+
+const fn add256(a: (u128, u128), b: (u128, u128)) -> (u128, u128) {
+    let (lo, carry) = a.1.overflowing_add(b.1);
+    (a.0 + b.0 + carry as u128, lo)
+}
+
+const fn sub256(a: (u128, u128), b: (u128, u128)) -> (u128, u128) {
+    let (lo, borrow) = a.1.overflowing_sub(b.1);
+    (a.0 - b.0 - borrow as u128, lo)
+}
+
+const fn shr256(a: (u128, u128), n: u32) -> (u128, u128) {
+    (a.0 >> n, (a.1 >> n) | (a.0 << (128 - n)))
+}
+
+const fn ge256(a: (u128, u128), b: (u128, u128)) -> bool {
+    a.0 > b.0 || (a.0 == b.0 && a.1 >= b.1)
+}
+
+/// Computes the integer square root of the 256-bit `n`, which must be at least `2^254`.
+///
+/// Returns the root and whether it is exact.
+const fn isqrt256(n: (u128, u128)) -> (u128, bool) {
+    let mut x = n;
+    let mut c = (0, 0);
+    let mut d = (1 << 126, 0);
+    while d.0 != 0 || d.1 != 0 {
+        let s = add256(c, d);
+        if ge256(x, s) {
+            x = sub256(x, s);
+            c = add256(shr256(c, 1), d);
+        } else {
+            c = shr256(c, 1);
+        }
+        d = shr256(d, 2);
+    }
+    (c.1, x.0 == 0 && x.1 == 0)
+}
+
+/// Computes the square root of `bits`, rounding once.
+pub(crate) const fn sqrt(fmt: Format, bits: u128, mode: RoundingMode) -> u128 {
+    sqrt_with_flags(fmt, bits, mode).0
+}
+
+/// Computes the square root of `bits` like [`sqrt`], also returning the flags raised.
+pub(crate) const fn sqrt_with_flags(
+    fmt: Format,
+    bits: u128,
+    mode: RoundingMode,
+) -> (u128, StatusFlags) {
+    let x = unpack(fmt, bits);
+    match x.kind {
+        Kind::Nan => return propagate_nan(fmt, bits, bits),
+        Kind::Zero => return (bits, StatusFlags::NONE),
+        _ if x.sign => return invalid(fmt),
+        Kind::Infinite => return (bits, StatusFlags::NONE),
+        Kind::Finite => {},
+    }
+
+    // Scale the significand so that the remaining power of two is even.
+    let (shift, n) =
+        if (x.exp & 1) != 0 { (128, (x.sig, 0)) } else { (127, (x.sig >> 1, x.sig << 127)) };
+    let (root, exact) = isqrt256(n);
+    let exp = 127 + (x.exp - 127 - shift) / 2;
+    round_with_flags(fmt, Unpacked::finite(false, exp, root), !exact, mode)
+}
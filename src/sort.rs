@@ -0,0 +1,342 @@
+//! Sorting slices of Rust floats in the IEEE 754 total order.
+
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::FloatBits;
+use crate::{F32, F64};
+
+/// Sorts a slice of `f32` in the order of [`f32::total_cmp`].
+///
+/// Negative NaNs come first and positive NaNs last, and `-0.0` comes before `+0.0`, so the sort
+/// never panics and the result is fully determined by the input.  The sort is unstable, but equal
+/// elements have the same bits, so this is indistinguishable from a stable sort.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::sort_total_f32;
+///
+/// let mut xs = [1.0, f32::NAN, -0.0, -f32::INFINITY, 0.0];
+/// sort_total_f32(&mut xs);
+/// assert_eq!([-f32::INFINITY, -0.0, 0.0, 1.0], xs[..4]);
+/// assert!(xs[4].is_nan());
+/// ```
+pub fn sort_total_f32(xs: &mut [f32]) {
+    F32::from_mut_slice(xs).sort_unstable();
+}
+
+/// Sorts a slice of `f64` in the order of [`f64::total_cmp`].
+///
+/// See [`sort_total_f32`] for details.
+pub fn sort_total_f64(xs: &mut [f64]) {
+    F64::from_mut_slice(xs).sort_unstable();
+}
+
+/// Returns an integer key whose order is the order of [`f32::total_cmp`].
+///
+/// This is meant for `sort_unstable_by_key` and similar, for sorting by a float field.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::total_key_f32;
+///
+/// let mut points = [(2.5f32, 'a'), (-1.0, 'b'), (f32::NAN, 'c'), (0.0, 'd')];
+/// points.sort_unstable_by_key(|p| total_key_f32(p.0));
+/// assert_eq!(['b', 'd', 'a', 'c'], points.map(|p| p.1));
+/// ```
+pub const fn total_key_f32(x: f32) -> i32 {
+    let bits = x.to_bits() as i32;
+    bits ^ (((bits >> 31) as u32) >> 1) as i32
+}
+
+/// Returns an integer key whose order is the order of [`f64::total_cmp`].
+///
+/// See [`total_key_f32`] for details.
+pub const fn total_key_f64(x: f64) -> i64 {
+    let bits = x.to_bits() as i64;
+    bits ^ (((bits >> 63) as u64) >> 1) as i64
+}
+
+/// Writes the [`total_key_f32`] of every element of `src` to `dst`.
+///
+/// With crate feature `portable-simd`, this is vectorized with `core::simd`.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+pub fn total_keys_f32(src: &[f32], dst: &mut [i32]) {
+    assert_eq!(src.len(), dst.len(), "slices must have the same length");
+    #[cfg(feature = "portable-simd")]
+    let (src, dst) = {
+        let done = crate::simd::total_keys_f32(src, dst);
+        (&src[done..], &mut dst[done..])
+    };
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = total_key_f32(s);
+    }
+}
+
+/// Writes the [`total_key_f64`] of every element of `src` to `dst`.
+///
+/// See [`total_keys_f32`] for details.
+pub fn total_keys_f64(src: &[f64], dst: &mut [i64]) {
+    assert_eq!(src.len(), dst.len(), "slices must have the same length");
+    #[cfg(feature = "portable-simd")]
+    let (src, dst) = {
+        let done = crate::simd::total_keys_f64(src, dst);
+        (&src[done..], &mut dst[done..])
+    };
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = total_key_f64(s);
+    }
+}
+
+/// Sorts a slice in the order of [`total_cmp`](FloatBits::total_cmp) by sorting integer keys,
+/// with an LSD radix sort for formats up to 32 bits wide.
+///
+/// The result is the same as that of `xs.sort_unstable()`.  The sort computes the keys of
+/// [`to_sortable_bytes`](crate::F64::to_sortable_bytes) once, as integers of the width of the
+/// format, sorts them, and writes the values back, allocating two buffers of keys as large as
+/// `xs`.  Formats up to 32 bits wide sort the keys with one linear pass per byte that is not the
+/// same for every element.  Wider formats would need so many passes that they sort the keys by
+/// comparison instead, as do short slices of any format.
+///
+/// Sorting five million random values, as the `radix_sort` example does, this took about a fifth
+/// of the time of `sort_unstable` for `BF16`, and 5 to 35 percent less for `F32`, `F64`, and
+/// `F128`, whose keys compare faster than the float types do.  The gain for the wider
+/// formats depends on the machine, so measure before relying on it.
+///
+/// # Features
+///
+/// Available only with feature: `alloc`.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::{F64, radix_sort_total};
+///
+/// let mut xs = [3.5, -0.0, f64::NAN, -1e300, 0.0].map(F64::from_float);
+/// radix_sort_total(&mut xs);
+/// let ys = xs.map(|x| x.to_float());
+/// assert_eq!([-1e300, -0.0, 0.0, 3.5], ys[..4]);
+/// assert!(ys[4].is_nan());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn radix_sort_total<T: FloatBits>(xs: &mut [T]) {
+    const MIN_LEN: usize = 64;
+
+    if xs.len() < MIN_LEN {
+        xs.sort_unstable();
+        return;
+    }
+    match T::BITS {
+        8 => sort_by_keys::<T, u8>(xs, radix_sort),
+        16 => sort_by_keys::<T, u16>(xs, radix_sort),
+        32 => sort_by_keys::<T, u32>(xs, radix_sort),
+        64 => sort_by_keys::<T, u64>(xs, |keys| keys.sort_unstable()),
+        _ => sort_by_keys::<T, u128>(xs, |keys| keys.sort_unstable()),
+    }
+}
+
+/// An unsigned integer as wide as a format, for the keys of [`radix_sort_total`].
+#[cfg(feature = "alloc")]
+trait SortKey: Copy + Default + Ord {
+    const BYTES: usize;
+
+    fn from_u128(bits: u128) -> Self;
+
+    fn to_u128(self) -> u128;
+
+    fn byte(self, i: usize) -> usize;
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! sort_key {
+    ($( $u_ty:ty ),*) => {
+        $(
+            impl SortKey for $u_ty {
+                const BYTES: usize = core::mem::size_of::<$u_ty>();
+
+                fn from_u128(bits: u128) -> Self {
+                    bits as $u_ty
+                }
+
+                fn to_u128(self) -> u128 {
+                    self as u128
+                }
+
+                fn byte(self, i: usize) -> usize {
+                    (self >> (8 * i)) as u8 as usize
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "alloc")]
+sort_key!(u8, u16, u32, u64, u128);
+
+/// Sorts `xs` by sorting their keys with `sort`, then writing the values of the keys back.
+#[cfg(feature = "alloc")]
+fn sort_by_keys<T: FloatBits, K: SortKey>(xs: &mut [T], sort: impl FnOnce(&mut Vec<K>)) {
+    // The bits with the sign bit flipped if positive and every bit flipped if negative.
+    let sign = 1 << (T::BITS - 1);
+    let mut keys: Vec<K> = xs
+        .iter()
+        .map(|x| {
+            let bits: u128 = x.to_bits().into();
+            K::from_u128(if bits & sign != 0 { !bits } else { bits | sign })
+        })
+        .collect();
+    sort(&mut keys);
+    for (x, key) in xs.iter_mut().zip(keys) {
+        let key = key.to_u128();
+        let bits = if key & sign != 0 { key ^ sign } else { !key & (sign | (sign - 1)) };
+        *x = match T::Bits::try_from(bits) {
+            Ok(bits) => T::from_bits(bits),
+            Err(_) => unreachable!("the key has the width of the format"),
+        };
+    }
+}
+
+/// Sorts `keys` with one pass per byte that is not the same for every key.
+#[cfg(feature = "alloc")]
+fn radix_sort<K: SortKey>(keys: &mut Vec<K>) {
+    let mut counts = [[0usize; 256]; 4];
+    let counts = &mut counts[..K::BYTES];
+    for &key in keys.iter() {
+        for (i, count) in counts.iter_mut().enumerate() {
+            count[key.byte(i)] += 1;
+        }
+    }
+
+    let mut buf = vec![K::default(); keys.len()];
+    for (i, count) in counts.iter().enumerate() {
+        if count.contains(&keys.len()) {
+            continue;
+        }
+        let mut offsets = [0usize; 256];
+        let mut sum = 0;
+        for (offset, &n) in offsets.iter_mut().zip(count) {
+            *offset = sum;
+            sum += n;
+        }
+        for &key in keys.iter() {
+            let digit = key.byte(i);
+            buf[offsets[digit]] = key;
+            offsets[digit] += 1;
+        }
+        core::mem::swap(keys, &mut buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_total_test() {
+        let mut xs = [
+            f64::NAN,
+            3.0,
+            -f64::NAN,
+            f64::MIN_POSITIVE,
+            -0.0,
+            f64::INFINITY,
+            0.0,
+            -1.0,
+            f64::from_bits(1),
+        ];
+        let mut expected = xs;
+        expected.sort_unstable_by(f64::total_cmp);
+        sort_total_f64(&mut xs);
+        assert_eq!(expected.map(f64::to_bits), xs.map(f64::to_bits));
+
+        let mut ys = xs.map(|x| x as f32);
+        let mut expected = ys;
+        expected.sort_unstable_by(f32::total_cmp);
+        sort_total_f32(&mut ys);
+        assert_eq!(expected.map(f32::to_bits), ys.map(f32::to_bits));
+    }
+
+    #[test]
+    fn total_key_test() {
+        let xs = [-f64::NAN, f64::NEG_INFINITY, -1.0, -0.0, 0.0, f64::from_bits(1), 1.0, f64::NAN];
+        for pair in xs.windows(2) {
+            assert!(total_key_f64(pair[0]) < total_key_f64(pair[1]));
+            let (a, b) = (pair[0] as f32, pair[1] as f32);
+            assert_eq!(a.total_cmp(&b), total_key_f32(a).cmp(&total_key_f32(b)));
+        }
+    }
+
+    #[test]
+    fn total_keys_test() {
+        let src: [f64; 37] = core::array::from_fn(|i| {
+            f64::from_bits((i as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15))
+        });
+        let mut keys = [0; 37];
+        total_keys_f64(&src, &mut keys);
+        assert_eq!(src.map(total_key_f64), keys);
+
+        let src = src.map(|x| x as f32);
+        let mut keys = [0; 37];
+        total_keys_f32(&src, &mut keys);
+        assert_eq!(src.map(total_key_f32), keys);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn radix_sort_total_test() {
+        use crate::{BF16, F8E5M2, F128};
+
+        let mut state = 0x243f_6a88_85a3_08d3u64;
+        let mut next = move || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            state
+        };
+
+        let mut xs: Vec<F64> = (0..1000).map(|_| F64::from_bits(next())).collect();
+        xs.extend([F64::ZERO, F64::NEG_ZERO, F64::ONE, F64::ONE, F64::NAN]);
+        let mut expected = xs.clone();
+        expected.sort_unstable();
+        radix_sort_total(&mut xs);
+        assert_eq!(expected, xs);
+
+        let mut ys: Vec<F32> =
+            (0..300).map(|_| F32::from_bits(next() as u32 & 0x807f_ffff)).collect();
+        let mut expected = ys.clone();
+        expected.sort_unstable();
+        radix_sort_total(&mut ys);
+        assert_eq!(expected, ys);
+
+        let mut zs: Vec<BF16> = (0..100).map(|_| BF16::from_bits((next() >> 48) as u16)).collect();
+        let mut expected = zs.clone();
+        expected.sort_unstable();
+        radix_sort_total(&mut zs);
+        assert_eq!(expected, zs);
+
+        let mut ws: Vec<F128> = (0..100)
+            .map(|_| F128::from_bits(u128::from(next()) << 64 | u128::from(next())))
+            .collect();
+        let mut expected = ws.clone();
+        expected.sort_unstable();
+        radix_sort_total(&mut ws);
+        assert_eq!(expected, ws);
+
+        let mut vs: Vec<F8E5M2> =
+            (0..300).map(|_| F8E5M2::from_bits((next() >> 56) as u8)).collect();
+        let mut expected = vs.clone();
+        expected.sort_unstable();
+        radix_sort_total(&mut vs);
+        assert_eq!(expected, vs);
+
+        let mut short = [F32::ONE, F32::NEG_ONE];
+        radix_sort_total(&mut short);
+        assert_eq!([F32::NEG_ONE, F32::ONE], short);
+    }
+}
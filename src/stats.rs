@@ -0,0 +1,259 @@
+//! Diagnostics over slices of floats.
+
+use core::num::FpCategory;
+
+use crate::{BF16, F16, F32, F64, F128, FloatBits};
+
+/// A summary of the values in a slice, for spotting NaNs, overflow, and underflow.
+///
+/// The summary counts the values in each [`FpCategory`], tracks the least and greatest values in
+/// the order of `total_cmp`, and keeps a histogram of the biased exponent fields.  Bucket `0` of
+/// the histogram holds the zeros and subnormals, and the last bucket holds the infinities and
+/// NaNs, so a histogram that piles up at either end shows values that are vanishing or exploding.
+///
+/// Summaries are available for the formats whose exponent field is at most 8 bits wide.
+///
+/// # Example
+///
+/// ```rust
+/// # use core::num::FpCategory;
+/// # use float_bits::{F32, SliceStats};
+///
+/// let values = [1.0, 1.5, f32::NAN, 0.0, 1e-40, -3.0].map(F32::from_float);
+/// let stats = SliceStats::analyze(&values);
+/// assert_eq!(1, stats.count(FpCategory::Nan));
+/// assert_eq!(1, stats.count(FpCategory::Subnormal));
+/// assert_eq!(Some(F32::from_float(-3.0)), stats.min());
+/// assert!(stats.max().unwrap().is_nan());
+/// assert_eq!(2, stats.exponent_histogram()[127]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SliceStats<T> {
+    nan: usize,
+    infinite: usize,
+    zero: usize,
+    subnormal: usize,
+    normal: usize,
+    min: Option<T>,
+    max: Option<T>,
+    exponents: [usize; 256],
+}
+
+impl<T: FloatBits> SliceStats<T>
+where
+    Self: FromIterator<T>,
+{
+    /// Returns the summary of the values in `values`.
+    pub fn analyze(values: &[T]) -> Self {
+        values.iter().copied().collect()
+    }
+}
+
+macro_rules! define_slice_stats {
+    ($ty:ident) => {
+        impl SliceStats<$ty> {
+            /// Creates an empty summary.
+            pub const fn new() -> Self {
+                Self {
+                    nan: 0,
+                    infinite: 0,
+                    zero: 0,
+                    subnormal: 0,
+                    normal: 0,
+                    min: None,
+                    max: None,
+                    exponents: [0; 256],
+                }
+            }
+
+            /// Adds `value` to the summary.
+            pub fn push(&mut self, value: $ty) {
+                match value.classify() {
+                    FpCategory::Nan => self.nan += 1,
+                    FpCategory::Infinite => self.infinite += 1,
+                    FpCategory::Zero => self.zero += 1,
+                    FpCategory::Subnormal => self.subnormal += 1,
+                    FpCategory::Normal => self.normal += 1,
+                }
+                match self.min {
+                    Some(min) if min.total_cmp(value).is_le() => {},
+                    _ => self.min = Some(value),
+                }
+                match self.max {
+                    Some(max) if max.total_cmp(value).is_ge() => {},
+                    _ => self.max = Some(value),
+                }
+                self.exponents[((value.bits & $ty::EXP_MASK) >> $ty::MANTISSA_BITS) as usize] += 1;
+            }
+
+            /// Returns the number of values summarized.
+            pub const fn len(&self) -> usize {
+                self.nan + self.infinite + self.zero + self.subnormal + self.normal
+            }
+
+            /// Returns `true` if no values have been summarized.
+            pub const fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            /// Returns the number of values in `category`.
+            pub const fn count(&self, category: FpCategory) -> usize {
+                match category {
+                    FpCategory::Nan => self.nan,
+                    FpCategory::Infinite => self.infinite,
+                    FpCategory::Zero => self.zero,
+                    FpCategory::Subnormal => self.subnormal,
+                    FpCategory::Normal => self.normal,
+                }
+            }
+
+            /// Returns the least value in the order of [`total_cmp`]($ty::total_cmp), or `None`
+            /// if no values have been summarized.
+            ///
+            /// A negative NaN is less than every other value, so it is returned if present.
+            pub const fn min(&self) -> Option<$ty> {
+                self.min
+            }
+
+            /// Returns the greatest value in the order of [`total_cmp`]($ty::total_cmp), or
+            /// `None` if no values have been summarized.
+            ///
+            /// A positive NaN is greater than every other value, so it is returned if present.
+            pub const fn max(&self) -> Option<$ty> {
+                self.max
+            }
+
+            /// Returns the number of values with each biased exponent field, indexed by the
+            /// field.
+            ///
+            /// The slice has one bucket per possible field, so the bucket of a normal value
+            /// `m × 2^e` is `e` plus the exponent bias.  Zeros and subnormals share the first
+            /// bucket, and infinities and NaNs share the last.
+            pub fn exponent_histogram(&self) -> &[usize] {
+                &self.exponents[..1 << $ty::EXP_BITS]
+            }
+        }
+
+        impl Default for SliceStats<$ty> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl Extend<$ty> for SliceStats<$ty> {
+            fn extend<I: IntoIterator<Item = $ty>>(&mut self, iter: I) {
+                for value in iter {
+                    self.push(value);
+                }
+            }
+        }
+
+        impl FromIterator<$ty> for SliceStats<$ty> {
+            fn from_iter<I: IntoIterator<Item = $ty>>(iter: I) -> Self {
+                let mut stats = Self::new();
+                stats.extend(iter);
+                stats
+            }
+        }
+    };
+}
+
+macro_rules! define_classify_slice {
+    ($ty:ident $(, $simd:path)?) => {
+        impl $ty {
+            /// Writes the [category](Self::classify) of every element of `values` to `out`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `values` and `out` have different lengths.
+            pub fn classify_slice(values: &[Self], out: &mut [FpCategory]) {
+                assert_eq!(values.len(), out.len(), "slices must have the same length");
+                $(
+                    #[cfg(feature = "portable-simd")]
+                    let (values, out) = {
+                        let done = $simd(values, out);
+                        (&values[done..], &mut out[done..])
+                    };
+                )?
+                for (d, s) in out.iter_mut().zip(values) {
+                    *d = s.classify();
+                }
+            }
+        }
+    };
+}
+
+define_classify_slice!(BF16);
+define_classify_slice!(F16);
+define_classify_slice!(F32, crate::simd::classify_f32);
+define_classify_slice!(F64);
+define_classify_slice!(F128);
+
+define_slice_stats!(BF16);
+define_slice_stats!(F16);
+define_slice_stats!(F32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slice_stats_test() {
+        let empty = SliceStats::<F32>::analyze(&[]);
+        assert!(empty.is_empty());
+        assert_eq!((None, None), (empty.min(), empty.max()));
+        assert_eq!(256, empty.exponent_histogram().len());
+
+        let values = [
+            F16::NEG_QNAN,
+            F16::INFINITY,
+            F16::NEG_ZERO,
+            F16::from_bits(1),
+            F16::ONE,
+            F16::ONE,
+            F16::MAX,
+        ];
+        let stats = SliceStats::analyze(&values);
+        assert_eq!(7, stats.len());
+        assert_eq!(
+            [1, 1, 1, 1, 3],
+            [
+                FpCategory::Nan,
+                FpCategory::Infinite,
+                FpCategory::Zero,
+                FpCategory::Subnormal,
+                FpCategory::Normal
+            ]
+            .map(|c| stats.count(c))
+        );
+        assert_eq!(Some(F16::NEG_QNAN), stats.min());
+        assert_eq!(Some(F16::INFINITY), stats.max());
+
+        let histogram = stats.exponent_histogram();
+        assert_eq!(32, histogram.len());
+        assert_eq!([2, 2, 1, 2], [histogram[0], histogram[15], histogram[30], histogram[31]]);
+        assert_eq!(7, histogram.iter().sum::<usize>());
+
+        let mut stats: SliceStats<BF16> = [BF16::ONE].into_iter().collect();
+        stats.push(BF16::NEG_ONE);
+        assert_eq!((Some(BF16::NEG_ONE), Some(BF16::ONE)), (stats.min(), stats.max()));
+    }
+
+    #[test]
+    fn classify_slice_test() {
+        let values: [F32; 40] = core::array::from_fn(|i| match i % 5 {
+            0 => F32::from_bits(i as u32),
+            1 => F32::from_bits(0x8000_0000 | (i as u32) << 20),
+            2 => F32::from_bits(0x7f80_0000 | (i as u32 % 2)),
+            3 => F32::ZERO,
+            _ => F32::from_bits((i as u32) << 26),
+        });
+        let mut out = [FpCategory::Zero; 40];
+        F32::classify_slice(&values, &mut out);
+        assert_eq!(values.map(|x| x.classify()), out);
+
+        let mut out = [FpCategory::Zero; 2];
+        F128::classify_slice(&[F128::ONE, F128::NAN], &mut out);
+        assert_eq!([FpCategory::Normal, FpCategory::Nan], out);
+    }
+}
@@ -0,0 +1,156 @@
+use core::fmt;
+use core::ops::{BitAnd, BitOr, BitOrAssign};
+
+/// The IEEE 754 exception flags that an operation raised.
+///
+/// Each flag is one bit, and a set of flags combines with `|` just as the sticky status flags of
+/// IEEE 754 accumulate over a computation.  Underflow is detected before rounding, that is, when
+/// the exact result is nonzero and smaller in magnitude than [`MIN_POSITIVE`](crate::F64::MIN_POSITIVE),
+/// and is raised only if the result is also inexact, as in the default exception handling of
+/// IEEE 754.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::{F64, RoundingMode, StatusFlags};
+///
+/// let mode = RoundingMode::TiesToEven;
+/// let (sum, flags) = F64::ONE.add_with_flags(F64::from_float(0.1), mode);
+/// assert_eq!(F64::from_float(1.1), sum);
+/// assert_eq!(StatusFlags::INEXACT, flags);
+/// assert!(F64::ONE.div_with_flags(F64::ZERO, mode).1.contains(StatusFlags::DIV_BY_ZERO));
+/// ```
+#[derive(Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct StatusFlags(u8);
+
+impl StatusFlags {
+    /// No flags.
+    pub const NONE: Self = Self(0);
+
+    /// The operation had no meaningful result, such as `0 / 0`, or an operand was a signaling NaN.
+    /// The result is a quiet NaN.
+    pub const INVALID: Self = Self(1);
+
+    /// A finite nonzero number was divided by zero, giving an exact infinity.
+    pub const DIV_BY_ZERO: Self = Self(2);
+
+    /// The rounded result was too large in magnitude to be finite, or would have been had the
+    /// exponent range been unbounded.
+    pub const OVERFLOW: Self = Self(4);
+
+    /// The exact result was tiny, below the normal range, and the rounded result was inexact.
+    pub const UNDERFLOW: Self = Self(8);
+
+    /// The rounded result differs from the exact result.
+    pub const INEXACT: Self = Self(16);
+
+    /// All flags.
+    pub const ALL: Self = Self(31);
+
+    const NAMES: [(Self, &'static str); 5] = [
+        (Self::INVALID, "INVALID"),
+        (Self::DIV_BY_ZERO, "DIV_BY_ZERO"),
+        (Self::OVERFLOW, "OVERFLOW"),
+        (Self::UNDERFLOW, "UNDERFLOW"),
+        (Self::INEXACT, "INEXACT"),
+    ];
+
+    /// Returns `true` if no flag is set.
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if every flag of `other` is set in `self`.
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns the flags set in either `self` or `other`.
+    pub const fn union(&self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Returns the flags set in both `self` and `other`.
+    pub const fn intersection(&self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// Returns the raw bits of the set, one per flag in the order of the constants.
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Returns the set with the given raw bits, ignoring bits that are not flags.
+    pub const fn from_bits_truncate(bits: u8) -> Self {
+        Self(bits & Self::ALL.0)
+    }
+}
+
+impl BitOr for StatusFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+impl BitOrAssign for StatusFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.union(rhs);
+    }
+}
+
+impl BitAnd for StatusFlags {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        self.intersection(rhs)
+    }
+}
+
+impl fmt::Debug for StatusFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("StatusFlags(")?;
+        let mut first = true;
+        for (flag, name) in Self::NAMES {
+            if self.contains(flag) {
+                if !first {
+                    f.write_str(" | ")?;
+                }
+                f.write_str(name)?;
+                first = false;
+            }
+        }
+        if first {
+            f.write_str("NONE")?;
+        }
+        f.write_str(")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_flags_test() {
+        let flags = StatusFlags::OVERFLOW | StatusFlags::INEXACT;
+        assert!(flags.contains(StatusFlags::INEXACT) && !flags.contains(StatusFlags::INVALID));
+        assert_eq!(StatusFlags::INEXACT, flags & StatusFlags::INEXACT);
+        assert!(StatusFlags::default().is_empty());
+        assert_eq!(StatusFlags::ALL, StatusFlags::from_bits_truncate(0xff));
+        let mut acc = StatusFlags::NONE;
+        acc |= StatusFlags::UNDERFLOW;
+        assert_eq!(8, acc.bits());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn debug_test() {
+        use std::format;
+
+        assert_eq!("StatusFlags(NONE)", format!("{:?}", StatusFlags::NONE));
+        let flags = StatusFlags::OVERFLOW | StatusFlags::INEXACT;
+        assert_eq!("StatusFlags(OVERFLOW | INEXACT)", format!("{flags:?}"));
+    }
+}
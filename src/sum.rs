@@ -0,0 +1,444 @@
+//! Deterministic accumulation primitives.
+
+use crate::soft::{self, Unpacked};
+use crate::{F32, F64, RoundingMode};
+
+/// An accumulator that sums floats with Neumaier's compensated summation.
+///
+/// A running compensation term captures the low-order bits lost by each addition, so the result
+/// is typically as accurate as if the sum were computed in twice the precision and then rounded.
+/// The result depends only on the sequence of values pushed, never on the platform.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::{CompensatedSum, F64};
+///
+/// let values = [1.0, 1e100, 1.0, -1e100].map(F64::from_float);
+/// let sum: CompensatedSum<F64> = values.into_iter().collect();
+/// assert_eq!(F64::from_float(2.0), sum.finish());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CompensatedSum<T> {
+    sum: T,
+    comp: T,
+}
+
+macro_rules! define_compensated_sum {
+    ($ty:ident) => {
+        impl CompensatedSum<$ty> {
+            /// Creates an empty accumulator, whose sum is `+0.0`.
+            pub const fn new() -> Self {
+                Self { sum: $ty::ZERO, comp: $ty::ZERO }
+            }
+
+            /// Adds `value` to the running sum.
+            pub fn push(&mut self, value: $ty) {
+                let (sum, x) = (self.sum.to_float(), value.to_float());
+                let t = sum + x;
+                let lost = if sum.abs() >= x.abs() { (sum - t) + x } else { (x - t) + sum };
+                self.sum = $ty::from_float(t);
+                self.comp = $ty::from_float(self.comp.to_float() + lost);
+            }
+
+            /// Returns the compensated sum of all values pushed so far.
+            ///
+            /// If the running sum is infinite or NaN, it is returned as-is, since the
+            /// compensation term is meaningless in that case.
+            pub fn finish(&self) -> $ty {
+                if self.sum.is_finite() {
+                    $ty::from_float(self.sum.to_float() + self.comp.to_float())
+                } else {
+                    self.sum
+                }
+            }
+        }
+
+        impl Default for CompensatedSum<$ty> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl Extend<$ty> for CompensatedSum<$ty> {
+            fn extend<I: IntoIterator<Item = $ty>>(&mut self, iter: I) {
+                for value in iter {
+                    self.push(value);
+                }
+            }
+        }
+
+        impl FromIterator<$ty> for CompensatedSum<$ty> {
+            fn from_iter<I: IntoIterator<Item = $ty>>(iter: I) -> Self {
+                let mut sum = Self::new();
+                sum.extend(iter);
+                sum
+            }
+        }
+    };
+}
+
+define_compensated_sum!(F32);
+define_compensated_sum!(F64);
+
+/// Number of 64-bit limbs in the [`ExactSum`] accumulator.
+///
+/// Exact products of two finite `f64` values span bit positions `2^-2148` through `2^2047` of a
+/// fixed-point number, which is 4196 bits.  The remaining bits leave room for the sign and for
+/// carries, so that no realistic number of additions can overflow the accumulator.
+const EXACT_LIMBS: usize = 70;
+
+/// Exponent of the least significant bit of the [`ExactSum`] accumulator.
+const EXACT_UNIT_EXP: i64 = -2148;
+
+/// An accumulator that computes the exact sum of [`F64`] values, rounded once.
+///
+/// Every value is added into a fixed-point "superaccumulator" wide enough to hold any finite
+/// `f64` without loss, so the result is the exact mathematical sum rounded to nearest, ties to
+/// even.  In particular, the result does not depend on the order in which values are pushed, nor
+/// on how the values were split between accumulators that are later [merged](Self::merge).
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::{ExactSum, F64};
+///
+/// let values = [1e308, 1e308, 1.0, -1e308, -1e308].map(F64::from_float);
+/// assert_eq!(F64::ONE, ExactSum::sum(&values));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExactSum {
+    // Two's complement fixed-point integer, in units of 2^-2148, least significant limb first.
+    acc: [u64; EXACT_LIMBS],
+    nan: bool,
+    pos_inf: bool,
+    neg_inf: bool,
+    empty: bool,
+    all_neg_zero: bool,
+    all_pos_zero: bool,
+}
+
+impl ExactSum {
+    /// Creates an empty accumulator, whose sum is `+0.0`.
+    pub const fn new() -> Self {
+        Self {
+            acc: [0; EXACT_LIMBS],
+            nan: false,
+            pos_inf: false,
+            neg_inf: false,
+            empty: true,
+            all_neg_zero: true,
+            all_pos_zero: true,
+        }
+    }
+
+    /// Returns the correctly rounded sum of `values`.
+    pub fn sum(values: &[F64]) -> F64 {
+        let mut sum = Self::new();
+        sum.extend(values.iter().copied());
+        sum.finish()
+    }
+
+    /// Adds `value` to the running sum.
+    pub const fn push(&mut self, value: F64) {
+        self.push_product(value, F64::ONE);
+    }
+
+    /// Adds the exact product `a × b` to the running sum, without rounding it first.
+    pub const fn push_product(&mut self, a: F64, b: F64) {
+        self.empty = false;
+        let negative = a.is_sign_negative() != b.is_sign_negative();
+        if a.is_zero() || b.is_zero() {
+            if a.is_nan() || b.is_nan() || a.is_infinite() || b.is_infinite() {
+                self.nan = true;
+            }
+            self.all_neg_zero &= negative;
+            self.all_pos_zero &= !negative;
+            return;
+        }
+        self.all_neg_zero = false;
+        self.all_pos_zero = false;
+        if a.is_nan() || b.is_nan() {
+            self.nan = true;
+        } else if a.is_infinite() || b.is_infinite() {
+            if negative {
+                self.neg_inf = true;
+            } else {
+                self.pos_inf = true;
+            }
+        } else {
+            let (mant_a, shift_a) = decompose(a);
+            let (mant_b, shift_b) = decompose(b);
+            self.add_shifted(mant_a as u128 * mant_b as u128, shift_a + shift_b, negative);
+        }
+    }
+
+    // Adds or subtracts `mant × 2^shift` (in units of 2^-2148) to the accumulator.
+    const fn add_shifted(&mut self, mant: u128, shift: u32, negative: bool) {
+        let bit = shift % 64;
+        let lo = mant << bit;
+        let hi = if bit == 0 { 0 } else { mant >> (128 - bit) };
+        let parts = [lo as u64, (lo >> 64) as u64, hi as u64];
+        let mut i = (shift / 64) as usize;
+        let mut j = 0;
+        let mut carry = false;
+        while i < EXACT_LIMBS && (j < parts.len() || carry) {
+            let part = if j < parts.len() { parts[j] } else { 0 };
+            let (v, c1, c2);
+            if negative {
+                (v, c1) = self.acc[i].overflowing_sub(part);
+                (self.acc[i], c2) = v.overflowing_sub(carry as u64);
+            } else {
+                (v, c1) = self.acc[i].overflowing_add(part);
+                (self.acc[i], c2) = v.overflowing_add(carry as u64);
+            }
+            carry = c1 || c2;
+            i += 1;
+            j += 1;
+        }
+    }
+
+    /// Adds the values accumulated by `other` to `self`.
+    pub const fn merge(&mut self, other: &Self) {
+        let mut carry = false;
+        let mut i = 0;
+        while i < EXACT_LIMBS {
+            let (v, c1) = self.acc[i].overflowing_add(other.acc[i]);
+            let (v, c2) = v.overflowing_add(carry as u64);
+            self.acc[i] = v;
+            carry = c1 || c2;
+            i += 1;
+        }
+        self.nan |= other.nan;
+        self.pos_inf |= other.pos_inf;
+        self.neg_inf |= other.neg_inf;
+        self.empty &= other.empty;
+        self.all_neg_zero &= other.all_neg_zero;
+        self.all_pos_zero &= other.all_pos_zero;
+    }
+
+    /// Returns the exact sum of all values pushed so far, rounded to nearest, ties to even.
+    ///
+    /// Following IEEE 754, the sum is NaN if any value was NaN (or a product was `0 × ∞`) or if
+    /// infinities of both signs were pushed, and infinite if infinities of only one sign were
+    /// pushed.  An exact sum of zero is `-0.0` if every value pushed was `-0.0`, and `+0.0`
+    /// otherwise.
+    pub const fn finish(&self) -> F64 {
+        self.finish_rounded(RoundingMode::TiesToEven)
+    }
+
+    /// Returns the exact sum of all values pushed so far, rounded according to `mode`.
+    ///
+    /// See [`Self::finish`] for the special cases.  As in IEEE 754, an exact sum of zero rounded
+    /// toward negative infinity is `-0.0` unless every value pushed was `+0.0`.
+    pub const fn finish_rounded(&self, mode: RoundingMode) -> F64 {
+        if self.nan || (self.pos_inf && self.neg_inf) {
+            return F64::NAN;
+        } else if self.pos_inf {
+            return F64::INFINITY;
+        } else if self.neg_inf {
+            return F64::NEG_INFINITY;
+        }
+
+        let negative = (self.acc[EXACT_LIMBS - 1] >> 63) != 0;
+        let mut mag = self.acc;
+        if negative {
+            let mut carry = true;
+            let mut i = 0;
+            while i < EXACT_LIMBS {
+                let (v, c) = (!mag[i]).overflowing_add(carry as u64);
+                mag[i] = v;
+                carry = c;
+                i += 1;
+            }
+        }
+
+        let mut top = EXACT_LIMBS;
+        while top > 0 && mag[top - 1] == 0 {
+            top -= 1;
+        }
+        if top == 0 {
+            let zero = F64::ZERO;
+            let neg = match mode {
+                RoundingMode::TowardNegative => !self.all_pos_zero,
+                _ => !self.empty && self.all_neg_zero,
+            };
+            return if neg { zero.neg() } else { zero };
+        }
+
+        // Gather the 128 bits starting at the most significant set bit.
+        let msb = (top * 64 - 1) as i64 - mag[top - 1].leading_zeros() as i64;
+        let lo = msb - 127;
+        let mut sig: u128 = 0;
+        let mut sticky = false;
+        let mut i = 0;
+        while i < top {
+            let pos = (i * 64) as i64 - lo;
+            let limb = mag[i] as u128;
+            if pos >= 0 {
+                sig |= limb << pos;
+            } else if pos > -64 {
+                sig |= limb >> -pos;
+                sticky |= (limb << (128 + pos)) != 0;
+            } else {
+                sticky |= limb != 0;
+            }
+            i += 1;
+        }
+        let value = Unpacked::finite(negative, (msb + EXACT_UNIT_EXP) as i32, sig);
+        let bits = soft::round(F64::FORMAT, value, sticky, mode);
+        F64::from_bits(bits as u64)
+    }
+}
+
+impl Default for ExactSum {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Extend<F64> for ExactSum {
+    fn extend<I: IntoIterator<Item = F64>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl FromIterator<F64> for ExactSum {
+    fn from_iter<I: IntoIterator<Item = F64>>(iter: I) -> Self {
+        let mut sum = Self::new();
+        sum.extend(iter);
+        sum
+    }
+}
+
+// Splits a finite non-zero value into an integer significand and a shift, such that the value's
+// magnitude is `mant × 2^(shift − 1074)`.
+const fn decompose(value: F64) -> (u64, u32) {
+    let bits = value.to_bits();
+    let exp_field = ((bits >> 52) & 0x7ff) as u32;
+    let mant = bits & ((1 << 52) - 1);
+    if exp_field == 0 { (mant, 0) } else { (mant | (1 << 52), exp_field - 1) }
+}
+
+/// Returns the dot product of `xs` and `ys`, rounded once.
+///
+/// Every product is accumulated exactly by an [`ExactSum`], so the result is the exact dot
+/// product rounded to nearest, ties to even, regardless of the length or order of the inputs.
+///
+/// # Panics
+///
+/// Panics if `xs` and `ys` have different lengths.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::{F64, exact_dot};
+///
+/// let xs = [1e300, 1.0, -1e300].map(F64::from_float);
+/// let ys = [1e10, 1e-10, 1e10].map(F64::from_float);
+/// assert_eq!(F64::from_float(1e-10), exact_dot(&xs, &ys));
+/// ```
+pub fn exact_dot(xs: &[F64], ys: &[F64]) -> F64 {
+    assert_eq!(xs.len(), ys.len(), "slices must have the same length");
+    let mut sum = ExactSum::new();
+    for (&x, &y) in xs.iter().zip(ys) {
+        sum.push_product(x, y);
+    }
+    sum.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compensated_sum_test() {
+        let mut sum = CompensatedSum::<F64>::new();
+        for _ in 0..10 {
+            sum.push(F64::from_float(0.1));
+        }
+        assert_eq!(F64::ONE, sum.finish());
+
+        let values = [1e16, 1.0, -1e16].map(F64::from_float);
+        assert_eq!(F64::ONE, values.into_iter().collect::<CompensatedSum<F64>>().finish());
+
+        let values = [1.0, F32::MAX.to_float(), F32::MAX.to_float()].map(F32::from_float);
+        assert_eq!(F32::INFINITY, values.into_iter().collect::<CompensatedSum<F32>>().finish());
+
+        assert_eq!(F32::ZERO, CompensatedSum::<F32>::default().finish());
+    }
+
+    #[test]
+    fn exact_sum_test() {
+        let f = F64::from_float;
+        let tiny = F64::from_bits(1);
+        assert_eq!(F64::ONE, ExactSum::sum(&[f(0.1); 10]));
+        assert_eq!(f(2e-60), ExactSum::sum(&[f(1.0), f(2e-60), f(-1.0)]));
+        assert_eq!(F64::ONE, ExactSum::sum(&[f(1.0), f(0.5f64.powi(53))]));
+        assert_eq!(
+            0x3ff0000000000001,
+            ExactSum::sum(&[f(1.0), f(0.5f64.powi(53)), tiny]).to_bits()
+        );
+        assert_eq!(3, ExactSum::sum(&[tiny, tiny, tiny]).to_bits());
+        assert_eq!(F64::INFINITY, ExactSum::sum(&[F64::MAX, F64::MAX]));
+        assert_eq!(F64::MAX, ExactSum::sum(&[F64::MAX, F64::MAX, F64::MIN]));
+        assert_eq!(F64::MIN, ExactSum::sum(&[F64::MIN, F64::MIN, F64::MAX]));
+        assert_eq!(f(-0.75), ExactSum::sum(&[f(-1.0), f(0.25)]));
+        assert!(ExactSum::sum(&[F64::INFINITY, F64::NEG_INFINITY]).is_nan());
+        assert_eq!(F64::NEG_INFINITY, ExactSum::sum(&[F64::NEG_INFINITY, F64::MAX]));
+        assert_eq!(F64::NEG_ZERO, ExactSum::sum(&[F64::NEG_ZERO, F64::NEG_ZERO]));
+        assert_eq!(F64::ZERO, ExactSum::sum(&[F64::NEG_ZERO, F64::ZERO]));
+        assert_eq!(F64::ZERO, ExactSum::sum(&[f(1.5), f(-1.5)]));
+        assert_eq!(F64::ZERO, ExactSum::sum(&[]));
+
+        let finish = |values: &[F64], mode| {
+            values.iter().copied().collect::<ExactSum>().finish_rounded(mode)
+        };
+        let (up, down) = (RoundingMode::TowardPositive, RoundingMode::TowardNegative);
+        let eps = f(f64::EPSILON);
+        assert_eq!(f(1.0 + f64::EPSILON), finish(&[f(1.0), f(2e-60)], up));
+        assert_eq!(F64::ONE, finish(&[f(1.0), f(2e-60)], down));
+        assert_eq!(f(-1.0 - f64::EPSILON), finish(&[f(-1.0), f(-2e-60)], down));
+        assert_eq!(F64::ONE, finish(&[f(1.0), eps.neg(), eps], up));
+        assert_eq!(F64::MAX, finish(&[F64::MAX, F64::MAX], down));
+        assert_eq!(F64::NEG_ZERO, finish(&[f(1.5), f(-1.5)], down));
+        assert_eq!(F64::NEG_ZERO, finish(&[F64::ZERO, F64::NEG_ZERO], down));
+        assert_eq!(F64::ZERO, finish(&[F64::ZERO, F64::ZERO], down));
+        assert_eq!(F64::ZERO, finish(&[], down));
+        assert_eq!(F64::ZERO, finish(&[f(1.5), f(-1.5)], up));
+
+        let values = [1e300, -3.5, 1e-300, 7.25, -1e300, 2.0f64.powi(-1070)].map(f);
+        let expected = ExactSum::sum(&values);
+        let mut left: ExactSum = values[..3].iter().copied().collect();
+        let right: ExactSum = values[3..].iter().rev().copied().collect();
+        left.merge(&right);
+        assert_eq!(expected, left.finish());
+        assert_eq!(f(3.75), expected);
+    }
+
+    #[test]
+    fn exact_dot_test() {
+        let f = F64::from_float;
+        let tiny = F64::from_bits(1);
+        assert_eq!(f(32.0), exact_dot(&[1.0, 2.0, 3.0].map(f), &[4.0, 5.0, 6.0].map(f)));
+        assert_eq!(F64::ZERO, exact_dot(&[tiny], &[tiny]));
+        assert_eq!(F64::MAX, exact_dot(&[F64::MAX, F64::MAX], &[f(2.0), f(-1.0)]));
+        assert_eq!(f(-0.25), exact_dot(&[tiny, f(-0.5)], &[tiny, f(0.5)]));
+        assert_eq!(
+            F64::NEG_ZERO,
+            exact_dot(&[F64::ZERO, F64::NEG_ONE], &[F64::NEG_ONE, F64::ZERO])
+        );
+        assert!(exact_dot(&[F64::ZERO], &[F64::INFINITY]).is_nan());
+        assert_eq!(
+            F64::NEG_INFINITY,
+            exact_dot(&[F64::INFINITY, F64::ONE], &[F64::NEG_ONE, F64::MAX])
+        );
+
+        // (1 + 2^-30)^2 − 1 − 2^-29 = 2^-60, which is lost if the products are rounded first.
+        let x = f(1.0 + 2.0f64.powi(-30));
+        let xs = [x, F64::NEG_ONE, f(-(2.0f64.powi(-29)))];
+        assert_eq!(f(2.0f64.powi(-60)), exact_dot(&xs, &[x, F64::ONE, F64::ONE]));
+    }
+}
@@ -0,0 +1,635 @@
+//! The traits shared by every float type, for code that works with any format.
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::Hash;
+use core::num::FpCategory;
+use core::str::FromStr;
+
+use crate::soft::{Format, convert};
+use crate::{BF16, F8E5M2, F16, F32, F64, F128, LowerHexFloat, RoundingMode, StatusFlags};
+
+/// A float type of this crate, in any format.
+///
+/// Every type has the same inherent API; this trait exposes the format-independent part of it, so
+/// that code can be written once for all the formats.  The methods behave exactly as the inherent
+/// methods of the same names, which they call, and take `&self` as those do, so that the inherent
+/// methods still take precedence for concrete types and can be called in `const` contexts.
+///
+/// This trait is sealed: it is implemented for [`F8E5M2`], [`BF16`], [`F16`], [`F32`], [`F64`],
+/// and [`F128`], and cannot be implemented outside this crate.
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::{F16, F64, FloatBits};
+///
+/// fn count_finite<T: FloatBits>(xs: &[T]) -> usize {
+///     xs.iter().filter(|x| x.is_finite()).count()
+/// }
+///
+/// assert_eq!(1, count_finite(&[F16::ONE, F16::INFINITY, F16::QNAN]));
+/// assert_eq!(2, count_finite(&[F64::MAX, F64::MIN_POSITIVE]));
+/// ```
+pub trait FloatBits:
+    sealed::Sealed
+    + Copy
+    + Default
+    + Hash
+    + Ord
+    + fmt::Debug
+    + fmt::Display
+    + FromStr<Err: core::error::Error>
+    + Send
+    + Sync
+    + 'static
+{
+    /// The unsigned integer type holding the raw bits.
+    type Bits: Copy
+        + Eq
+        + Ord
+        + Hash
+        + fmt::Debug
+        + fmt::Binary
+        + fmt::LowerHex
+        + Into<u128>
+        + TryFrom<u128>;
+
+    /// The signed integer type of the same width as [`Self::Bits`].
+    type SignedBits: Copy + Eq + Ord + Hash + fmt::Debug + Into<i128>;
+
+    /// Total number of bits.
+    const BITS: usize;
+
+    /// Number of bits in the exponent field.
+    const EXP_BITS: usize;
+
+    /// Number of significant digits in base 2, counting the implicit leading 1.
+    const MANTISSA_DIGITS: usize;
+
+    /// Positive zero (`+0.0`).
+    const ZERO: Self;
+
+    /// Negative zero (`−0.0`).
+    const NEG_ZERO: Self;
+
+    /// Positive one (`+1.0`).
+    const ONE: Self;
+
+    /// Negative one (`−1.0`).
+    const NEG_ONE: Self;
+
+    /// Positive infinity (`+∞`).
+    const INFINITY: Self;
+
+    /// Negative infinity (`−∞`).
+    const NEG_INFINITY: Self;
+
+    /// A positive quiet NaN.
+    const QNAN: Self;
+
+    /// A positive signaling NaN.
+    const SNAN: Self;
+
+    /// The greatest finite value.
+    const MAX: Self;
+
+    /// The least finite value.
+    const MIN: Self;
+
+    /// The least positive normal value.
+    const MIN_POSITIVE: Self;
+
+    /// Constructs a float from its raw bits.
+    fn from_bits(bits: Self::Bits) -> Self;
+
+    /// Returns the raw bits.
+    fn to_bits(&self) -> Self::Bits;
+
+    /// Returns the floating point category of the number.
+    fn classify(&self) -> FpCategory;
+
+    /// Returns `true` if `self` has a positive sign, including `+0.0`, positive NaNs, and `+∞`.
+    fn is_sign_positive(&self) -> bool;
+
+    /// Returns `true` if `self` has a negative sign, including `−0.0`, negative NaNs, and `−∞`.
+    fn is_sign_negative(&self) -> bool;
+
+    /// Returns `true` if `self` is either zero.
+    fn is_zero(&self) -> bool;
+
+    /// Returns `true` if `self` is subnormal.
+    fn is_subnormal(&self) -> bool;
+
+    /// Returns `true` if `self` is neither zero, infinite, subnormal, nor NaN.
+    fn is_normal(&self) -> bool;
+
+    /// Returns `true` if `self` is either infinity.
+    fn is_infinite(&self) -> bool;
+
+    /// Returns `true` if `self` is NaN.
+    fn is_nan(&self) -> bool;
+
+    /// Returns `true` if `self` is neither infinite nor NaN.
+    fn is_finite(&self) -> bool;
+
+    /// Computes the absolute value of `self`, exactly.
+    fn abs(&self) -> Self;
+
+    /// Computes the negation of `self`, exactly.
+    fn neg(&self) -> Self;
+
+    /// Returns `1.0` or `−1.0` with the sign of `self`, or `self` if it is NaN.
+    fn signum(&self) -> Self;
+
+    /// Returns the magnitude of `self` with the sign of `sign`.
+    fn copysign(&self, sign: Self) -> Self;
+
+    /// Returns the least number greater than `self`.
+    fn next_up(&self) -> Self;
+
+    /// Returns the greatest number less than `self`.
+    fn next_down(&self) -> Self;
+
+    /// Returns the ordering between `self` and `other` by the IEEE 754 `totalOrder` predicate.
+    fn total_cmp(&self, other: Self) -> Ordering;
+
+    /// Returns the ordering between the absolute values of `self` and `other` by the IEEE 754
+    /// `totalOrderMag` predicate.
+    fn total_cmp_abs(&self, other: Self) -> Ordering;
+
+    /// Returns the number of representable values between `self` and `other`.
+    fn ulp_distance(&self, other: Self) -> Option<Self::Bits>;
+
+    /// Returns the lesser of `self` and `other`, propagating NaN.
+    fn minimum(&self, other: Self) -> Self;
+
+    /// Returns the greater of `self` and `other`, propagating NaN.
+    fn maximum(&self, other: Self) -> Self;
+
+    /// Returns `mantissa × 2^exponent`, rounded according to `mode`.
+    fn from_ratio_rounded(mantissa: i128, exponent: i32, mode: RoundingMode) -> Self;
+
+    /// Decomposes `self` into `(mantissa, exponent)`, or returns `None` for infinities and NaN.
+    fn to_ratio(&self) -> Option<(i128, i32)>;
+
+    /// Returns `self + rhs` rounded according to `mode`, with the exception flags raised.
+    fn add_with_flags(&self, rhs: Self, mode: RoundingMode) -> (Self, StatusFlags);
+
+    /// Returns `self - rhs` rounded according to `mode`, with the exception flags raised.
+    fn sub_with_flags(&self, rhs: Self, mode: RoundingMode) -> (Self, StatusFlags);
+
+    /// Returns `self × rhs` rounded according to `mode`, with the exception flags raised.
+    fn mul_with_flags(&self, rhs: Self, mode: RoundingMode) -> (Self, StatusFlags);
+
+    /// Returns `self / rhs` rounded according to `mode`, with the exception flags raised.
+    fn div_with_flags(&self, rhs: Self, mode: RoundingMode) -> (Self, StatusFlags);
+
+    /// Returns the square root of `self` rounded according to `mode`, with the exception flags
+    /// raised.
+    fn sqrt_with_flags(&self, mode: RoundingMode) -> (Self, StatusFlags);
+
+    /// Raises `self` to an integer power rounded according to `mode`, with the exception flags
+    /// raised.
+    fn powi_with_flags(&self, n: i32, mode: RoundingMode) -> (Self, StatusFlags);
+
+    /// Converts `self` to the format `U` rounding according to `mode`, with the exception flags
+    /// raised.
+    fn convert_with_flags<U: FloatBits>(&self, mode: RoundingMode) -> (U, StatusFlags);
+
+    /// Returns an adapter that formats `self` in hexadecimal.
+    fn fmt_hex(&self) -> LowerHexFloat;
+}
+
+/// The format of a float type, for code that chooses or inspects the format at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum FloatFormat {
+    /// The format of [`F8E5M2`].
+    F8E5M2,
+
+    /// The format of [`BF16`].
+    BF16,
+
+    /// The format of [`F16`].
+    F16,
+
+    /// The format of [`F32`].
+    F32,
+
+    /// The format of [`F64`].
+    F64,
+
+    /// The format of [`F128`].
+    F128,
+}
+
+impl FloatFormat {
+    /// Returns the total number of bits.
+    pub const fn bits(self) -> usize {
+        match self {
+            Self::F8E5M2 => F8E5M2::BITS,
+            Self::BF16 => BF16::BITS,
+            Self::F16 => F16::BITS,
+            Self::F32 => F32::BITS,
+            Self::F64 => F64::BITS,
+            Self::F128 => F128::BITS,
+        }
+    }
+
+    /// Returns the number of bits in the exponent field.
+    pub const fn exp_bits(self) -> usize {
+        match self {
+            Self::F8E5M2 => F8E5M2::EXP_BITS,
+            Self::BF16 => BF16::EXP_BITS,
+            Self::F16 => F16::EXP_BITS,
+            Self::F32 => F32::EXP_BITS,
+            Self::F64 => F64::EXP_BITS,
+            Self::F128 => F128::EXP_BITS,
+        }
+    }
+
+    /// Returns the number of significant digits in base 2, counting the implicit leading 1.
+    pub const fn mantissa_digits(self) -> usize {
+        self.bits() - self.exp_bits()
+    }
+
+    /// Returns the name of the corresponding type, such as `"BF16"`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::F8E5M2 => "F8E5M2",
+            Self::BF16 => "BF16",
+            Self::F16 => "F16",
+            Self::F32 => "F32",
+            Self::F64 => "F64",
+            Self::F128 => "F128",
+        }
+    }
+
+    const fn soft(self) -> Format {
+        Format::new(self.bits(), self.exp_bits())
+    }
+}
+
+impl fmt::Display for FloatFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// The object-safe part of [`FloatBits`], for values whose format is known only at runtime.
+///
+/// A `&dyn DynFloat` can refer to a value of any format, so collections can mix formats.  The
+/// methods behave as the inherent methods of the same names.
+///
+/// This trait is sealed, like [`FloatBits`].
+///
+/// # Example
+///
+/// ```rust
+/// # use float_bits::{BF16, DynFloat, F64, F128, FloatFormat};
+///
+/// let xs: [&dyn DynFloat; 3] = [&BF16::ONE, &F64::from_float(0.1), &F128::NEG_INFINITY];
+/// let sum: f64 = xs.iter().filter(|x| x.is_finite()).map(|x| x.to_f64_lossy()).sum();
+/// assert_eq!(1.1, sum);
+/// assert_eq!(FloatFormat::F128, xs[2].format());
+/// assert_eq!("-inf", xs[2].to_string());
+/// ```
+pub trait DynFloat: sealed::Sealed + fmt::Debug + fmt::Display + Send + Sync {
+    /// Returns the format of `self`.
+    fn format(&self) -> FloatFormat;
+
+    /// Returns the raw bits, zero-extended to 128 bits.
+    fn to_bits_u128(&self) -> u128;
+
+    /// Returns the floating point category of the number.
+    fn classify(&self) -> FpCategory;
+
+    /// Returns `true` if `self` has a positive sign, including `+0.0`, positive NaNs, and `+∞`.
+    fn is_sign_positive(&self) -> bool;
+
+    /// Returns `true` if `self` has a negative sign, including `−0.0`, negative NaNs, and `−∞`.
+    fn is_sign_negative(&self) -> bool;
+
+    /// Returns `true` if `self` is either zero.
+    fn is_zero(&self) -> bool;
+
+    /// Returns `true` if `self` is subnormal.
+    fn is_subnormal(&self) -> bool;
+
+    /// Returns `true` if `self` is either infinity.
+    fn is_infinite(&self) -> bool;
+
+    /// Returns `true` if `self` is NaN.
+    fn is_nan(&self) -> bool;
+
+    /// Returns `true` if `self` is neither infinite nor NaN.
+    fn is_finite(&self) -> bool;
+
+    /// Converts `self` to `f64`, rounding to nearest with ties to even.
+    ///
+    /// The conversion is exact for every format but [`F128`].  NaNs keep their sign and the most
+    /// significant bits of their payloads.
+    fn to_f64_lossy(&self) -> f64 {
+        let fmt = self.format().soft();
+        f64::from_bits(convert(fmt, F64::FORMAT, self.to_bits_u128()) as u64)
+    }
+}
+
+pub(crate) mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! float_bits {
+    ($( $ty:ident / $u_ty:ident / $s_ty:ident ),*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl DynFloat for $ty {
+                fn format(&self) -> FloatFormat {
+                    FloatFormat::$ty
+                }
+
+                fn to_bits_u128(&self) -> u128 {
+                    self.bits as u128
+                }
+
+                fn classify(&self) -> FpCategory {
+                    $ty::classify(self)
+                }
+
+                fn is_sign_positive(&self) -> bool {
+                    $ty::is_sign_positive(self)
+                }
+
+                fn is_sign_negative(&self) -> bool {
+                    $ty::is_sign_negative(self)
+                }
+
+                fn is_zero(&self) -> bool {
+                    $ty::is_zero(self)
+                }
+
+                fn is_subnormal(&self) -> bool {
+                    $ty::is_subnormal(self)
+                }
+
+                fn is_infinite(&self) -> bool {
+                    $ty::is_infinite(self)
+                }
+
+                fn is_nan(&self) -> bool {
+                    $ty::is_nan(self)
+                }
+
+                fn is_finite(&self) -> bool {
+                    $ty::is_finite(self)
+                }
+            }
+
+            impl FloatBits for $ty {
+                type Bits = $u_ty;
+                type SignedBits = $s_ty;
+
+                const BITS: usize = $ty::BITS;
+                const EXP_BITS: usize = $ty::EXP_BITS;
+                const MANTISSA_DIGITS: usize = $ty::MANTISSA_DIGITS;
+                const ZERO: Self = $ty::ZERO;
+                const NEG_ZERO: Self = $ty::NEG_ZERO;
+                const ONE: Self = $ty::ONE;
+                const NEG_ONE: Self = $ty::NEG_ONE;
+                const INFINITY: Self = $ty::INFINITY;
+                const NEG_INFINITY: Self = $ty::NEG_INFINITY;
+                const QNAN: Self = $ty::QNAN;
+                const SNAN: Self = $ty::SNAN;
+                const MAX: Self = $ty::MAX;
+                const MIN: Self = $ty::MIN;
+                const MIN_POSITIVE: Self = $ty::MIN_POSITIVE;
+
+                fn from_bits(bits: $u_ty) -> Self {
+                    $ty::from_bits(bits)
+                }
+
+                fn to_bits(&self) -> $u_ty {
+                    $ty::to_bits(self)
+                }
+
+                fn classify(&self) -> FpCategory {
+                    $ty::classify(self)
+                }
+
+                fn is_sign_positive(&self) -> bool {
+                    $ty::is_sign_positive(self)
+                }
+
+                fn is_sign_negative(&self) -> bool {
+                    $ty::is_sign_negative(self)
+                }
+
+                fn is_zero(&self) -> bool {
+                    $ty::is_zero(self)
+                }
+
+                fn is_subnormal(&self) -> bool {
+                    $ty::is_subnormal(self)
+                }
+
+                fn is_normal(&self) -> bool {
+                    $ty::is_normal(self)
+                }
+
+                fn is_infinite(&self) -> bool {
+                    $ty::is_infinite(self)
+                }
+
+                fn is_nan(&self) -> bool {
+                    $ty::is_nan(self)
+                }
+
+                fn is_finite(&self) -> bool {
+                    $ty::is_finite(self)
+                }
+
+                fn abs(&self) -> Self {
+                    $ty::abs(self)
+                }
+
+                fn neg(&self) -> Self {
+                    $ty::neg(self)
+                }
+
+                fn signum(&self) -> Self {
+                    $ty::signum(self)
+                }
+
+                fn copysign(&self, sign: Self) -> Self {
+                    $ty::copysign(self, sign)
+                }
+
+                fn next_up(&self) -> Self {
+                    $ty::next_up(self)
+                }
+
+                fn next_down(&self) -> Self {
+                    $ty::next_down(self)
+                }
+
+                fn total_cmp(&self, other: Self) -> Ordering {
+                    $ty::total_cmp(self, other)
+                }
+
+                fn total_cmp_abs(&self, other: Self) -> Ordering {
+                    $ty::total_cmp_abs(self, other)
+                }
+
+                fn ulp_distance(&self, other: Self) -> Option<$u_ty> {
+                    $ty::ulp_distance(self, other)
+                }
+
+                fn minimum(&self, other: Self) -> Self {
+                    $ty::minimum(self, other)
+                }
+
+                fn maximum(&self, other: Self) -> Self {
+                    $ty::maximum(self, other)
+                }
+
+                fn from_ratio_rounded(mantissa: i128, exponent: i32, mode: RoundingMode) -> Self {
+                    $ty::from_ratio_rounded(mantissa, exponent, mode)
+                }
+
+                fn to_ratio(&self) -> Option<(i128, i32)> {
+                    $ty::to_ratio(self)
+                }
+
+                fn add_with_flags(&self, rhs: Self, mode: RoundingMode) -> (Self, StatusFlags) {
+                    $ty::add_with_flags(self, rhs, mode)
+                }
+
+                fn sub_with_flags(&self, rhs: Self, mode: RoundingMode) -> (Self, StatusFlags) {
+                    $ty::sub_with_flags(self, rhs, mode)
+                }
+
+                fn mul_with_flags(&self, rhs: Self, mode: RoundingMode) -> (Self, StatusFlags) {
+                    $ty::mul_with_flags(self, rhs, mode)
+                }
+
+                fn div_with_flags(&self, rhs: Self, mode: RoundingMode) -> (Self, StatusFlags) {
+                    $ty::div_with_flags(self, rhs, mode)
+                }
+
+                fn sqrt_with_flags(&self, mode: RoundingMode) -> (Self, StatusFlags) {
+                    $ty::sqrt_with_flags(self, mode)
+                }
+
+                fn powi_with_flags(&self, n: i32, mode: RoundingMode) -> (Self, StatusFlags) {
+                    $ty::powi_with_flags(self, n, mode)
+                }
+
+                fn convert_with_flags<U: FloatBits>(&self, mode: RoundingMode) -> (U, StatusFlags) {
+                    $ty::convert_with_flags(self, mode)
+                }
+
+                fn fmt_hex(&self) -> LowerHexFloat {
+                    $ty::fmt_hex(self)
+                }
+            }
+        )*
+    };
+}
+
+float_bits!(
+    F8E5M2 / u8 / i8,
+    BF16 / u16 / i16,
+    F16 / u16 / i16,
+    F32 / u32 / i32,
+    F64 / u64 / i64,
+    F128 / u128 / i128
+);
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::num::FpCategory;
+    use std::format;
+
+    use super::{DynFloat, FloatBits, FloatFormat};
+    use crate::{BF16, F16, F32, F64, F128};
+
+    fn check<T: FloatBits>() {
+        assert_eq!(T::ONE, T::from_bits(T::ONE.to_bits()));
+        assert_eq!(T::BITS, T::EXP_BITS + T::MANTISSA_DIGITS);
+        assert_eq!(T::MAX.next_up(), T::INFINITY);
+        assert_eq!(
+            T::ZERO.next_up(),
+            T::from_ratio_rounded(1, -1 << 20, crate::RoundingMode::TowardPositive)
+        );
+        assert_eq!(T::NEG_ZERO, T::ZERO.neg());
+        assert_eq!(T::MIN, T::MAX.neg());
+        assert!(T::QNAN.is_nan() && T::SNAN.is_nan());
+        assert_eq!(T::NEG_ONE, T::ONE.copysign(T::NEG_INFINITY));
+        assert_eq!(Some(1), T::ONE.ulp_distance(T::ONE.next_down()).map(Into::into));
+        assert_eq!(Some((1, 0)), T::ONE.to_ratio());
+        assert_eq!(T::ONE, T::MIN_POSITIVE.maximum(T::ONE).minimum(T::INFINITY));
+        assert!(T::NEG_INFINITY.next_up() == T::MIN && T::MIN.next_down() == T::NEG_INFINITY);
+        let mut xs = [T::ONE, T::NEG_ZERO, T::QNAN, T::MIN_POSITIVE, T::ZERO];
+        xs.sort_by(|a, b| a.total_cmp(*b));
+        assert_eq!([T::NEG_ZERO, T::ZERO, T::MIN_POSITIVE, T::ONE, T::QNAN], xs);
+    }
+
+    #[test]
+    fn float_bits_test() {
+        check::<BF16>();
+        check::<F16>();
+        check::<F32>();
+        check::<F64>();
+        check::<F128>();
+    }
+
+    #[test]
+    fn next_up_test() {
+        assert_eq!(F32::from_bits(1), F32::NEG_ZERO.next_up());
+        assert_eq!(F32::NEG_ZERO, F32::from_bits(0x80000001).next_up());
+        assert_eq!(F32::from_bits(0x80000001), F32::ZERO.next_down());
+        assert_eq!(F64::from_bits(0x3ff0000000000001), F64::ONE.next_up());
+        assert_eq!(F64::from_bits(0xbff0000000000001), F64::NEG_ONE.next_down());
+        assert_eq!(F16::INFINITY, F16::INFINITY.next_up());
+        assert_eq!(F16::MAX, F16::INFINITY.next_down());
+        assert_eq!(F16::NEG_INFINITY, F16::NEG_INFINITY.next_down());
+        assert_eq!(F16::SNAN.to_bits(), F16::SNAN.next_up().to_bits());
+        assert_eq!(F128::MIN_POSITIVE, F128::MIN_POSITIVE.next_down().next_up());
+    }
+
+    #[test]
+    fn dyn_float_test() {
+        let xs: [&dyn DynFloat; 5] = [
+            &BF16::from_bits(0x8001),
+            &F16::MAX,
+            &F32::from_float(0.1),
+            &F64::NEG_ZERO,
+            &F128::from_bits(F128::QNAN.bits | 0xffff << 96 | 1),
+        ];
+        let formats = xs.map(|x| x.format());
+        let expected = [
+            FloatFormat::BF16,
+            FloatFormat::F16,
+            FloatFormat::F32,
+            FloatFormat::F64,
+            FloatFormat::F128,
+        ];
+        assert_eq!(expected, formats);
+        assert_eq!([16, 16, 32, 64, 128], formats.map(FloatFormat::bits));
+        assert_eq!([8, 11, 24, 53, 113], formats.map(FloatFormat::mantissa_digits));
+
+        assert_eq!(FpCategory::Subnormal, xs[0].classify());
+        assert!(xs[0].is_sign_negative() && xs[3].is_zero() && xs[4].is_nan());
+        assert_eq!(-f32::from_bits(0x10000) as f64, xs[0].to_f64_lossy());
+        assert_eq!(65504.0, xs[1].to_f64_lossy());
+        assert_eq!(0.1f32 as f64, xs[2].to_f64_lossy());
+        assert_eq!(0x7ffffff000000000, xs[4].to_f64_lossy().to_bits());
+        assert_eq!(0x8000, xs[3].to_bits_u128() >> 48);
+        assert_eq!("0.1", format!("{}", xs[2]));
+        assert_eq!("F128", format!("{}", formats[4]));
+    }
+}
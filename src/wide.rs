@@ -0,0 +1,466 @@
+//! 256-bit software floats, used as the working precision for transcendental functions.
+//!
+//! These are deliberately simple: only finite values are represented, and every operation
+//! truncates its result to 256 significant bits.  That is far more precision than the widest
+//! supported format needs, so the accumulated error of a few dozen operations still vanishes when
+//! the result is rounded to the target format by [`crate::soft::round`].
+
+use crate::RoundingMode;
+use crate::soft::{self, Format, Kind, Unpacked};
+
+const LIMBS: usize = 4;
+
+type Limbs = [u64; LIMBS];
+
+/// A finite 256-bit float.
+///
+/// The value is `sig × 2^(exp − 255)`, where `sig` is stored least significant limb first and,
+/// unless the value is zero, has bit 255 set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Wide {
+    neg: bool,
+    exp: i32,
+    sig: Limbs,
+}
+
+/// `ln(2)`, truncated to 256 bits.
+const LN_2: Wide = Wide {
+    neg: false,
+    exp: -1,
+    sig: [0x8a0d175b8baafa2b, 0x40f343267298b62d, 0xc9e3b39803f2f6af, 0xb17217f7d1cf79ab],
+};
+
+const fn is_zero(a: &Limbs) -> bool {
+    let mut i = 0;
+    while i < LIMBS {
+        if a[i] != 0 {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn leading_zeros(a: &Limbs) -> u32 {
+    let mut i = LIMBS;
+    let mut n = 0;
+    while i > 0 {
+        i -= 1;
+        if a[i] != 0 {
+            return n + a[i].leading_zeros();
+        }
+        n += 64;
+    }
+    n
+}
+
+const fn shl(a: &Limbs, n: u32) -> Limbs {
+    let mut out = [0; LIMBS];
+    let (words, bits) = ((n / 64) as usize, n % 64);
+    let mut i = LIMBS;
+    while i > words {
+        i -= 1;
+        let src = i - words;
+        let mut v = a[src] << bits;
+        if bits != 0 && src > 0 {
+            v |= a[src - 1] >> (64 - bits);
+        }
+        out[i] = v;
+    }
+    out
+}
+
+const fn shr(a: &Limbs, n: u32) -> Limbs {
+    let mut out = [0; LIMBS];
+    let (words, bits) = ((n / 64) as usize, n % 64);
+    let mut i = 0;
+    while i + words < LIMBS {
+        let src = i + words;
+        let mut v = a[src] >> bits;
+        if bits != 0 && src + 1 < LIMBS {
+            v |= a[src + 1] << (64 - bits);
+        }
+        out[i] = v;
+        i += 1;
+    }
+    out
+}
+
+const fn cmp(a: &Limbs, b: &Limbs) -> core::cmp::Ordering {
+    use core::cmp::Ordering;
+    let mut i = LIMBS;
+    while i > 0 {
+        i -= 1;
+        if a[i] != b[i] {
+            return if a[i] < b[i] { Ordering::Less } else { Ordering::Greater };
+        }
+    }
+    Ordering::Equal
+}
+
+/// Computes `a + b`, returning the sum and the carry out.
+const fn add(a: &Limbs, b: &Limbs) -> (Limbs, bool) {
+    let mut out = [0; LIMBS];
+    let mut carry = false;
+    let mut i = 0;
+    while i < LIMBS {
+        let (s, c1) = a[i].overflowing_add(b[i]);
+        let (s, c2) = s.overflowing_add(carry as u64);
+        out[i] = s;
+        carry = c1 || c2;
+        i += 1;
+    }
+    (out, carry)
+}
+
+/// Computes `a − b`, wrapping on underflow.
+const fn sub(a: &Limbs, b: &Limbs) -> Limbs {
+    let mut out = [0; LIMBS];
+    let mut borrow = false;
+    let mut i = 0;
+    while i < LIMBS {
+        let (s, b1) = a[i].overflowing_sub(b[i]);
+        let (s, b2) = s.overflowing_sub(borrow as u64);
+        out[i] = s;
+        borrow = b1 || b2;
+        i += 1;
+    }
+    out
+}
+
+impl Wide {
+    pub(crate) const ZERO: Self = Self { neg: false, exp: 0, sig: [0; LIMBS] };
+
+    pub(crate) const ONE: Self = Self { neg: false, exp: 0, sig: [0, 0, 0, 1 << 63] };
+
+    const fn normalize(neg: bool, exp: i32, sig: Limbs) -> Self {
+        if is_zero(&sig) {
+            return Self::ZERO;
+        }
+        let lz = leading_zeros(&sig);
+        Self { neg, exp: exp - lz as i32, sig: shl(&sig, lz) }
+    }
+
+    /// Widens a finite, non-zero unpacked float.
+    pub(crate) const fn from_unpacked(value: Unpacked) -> Self {
+        let sig = [0, 0, value.sig as u64, (value.sig >> 64) as u64];
+        Self { neg: value.sign, exp: value.exp, sig }
+    }
+
+    pub(crate) const fn from_i64(n: i64) -> Self {
+        Self::normalize(n < 0, 255, [n.unsigned_abs(), 0, 0, 0])
+    }
+
+    /// Rounds to `fmt`, returning the raw bits.
+    pub(crate) const fn round(self, fmt: Format, mode: RoundingMode) -> u128 {
+        if self.is_zero() {
+            return soft::round(fmt, Unpacked::special(self.neg, Kind::Zero), false, mode);
+        }
+        let sig = ((self.sig[3] as u128) << 64) | self.sig[2] as u128;
+        let sticky = self.sig[1] != 0 || self.sig[0] != 0;
+        soft::round(fmt, Unpacked::finite(self.neg, self.exp, sig), sticky, mode)
+    }
+
+    pub(crate) const fn is_zero(&self) -> bool {
+        is_zero(&self.sig)
+    }
+
+    pub(crate) const fn is_negative(&self) -> bool {
+        self.neg && !self.is_zero()
+    }
+
+    /// Returns the exponent of the leading bit, i.e. `floor(log2(|self|))`.
+    pub(crate) const fn exponent(&self) -> i32 {
+        self.exp
+    }
+
+    pub(crate) const fn neg(self) -> Self {
+        Self { neg: !self.neg, ..self }
+    }
+
+    /// Multiplies by `2^n`.
+    pub(crate) const fn ldexp(self, n: i32) -> Self {
+        if self.is_zero() { self } else { Self { exp: self.exp + n, ..self } }
+    }
+
+    pub(crate) const fn add(self, rhs: Self) -> Self {
+        use core::cmp::Ordering;
+        if self.is_zero() {
+            return rhs;
+        }
+        if rhs.is_zero() {
+            return self;
+        }
+        let (big, small) = if self.exp > rhs.exp
+            || (self.exp == rhs.exp && !matches!(cmp(&self.sig, &rhs.sig), Ordering::Less))
+        {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+        let shift = (big.exp as i64 - small.exp as i64) as u64;
+        if shift >= 256 {
+            return big;
+        }
+        let small_sig = shr(&small.sig, shift as u32);
+        if big.neg == small.neg {
+            let (sum, carry) = add(&big.sig, &small_sig);
+            if carry {
+                let mut sig = shr(&sum, 1);
+                sig[LIMBS - 1] |= 1 << 63;
+                Self { neg: big.neg, exp: big.exp + 1, sig }
+            } else {
+                Self { neg: big.neg, exp: big.exp, sig: sum }
+            }
+        } else {
+            Self::normalize(big.neg, big.exp, sub(&big.sig, &small_sig))
+        }
+    }
+
+    pub(crate) const fn sub(self, rhs: Self) -> Self {
+        self.add(rhs.neg())
+    }
+
+    pub(crate) const fn mul(self, rhs: Self) -> Self {
+        if self.is_zero() || rhs.is_zero() {
+            return Self::ZERO;
+        }
+        let mut prod = [0u64; 2 * LIMBS];
+        let mut i = 0;
+        while i < LIMBS {
+            let mut carry: u128 = 0;
+            let mut j = 0;
+            while j < LIMBS {
+                let t = (self.sig[i] as u128) * (rhs.sig[j] as u128) + prod[i + j] as u128 + carry;
+                prod[i + j] = t as u64;
+                carry = t >> 64;
+                j += 1;
+            }
+            prod[i + LIMBS] = carry as u64;
+            i += 1;
+        }
+        let hi = [prod[4], prod[5], prod[6], prod[7]];
+        let neg = self.neg != rhs.neg;
+        let exp = self.exp + rhs.exp;
+        if (hi[LIMBS - 1] >> 63) != 0 {
+            Self { neg, exp: exp + 1, sig: hi }
+        } else {
+            let mut sig = shl(&hi, 1);
+            sig[0] |= prod[3] >> 63;
+            Self { neg, exp, sig }
+        }
+    }
+
+    pub(crate) const fn div(self, rhs: Self) -> Self {
+        use core::cmp::Ordering;
+        if self.is_zero() {
+            return Self::ZERO;
+        }
+        let ge = !matches!(cmp(&self.sig, &rhs.sig), Ordering::Less);
+        let (exp, steps) =
+            if ge { (self.exp - rhs.exp, 256) } else { (self.exp - rhs.exp - 1, 257) };
+        let mut rem = self.sig;
+        let mut carry = false;
+        let mut q = [0u64; LIMBS];
+        let mut i = 0;
+        while i < steps {
+            q = shl(&q, 1);
+            if carry || !matches!(cmp(&rem, &rhs.sig), Ordering::Less) {
+                rem = sub(&rem, &rhs.sig);
+                q[0] |= 1;
+            }
+            carry = (rem[LIMBS - 1] >> 63) != 0;
+            rem = shl(&rem, 1);
+            i += 1;
+        }
+        Self { neg: self.neg != rhs.neg, exp, sig: q }
+    }
+
+    /// Divides by a small positive integer.
+    pub(crate) const fn div_small(self, d: u64) -> Self {
+        let mut q = [0u64; LIMBS + 1];
+        let mut rem: u128 = 0;
+        let mut i = LIMBS + 1;
+        while i > 0 {
+            i -= 1;
+            let limb = if i == 0 { 0 } else { self.sig[i - 1] };
+            let cur = (rem << 64) | limb as u128;
+            q[i] = (cur / d as u128) as u64;
+            rem = cur % d as u128;
+        }
+        let top = [q[1], q[2], q[3], q[4]];
+        if is_zero(&top) {
+            return Self::ZERO;
+        }
+        let lz = leading_zeros(&top);
+        let mut sig = shl(&top, lz);
+        if lz != 0 {
+            sig[0] |= q[0] >> (64 - lz);
+        }
+        Self { neg: self.neg, exp: self.exp - lz as i32, sig }
+    }
+
+    /// Rounds to the nearest integer, ties away from zero.  `|self|` must be less than `2^62`.
+    pub(crate) const fn round_to_i64(self) -> i64 {
+        if self.is_zero() || self.exp < -1 {
+            return 0;
+        }
+        let shifted = shr(&self.sig, (254 - self.exp) as u32);
+        let mag = ((shifted[0] >> 1) + (shifted[0] & 1)) as i64;
+        if self.neg { -mag } else { mag }
+    }
+}
+
+/// Computes `e^x`.  Results too large or too small for any format saturate to a huge or tiny
+/// value, which the final rounding turns into infinity or zero.
+pub(crate) const fn exp(x: Wide) -> Wide {
+    if x.exponent() >= 20 {
+        let huge = Wide::ONE.ldexp(1 << 21);
+        return if x.is_negative() { Wide::ONE.ldexp(-(1 << 21)) } else { huge };
+    }
+
+    // Reduce to e^x = 2^k × e^r with |r| ≤ ln(2)/2.
+    let k = x.div(LN_2).round_to_i64();
+    let r = x.sub(Wide::from_i64(k).mul(LN_2));
+
+    // Taylor series for e^r.
+    let mut sum = Wide::ONE;
+    let mut term = Wide::ONE;
+    let mut n = 1;
+    loop {
+        term = term.mul(r).div_small(n);
+        if term.is_zero() || term.exponent() < sum.exponent() - 260 {
+            break;
+        }
+        sum = sum.add(term);
+        n += 1;
+    }
+    sum.ldexp(k as i32)
+}
+
+/// Computes `ln(x)` for positive `x`.
+pub(crate) const fn ln(x: Wide) -> Wide {
+    // Write x = m × 2^e with m in [√½, √2).
+    let mut e = x.exponent();
+    let mut m = x.ldexp(-e);
+    // √2 × 2^255, so that m > √2 exactly when its significand exceeds this.
+    const SQRT_2: Limbs =
+        [0xed17ac8583339915, 0x1d6f60ba893ba84c, 0x597d89b3754abe9f, 0xb504f333f9de6484];
+    if matches!(cmp(&m.sig, &SQRT_2), core::cmp::Ordering::Greater) {
+        m = m.ldexp(-1);
+        e += 1;
+    }
+
+    // ln(m) = 2 atanh(t) = 2 (t + t³/3 + t⁵/5 + …) where t = (m − 1) / (m + 1).
+    let t = m.sub(Wide::ONE).div(m.add(Wide::ONE));
+    let t2 = t.mul(t);
+    let mut sum = t;
+    let mut power = t;
+    let mut n = 3;
+    loop {
+        power = power.mul(t2);
+        let term = power.div_small(n);
+        if term.is_zero() || term.exponent() < sum.exponent() - 260 {
+            break;
+        }
+        sum = sum.add(term);
+        n += 2;
+    }
+    Wide::from_i64(e as i64).mul(LN_2).add(sum.ldexp(1))
+}
+
+/// Returns the default quiet NaN of `fmt`.
+const fn default_nan(fmt: Format) -> u128 {
+    fmt.exp_mask() | fmt.quiet_mask()
+}
+
+/// Computes `e^bits` in `fmt`.
+pub(crate) const fn exp_bits(fmt: Format, bits: u128, mode: RoundingMode) -> u128 {
+    let x = soft::unpack(fmt, bits);
+    match x.kind {
+        Kind::Nan => soft::quiet(fmt, bits),
+        Kind::Infinite if x.sign => 0,
+        Kind::Infinite => fmt.exp_mask(),
+        Kind::Zero => fmt.one(),
+        Kind::Finite => exp(Wide::from_unpacked(x)).round(fmt, mode),
+    }
+}
+
+/// Computes `ln(bits)` in `fmt`.
+pub(crate) const fn ln_bits(fmt: Format, bits: u128, mode: RoundingMode) -> u128 {
+    let x = soft::unpack(fmt, bits);
+    match x.kind {
+        Kind::Nan => soft::quiet(fmt, bits),
+        Kind::Zero => fmt.sign_mask() | fmt.exp_mask(),
+        _ if x.sign => default_nan(fmt),
+        Kind::Infinite => fmt.exp_mask(),
+        Kind::Finite => ln(Wide::from_unpacked(x)).round(fmt, mode),
+    }
+}
+
+/// Classifies a finite value as an integer, returning `(is_integer, is_odd)`.
+const fn integer_parity(x: Unpacked) -> (bool, bool) {
+    match x.kind {
+        Kind::Zero => (true, false),
+        Kind::Finite if x.exp < 0 => (false, false),
+        Kind::Finite if x.exp > 127 => (true, false),
+        Kind::Finite => {
+            let frac = if x.exp == 127 { 0 } else { x.sig << (x.exp + 1) };
+            let odd = ((x.sig >> (127 - x.exp)) & 1) != 0;
+            (frac == 0, frac == 0 && odd)
+        },
+        // Infinities are even integers as far as `pow` is concerned.
+        Kind::Infinite => (true, false),
+        Kind::Nan => (false, false),
+    }
+}
+
+/// Computes `x^y` in `fmt`, with the special cases of IEEE 754 `pow`.
+pub(crate) const fn pow_bits(fmt: Format, x_bits: u128, y_bits: u128, mode: RoundingMode) -> u128 {
+    let x = soft::unpack(fmt, x_bits);
+    let y = soft::unpack(fmt, y_bits);
+    if matches!(y.kind, Kind::Zero) || x_bits == fmt.one() {
+        return fmt.one();
+    }
+    if matches!(x.kind, Kind::Nan) {
+        return soft::quiet(fmt, x_bits);
+    }
+    if matches!(y.kind, Kind::Nan) {
+        return soft::quiet(fmt, y_bits);
+    }
+
+    let (y_int, y_odd) = integer_parity(y);
+    let sign_bit = if x.sign && y_odd { fmt.sign_mask() } else { 0 };
+    let inf = fmt.exp_mask();
+    match (x.kind, y.kind) {
+        (Kind::Zero, _) => {
+            if y.sign {
+                sign_bit | inf
+            } else {
+                sign_bit
+            }
+        },
+        (Kind::Infinite, _) => {
+            if y.sign {
+                sign_bit
+            } else {
+                sign_bit | inf
+            }
+        },
+        (_, Kind::Infinite) => {
+            if (x_bits & !fmt.sign_mask()) == fmt.one() {
+                fmt.one()
+            } else if (x.exp < 0) != y.sign {
+                0
+            } else {
+                inf
+            }
+        },
+        _ if x.sign && !y_int => default_nan(fmt),
+        _ => {
+            let x_abs = Wide::from_unpacked(Unpacked { sign: false, ..x });
+            let r = exp(Wide::from_unpacked(y).mul(ln(x_abs)));
+            if sign_bit != 0 { r.neg().round(fmt, mode) } else { r.round(fmt, mode) }
+        },
+    }
+}